@@ -1,17 +1,99 @@
-const SOURCE: &str = r#"
-fn main() {
-    a := 1 + 2
-    return a
-}
-"#;
+use std::path::{Path, PathBuf};
+use std::process::exit;
 
 use x_compiler::{
+    ast, checker, codegen,
     fmt::{Format, Formatter},
-    Parser,
+    resolver, Parser,
 };
 
+const USAGE: &str = r#"Usage: x <command> <file>
+
+Commands:
+  fmt <file>                  print the formatted source of the file
+  run <file>                  compile the file in memory and run its main function
+  build <file> [-o <output>]  compile the file into a native executable
+"#;
+
 fn main() {
-    let mut parser = Parser::new(SOURCE);
-    let file = parser.parse().unwrap();
-    println!("{}", file.fmt(&mut Formatter::default()));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+    let result = match args.as_slice() {
+        ["fmt", file] => fmt(Path::new(file)),
+        ["run", file] => run(Path::new(file)),
+        ["build", file] => build(Path::new(file), &Path::new(file).with_extension("")),
+        ["build", file, "-o", output] => build(Path::new(file), Path::new(output)),
+        _ => Err(USAGE.into()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        exit(1);
+    }
+}
+
+fn parse(path: &Path) -> Result<ast::File, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut parser = Parser::new(&source);
+    return parser
+        .parse()
+        .map_err(|e| format!("{}: {}", path.display(), e));
+}
+
+fn check(path: &Path) -> Result<ast::File, String> {
+    let mut file = parse(path)?;
+
+    let resolution = resolver::resolve(&file).map_err(|errors| {
+        errors
+            .iter()
+            .map(|e| format!("{}: {}", path.display(), e))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+    for warning in &resolution.warnings {
+        eprintln!("{}: warning: {}", path.display(), warning);
+    }
+
+    checker::check(&mut file).map_err(|errors| {
+        errors
+            .iter()
+            .map(|e| format!("{}: {}", path.display(), e))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+    return Ok(file);
+}
+
+fn fmt(path: &Path) -> Result<(), String> {
+    let file = parse(path)?;
+    print!("{}", file.fmt(&mut Formatter::default()));
+    return Ok(());
+}
+
+fn run(path: &Path) -> Result<(), String> {
+    let file = check(path)?;
+    let output = codegen::run(&file).map_err(|e| e.to_string())?;
+    match output {
+        codegen::Output::Unit => {}
+        // an `Err` is the error of the program, so it fails the command
+        codegen::Output::Err(_) => return Err(output.to_string()),
+        output => println!("{}", output),
+    }
+    return Ok(());
+}
+
+fn build(path: &Path, output: &Path) -> Result<(), String> {
+    let file = check(path)?;
+
+    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+    let bytes = codegen::build(&file, &name).map_err(|e| e.to_string())?;
+
+    // the object file is only an intermediate step, so it does not end up next to the executable
+    let object: PathBuf = std::env::temp_dir().join(format!("x-{}-{}.o", name, std::process::id()));
+    std::fs::write(&object, bytes).map_err(|e| format!("{}: {}", object.display(), e))?;
+    let result = codegen::link(&object, output).map_err(|e| e.to_string());
+    let _ = std::fs::remove_file(&object);
+
+    return result;
 }
@@ -13,10 +13,49 @@ pub enum ExpressionKind {
     Boolean(bool),
     Integer(String),
     Float(String),
+    /// Spelled as in the source, including the delimiters and escape sequences.
     String(String),
+    InterpolatedString(Vec<StringPart>),
+    /// (1, "a"), (x,)
+    Tuple(Vec<Expression>),
+    /// [1, 2, 3]
+    List(Vec<Expression>),
+    /// {"a": 1, "b": 2}
+    Map(Vec<MapEntry>),
+    /// Point{ x: 1, y: 2 }
+    Struct(StructExpression),
+    /// `none`, the absent value of an optional type.
+    NoneLiteral,
 
     Prefix(PrefixExpression),
+    Postfix(PostfixExpression),
     Infix(InfixExpression),
+    Call(CallExpression),
+    Member(MemberExpression),
+    Index(IndexExpression),
+    Match(MatchExpression),
+    Lambda(LambdaExpression),
+}
+
+impl Expression {
+    /// Returns the variable that is changed by assigning to the expression,
+    /// `a` for `a`, `a.b.0` and `a[i].b`, or `None` if the expression cannot be assigned to.
+    pub fn assigned_variable(&self) -> Option<&str> {
+        return match &self.kind {
+            ExpressionKind::Identifier(name) => Some(name),
+            ExpressionKind::Member(member) => member.object.assigned_variable(),
+            ExpressionKind::Index(index) => index.object.assigned_variable(),
+            _ => None,
+        };
+    }
+}
+
+/// Part of an interpolated string: "Hello {name}!"
+#[derive(Clone, Debug, PartialEq)]
+pub enum StringPart {
+    /// Spelled as in the source, including escape sequences.
+    Literal(String),
+    Expression(Expression),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -25,9 +64,79 @@ pub struct PrefixExpression {
     pub right: Box<Expression>,
 }
 
+/// `result?`, which unwraps a result or returns its error from the enclosing function
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostfixExpression {
+    pub left: Box<Expression>,
+    pub operator: Token,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct InfixExpression {
     pub left: Box<Expression>,
     pub operator: Token,
     pub right: Box<Expression>,
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallExpression {
+    pub callee: Box<Expression>,
+    pub arguments: Vec<Expression>,
+}
+
+/// Access of a member: `pair.0`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemberExpression {
+    pub object: Box<Expression>,
+    pub name: String,
+}
+
+/// a[i]
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexExpression {
+    pub object: Box<Expression>,
+    pub index: Box<Expression>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructExpression {
+    pub name: String,
+    pub fields: Vec<FieldValue>,
+}
+
+/// Value of a field in a struct literal: `x: 1`
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldValue {
+    pub name: String,
+    pub value: Expression,
+}
+
+/// Entry of a map literal: `"a": 1`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapEntry {
+    pub key: Expression,
+    pub value: Expression,
+}
+
+/// `fn (x: int): int { return x + y }`, a function without a name that can use
+/// the variables of the functions it is written in, like `y`
+#[derive(Clone, Debug, PartialEq)]
+pub struct LambdaExpression {
+    pub typ: FunctionType,
+    pub body: Block,
+}
+
+/// match shape { Circle(r) => r * r, Rect(w, h) => w * h }
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchExpression {
+    pub subject: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Expression,
+    /// From the start of the pattern to the end of the body.
+    pub position: Span,
+}
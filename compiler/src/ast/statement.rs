@@ -13,7 +13,13 @@ pub enum StatementKind {
     Assignment(Assignment),
     Block(Block),
     FunctionDeclaration(FunctionDeclaration),
+    TypeDeclaration(TypeDeclaration),
     Return(Return),
+    If(If),
+    While(While),
+    For(For),
+    Break,
+    Continue,
     Expression(Expression),
 }
 
@@ -25,18 +31,89 @@ pub struct Comment {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Declaration {
-    pub name: String,
+    pub pattern: Pattern,
     pub mutable: bool,
     pub value: Box<Expression>,
 }
 
+/// Names bound by a declaration or a match arm: `a := 1`, `(a, _) := (1, 2)`, `Circle(r) =>`
 #[derive(Clone, Debug, PartialEq)]
-pub struct Assignment {
+pub enum Pattern {
+    /// A name, which matches a variant without elements if there is one with the name.
+    Identifier(String),
+    /// `_`, which binds nothing.
+    Wildcard,
+    Tuple(Vec<Pattern>),
+    Variant(VariantPattern),
+}
+
+impl Pattern {
+    /// Returns the names bound by the pattern from left to right.
+    pub fn names(&self) -> Vec<&str> {
+        return match self {
+            Pattern::Identifier(name) => vec![name],
+            Pattern::Wildcard => vec![],
+            Pattern::Tuple(elements) => elements.iter().flat_map(Pattern::names).collect(),
+            Pattern::Variant(variant) => variant.elements.iter().flat_map(Pattern::names).collect(),
+        };
+    }
+}
+
+/// `Rect(w, _)`
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantPattern {
     pub name: String,
+    pub elements: Vec<Pattern>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assignment {
+    /// Variable or part of a variable that is assigned to: `a`, `a.b.0`, `a[i].b`
+    pub target: Expression,
+    /// Operator of a compound assignment like `a += 1`, which is `Token::Plus` in that case.
+    pub operator: Option<Token>,
     pub value: Box<Expression>,
 }
 
+/// `type Point = { x: int, y: int = 0 }` or `enum Shape { Circle(float), Rect(float, float) }`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeDeclaration {
+    pub name: String,
+    pub typ: Type,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Block {
     pub statements: Vec<Box<Statement>>,
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct If {
+    /// Pattern of `if x := maybe { ... }`, which is bound to the value of the optional
+    /// condition in the consequence if the value is not `none`.
+    pub binding: Option<Pattern>,
+    pub condition: Box<Expression>,
+    pub consequence: Block,
+    pub alternative: Option<Else>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Else {
+    /// else if condition { ... }
+    If(Box<If>),
+    /// else { ... }
+    Block(Block),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct While {
+    pub condition: Box<Expression>,
+    pub body: Block,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct For {
+    pub variable: String,
+    pub iterable: Box<Expression>,
+    pub body: Block,
+}
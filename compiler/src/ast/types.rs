@@ -2,7 +2,10 @@ use super::*;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Type {
+    /// A type that is not known yet, written as `_` in type positions.
     Unknown,
+    /// A type variable introduced by the checker while the type is being inferred.
+    Variable(usize),
     Name(String),
 
     Boolean,
@@ -11,8 +14,25 @@ pub enum Type {
     String,
 
     Tuple(TupleType),
+    /// [T]
+    List(Box<Type>),
+    /// {K: V}
+    Map(MapType),
     Struct(StructType),
+    /// enum { Circle(float), Empty }
+    Enum(EnumType),
     Function(FunctionType),
+    /// T?, a value of type T or `none`
+    Optional(Box<Type>),
+    /// T!E, `Ok` with a value of type T or `Err` with an error of type E
+    Result(ResultType),
+}
+
+impl Type {
+    /// The type of expressions without a value, written as the empty tuple `()`.
+    pub fn unit() -> Self {
+        return Type::Tuple(TupleType { elements: vec![] });
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -20,6 +40,12 @@ pub struct TupleType {
     pub elements: Vec<Type>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapType {
+    pub key: Box<Type>,
+    pub value: Box<Type>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct StructType {
     pub fields: Vec<Field>,
@@ -32,6 +58,23 @@ pub struct Field {
     pub default: Option<Box<Expression>>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumType {
+    pub variants: Vec<Variant>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variant {
+    pub name: String,
+    pub elements: Vec<Type>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResultType {
+    pub value: Box<Type>,
+    pub error: Box<Type>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct FunctionType {
     pub parameters: Vec<Parameter>,
@@ -42,4 +85,5 @@ pub struct FunctionType {
 pub struct Parameter {
     pub name: String,
     pub typ: Type,
+    pub position: Span,
 }
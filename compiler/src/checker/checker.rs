@@ -0,0 +1,1222 @@
+mod error;
+mod exhaustive;
+mod infer;
+
+pub use error::*;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{self, Type};
+use crate::fmt::{Format, Formatter};
+use crate::lexer;
+use crate::resolver::{PRINT, RESULT_VARIANTS};
+use crate::token::{Span, Token};
+
+/// Infers the type of every binding and expression and checks that operators,
+/// assignments and return values are used with compatible types.
+///
+/// Types that are not written down (`:=` declarations, `_` in signatures) start out
+/// as type variables that are unified with the types of their uses. After checking,
+/// no `Type::Unknown` or `Type::Variable` is left in a successfully checked file.
+#[derive(Clone, Debug)]
+pub struct Checker {
+    scopes: Vec<HashMap<String, Type>>,
+    /// Declared types by their name.
+    types: HashMap<String, Type>,
+    return_type: Type,
+    errors: Vec<Error>,
+
+    /// Type each type variable is bound to, indexed by the variable.
+    substitution: Vec<Option<Type>>,
+    /// Operators applied to operands of a not yet inferred type, validated once checking is done.
+    deferred: Vec<(Token, Type, Span)>,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        return Checker {
+            scopes: vec![HashMap::new()],
+            types: HashMap::new(),
+            return_type: Type::unit(),
+            errors: vec![],
+
+            substitution: vec![],
+            deferred: vec![],
+        };
+    }
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Checks the file and fills in the `typ` of all of its expressions as well as
+    /// the omitted types of function signatures.
+    pub fn check(&mut self, file: &mut ast::File) -> Result<(), Vec<Error>> {
+        // types and functions can be used before their declaration at the top level
+        for statement in &file.statements {
+            if let ast::StatementKind::TypeDeclaration(declaration) = &statement.kind {
+                self.types
+                    .insert(declaration.name.clone(), declaration.typ.clone());
+                self.declare_variants(declaration);
+            }
+        }
+        for statement in &mut file.statements {
+            if let ast::StatementKind::FunctionDeclaration(function) = &mut statement.kind {
+                self.declare_function(function);
+            }
+        }
+
+        self.check_statements(&mut file.statements);
+        self.check_deferred();
+        self.apply_statements(&mut file.statements);
+
+        if self.errors.is_empty() {
+            return Ok(());
+        } else {
+            return Err(std::mem::take(&mut self.errors));
+        }
+    }
+
+    fn check_statements(&mut self, statements: &mut [Box<ast::Statement>]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_statement(&mut self, statement: &mut ast::Statement) {
+        use ast::StatementKind::*;
+
+        match &mut statement.kind {
+            Comment(_) => {}
+            Declaration(declaration) => {
+                let typ = self.check_expression(&mut declaration.value);
+                self.check_pattern(&declaration.pattern, typ, false, &statement.position);
+            }
+            Assignment(assignment) => {
+                let mut got = self.check_expression(&mut assignment.value);
+                let want = self.check_expression(&mut assignment.target);
+                self.check_assignable(&assignment.target);
+                if let Some(operator) = &assignment.operator {
+                    got = self.check_infix(operator, want.clone(), got, &statement.position);
+                }
+                self.expect_type(&want, &got, &assignment.value.position);
+            }
+            Block(block) => self.check_block(block),
+            FunctionDeclaration(function) => self.check_function(function, &statement.position),
+            TypeDeclaration(declaration) => {
+                self.check_type_declaration(declaration, &statement.position);
+            }
+            Return(ret) => {
+                let got = self.check_expression(&mut ret.value);
+                let want = self.return_type.clone();
+                self.expect_type(&want, &got, &ret.value.position);
+            }
+            If(i) => self.check_if(i),
+            While(w) => {
+                let condition = self.check_expression(&mut w.condition);
+                self.expect_type(&Type::Boolean, &condition, &w.condition.position);
+                self.check_block(&mut w.body);
+            }
+            For(f) => {
+                let iterable = self.check_expression(&mut f.iterable);
+                let element = self.element_type(&iterable, &f.iterable.position);
+
+                self.scopes.push(HashMap::new());
+                self.declare(&f.variable, element);
+                self.check_block(&mut f.body);
+                self.scopes.pop();
+            }
+            Break | Continue => {}
+            Expression(expression) => {
+                self.check_expression(expression);
+            }
+        }
+    }
+
+    fn check_block(&mut self, block: &mut ast::Block) {
+        self.scopes.push(HashMap::new());
+        self.check_statements(&mut block.statements);
+        self.scopes.pop();
+    }
+
+    fn check_if(&mut self, i: &mut ast::If) {
+        let condition = self.check_expression(&mut i.condition);
+        match &i.binding {
+            Some(binding) => {
+                let value = self.unwrap_optional(&condition, &i.condition.position);
+
+                self.scopes.push(HashMap::new());
+                self.check_pattern(binding, value, false, &i.condition.position);
+                self.check_block(&mut i.consequence);
+                self.scopes.pop();
+            }
+            None => {
+                self.expect_type(&Type::Boolean, &condition, &i.condition.position);
+                self.check_block(&mut i.consequence);
+            }
+        }
+        match &mut i.alternative {
+            Some(ast::Else::If(alternative)) => self.check_if(alternative),
+            Some(ast::Else::Block(alternative)) => self.check_block(alternative),
+            None => {}
+        }
+    }
+
+    /// Returns the type of the elements produced by iterating over a value of the type.
+    /// Iterating over a string produces its characters as strings
+    /// and iterating over a map produces its entries as `(key, value)` tuples.
+    fn element_type(&mut self, iterable: &Type, position: &Span) -> Type {
+        return match self.expand(iterable) {
+            Type::String => Type::String,
+            Type::List(element) => *element,
+            Type::Map(map) => Type::Tuple(ast::TupleType {
+                elements: vec![*map.key, *map.value],
+            }),
+            Type::Unknown => Type::Unknown,
+            typ => {
+                self.errors.push(Error::NotIterable {
+                    typ,
+                    position: position.clone(),
+                });
+                Type::Unknown
+            }
+        };
+    }
+
+    /// Replaces the omitted types of the signature with type variables and declares the function.
+    fn declare_function(&mut self, function: &mut ast::FunctionDeclaration) {
+        for parameter in &mut function.typ.parameters {
+            self.instantiate(&mut parameter.typ);
+        }
+        if let Some(return_type) = &mut function.typ.return_type {
+            self.instantiate(return_type);
+        }
+
+        self.declare(&function.name, Type::Function(function.typ.clone()));
+    }
+
+    fn check_function(&mut self, function: &mut ast::FunctionDeclaration, position: &Span) {
+        // hoisted functions are already instantiated, so declaring them again has no effect
+        self.declare_function(function);
+        self.check_type(&Type::Function(function.typ.clone()), position);
+        self.check_body(&function.typ, &mut function.body, position);
+    }
+
+    /// Checks a lambda like a function declaration and returns its type. The locals of the
+    /// enclosing functions stay declared, so the body can use the ones it captures.
+    fn check_lambda(&mut self, lambda: &mut ast::LambdaExpression, position: &Span) -> Type {
+        for parameter in &mut lambda.typ.parameters {
+            self.instantiate(&mut parameter.typ);
+        }
+        if let Some(return_type) = &mut lambda.typ.return_type {
+            self.instantiate(return_type);
+        }
+
+        let typ = Type::Function(lambda.typ.clone());
+        self.check_type(&typ, position);
+        self.check_body(&lambda.typ, &mut lambda.body, position);
+        return typ;
+    }
+
+    /// Checks the body of a function with its parameters declared.
+    fn check_body(&mut self, typ: &ast::FunctionType, body: &mut ast::Block, position: &Span) {
+        let return_type = match &typ.return_type {
+            Some(typ) => (**typ).clone(),
+            None => Type::unit(),
+        };
+        let caller = std::mem::replace(&mut self.return_type, return_type);
+
+        self.scopes.push(HashMap::new());
+        for parameter in &typ.parameters {
+            self.declare(&parameter.name, parameter.typ.clone());
+        }
+        self.check_statements(&mut body.statements);
+        self.scopes.pop();
+
+        // a return type that is still unknown has already been reported
+        let return_type = std::mem::replace(&mut self.return_type, caller);
+        let return_type = self.resolve(&return_type);
+        let expanded = self.expand(&return_type);
+        let known = !matches!(expanded, Type::Unknown | Type::Variable(_));
+        if known && expanded != Type::unit() && !returns(&body.statements) {
+            self.errors.push(Error::MissingReturn {
+                typ: return_type,
+                position: position.clone(),
+            });
+        }
+    }
+
+    /// Reports map keys of types that cannot be hashed. Unresolved and undefined types
+    /// are reported elsewhere.
+    pub(super) fn check_key(&mut self, key: &Type, position: &Span) {
+        match self.expand(key) {
+            Type::Boolean
+            | Type::Integer
+            | Type::String
+            | Type::Unknown
+            | Type::Variable(_)
+            | Type::Name(_) => {}
+            typ => self.errors.push(Error::InvalidKey {
+                typ,
+                position: position.clone(),
+            }),
+        }
+    }
+
+    /// Checks the field types and the default values of a declared struct type.
+    fn check_type_declaration(&mut self, declaration: &mut ast::TypeDeclaration, position: &Span) {
+        self.types
+            .insert(declaration.name.clone(), declaration.typ.clone());
+        self.declare_variants(declaration);
+        self.check_type(&declaration.typ, position);
+
+        // default values are evaluated in the scope of the file
+        let locals = self.scopes.split_off(1);
+        if let Type::Struct(structure) = &mut declaration.typ {
+            for field in &mut structure.fields {
+                if let Some(default) = &mut field.default {
+                    let got = self.check_expression(default);
+                    self.expect_type(&field.typ, &got, &default.position);
+                }
+            }
+        }
+        self.scopes.extend(locals);
+        // the checked defaults are kept for applying the inferred types
+        self.types
+            .insert(declaration.name.clone(), declaration.typ.clone());
+    }
+
+    /// Declares the variants of an enum type as values of the type.
+    /// Variants with elements are functions that take the elements.
+    fn declare_variants(&mut self, declaration: &ast::TypeDeclaration) {
+        let ast::Type::Enum(e) = &declaration.typ else {
+            return;
+        };
+        for variant in &e.variants {
+            let typ = Type::Name(declaration.name.clone());
+            if variant.elements.is_empty() {
+                self.declare(&variant.name, typ);
+            } else {
+                let parameters = variant
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, element)| ast::Parameter {
+                        name: i.to_string(),
+                        typ: element.clone(),
+                        position: Span::new(),
+                    })
+                    .collect();
+                let constructor = Type::Function(ast::FunctionType {
+                    parameters,
+                    return_type: Some(Box::new(typ)),
+                });
+                self.declare(&variant.name, constructor);
+            }
+        }
+    }
+
+    /// Returns the name of the enum type and the variant if the name refers to a variant.
+    fn variant(&self, name: &str) -> Option<(String, ast::Variant)> {
+        let enumeration = match self.lookup(name)? {
+            Type::Name(enumeration) => enumeration,
+            Type::Function(ast::FunctionType {
+                return_type: Some(typ),
+                ..
+            }) => match *typ {
+                Type::Name(enumeration) => enumeration,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let Some(Type::Enum(e)) = self.types.get(&enumeration) else {
+            return None;
+        };
+        let variant = e.variants.iter().find(|variant| variant.name == name)?;
+        return Some((enumeration, variant.clone()));
+    }
+
+    /// Returns whether the name refers to a variant of an enum or of the built-in result type.
+    pub(super) fn is_variant(&self, name: &str) -> bool {
+        return self.variant(name).is_some() || self.is_result_variant(name);
+    }
+
+    /// Returns whether the name refers to `Ok` or `Err` of the built-in result type,
+    /// which a file can shadow by declaring its own.
+    fn is_result_variant(&self, name: &str) -> bool {
+        return RESULT_VARIANTS.contains(&name) && self.lookup(name).is_none();
+    }
+
+    /// Returns the type of the values of the variant and the types of its elements.
+    /// `Ok` and `Err` are variants of a result with new type variables for its types.
+    fn variant_type(&mut self, name: &str) -> Option<(Type, Vec<Type>)> {
+        if let Some((enumeration, variant)) = self.variant(name) {
+            return Some((Type::Name(enumeration), variant.elements));
+        }
+        if !self.is_result_variant(name) {
+            return None;
+        }
+
+        let (value, error) = (self.fresh(), self.fresh());
+        let element = match name {
+            "Ok" => value.clone(),
+            _ => error.clone(),
+        };
+        let result = Type::Result(ast::ResultType {
+            value: Box::new(value),
+            error: Box::new(error),
+        });
+        return Some((result, vec![element]));
+    }
+
+    /// Reports names in the type that are not declared types and fields that are declared twice.
+    fn check_type(&mut self, typ: &Type, position: &Span) {
+        match typ {
+            Type::Name(name) if !self.types.contains_key(name) => {
+                self.errors.push(Error::UndefinedType {
+                    name: name.clone(),
+                    position: position.clone(),
+                });
+            }
+            Type::Tuple(tuple) => {
+                for element in &tuple.elements {
+                    self.check_type(element, position);
+                }
+            }
+            Type::List(element) | Type::Optional(element) => self.check_type(element, position),
+            Type::Result(result) => {
+                self.check_type(&result.value, position);
+                self.check_type(&result.error, position);
+            }
+            Type::Map(map) => {
+                self.check_type(&map.key, position);
+                self.check_key(&map.key, position);
+                self.check_type(&map.value, position);
+            }
+            Type::Struct(structure) => {
+                for (i, field) in structure.fields.iter().enumerate() {
+                    if structure.fields[..i].iter().any(|f| f.name == field.name) {
+                        self.errors.push(Error::DuplicateField {
+                            name: field.name.clone(),
+                            position: position.clone(),
+                        });
+                    }
+                    self.check_type(&field.typ, position);
+                }
+            }
+            Type::Enum(e) => {
+                for (i, variant) in e.variants.iter().enumerate() {
+                    if e.variants[..i].iter().any(|v| v.name == variant.name) {
+                        self.errors.push(Error::DuplicateVariant {
+                            name: variant.name.clone(),
+                            position: position.clone(),
+                        });
+                    }
+                    for element in &variant.elements {
+                        self.check_type(element, position);
+                    }
+                }
+            }
+            Type::Function(function) => {
+                for parameter in &function.parameters {
+                    self.check_type(&parameter.typ, position);
+                }
+                if let Some(return_type) = &function.return_type {
+                    self.check_type(return_type, position);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_expression(&mut self, expression: &mut ast::Expression) -> Type {
+        use ast::ExpressionKind::*;
+
+        let typ = match &mut expression.kind {
+            Identifier(name) => match self.lookup(name) {
+                Some(typ) => typ,
+                None if self.is_result_variant(name) => {
+                    let (result, elements) = self.variant_type(name).expect("result variant");
+                    Type::Function(ast::FunctionType {
+                        parameters: vec![ast::Parameter {
+                            name: "0".into(),
+                            typ: elements[0].clone(),
+                            position: Span::new(),
+                        }],
+                        return_type: Some(Box::new(result)),
+                    })
+                }
+                None if name == PRINT => Type::Function(ast::FunctionType {
+                    parameters: vec![ast::Parameter {
+                        name: "s".into(),
+                        typ: Type::String,
+                        position: Span::new(),
+                    }],
+                    return_type: None,
+                }),
+                None => {
+                    self.errors.push(Error::UndefinedName {
+                        name: name.clone(),
+                        position: expression.position.clone(),
+                    });
+                    Type::Unknown
+                }
+            },
+            Boolean(_) => Type::Boolean,
+            Integer(i) => {
+                if lexer::integer_value(i).is_none() {
+                    self.errors.push(Error::IntegerOverflow {
+                        literal: i.clone(),
+                        position: expression.position.clone(),
+                    });
+                }
+                Type::Integer
+            }
+            Float(_) => Type::Float,
+            String(_) => Type::String,
+            NoneLiteral => Type::Optional(Box::new(self.fresh())),
+            InterpolatedString(parts) => {
+                for part in parts {
+                    if let ast::StringPart::Expression(expression) = part {
+                        self.check_interpolation(expression);
+                    }
+                }
+                Type::String
+            }
+            Tuple(elements) => Type::Tuple(ast::TupleType {
+                elements: elements
+                    .iter_mut()
+                    .map(|element| self.check_expression(element))
+                    .collect(),
+            }),
+            List(elements) => {
+                let typ = self.fresh();
+                for element in elements {
+                    let got = self.check_expression(element);
+                    self.expect_type(&typ, &got, &element.position);
+                }
+                Type::List(Box::new(typ))
+            }
+            Map(entries) => {
+                let (key, value) = (self.fresh(), self.fresh());
+                let mut keys = HashSet::new();
+                for entry in entries {
+                    if let Some(literal) = key_literal(&entry.key) {
+                        if !keys.insert(literal) {
+                            self.errors.push(Error::DuplicateKey {
+                                key: entry.key.fmt(&mut Formatter::default()),
+                                position: entry.key.position.clone(),
+                            });
+                        }
+                    }
+                    let got = self.check_expression(&mut entry.key);
+                    self.expect_type(&key, &got, &entry.key.position);
+                    let got = self.check_expression(&mut entry.value);
+                    self.expect_type(&value, &got, &entry.value.position);
+                }
+                Type::Map(ast::MapType {
+                    key: Box::new(key),
+                    value: Box::new(value),
+                })
+            }
+
+            Prefix(prefix) => {
+                let operand = match (&prefix.operator, &prefix.right.kind) {
+                    // the literal of `-9223372036854775808` alone does not fit into an `int`
+                    (Token::Minus, Integer(i)) if lexer::negated_integer_value(i).is_some() => {
+                        prefix.right.typ = Type::Integer;
+                        Type::Integer
+                    }
+                    _ => self.check_expression(&mut prefix.right),
+                };
+                self.check_prefix(&prefix.operator, operand, &expression.position)
+            }
+            Postfix(postfix) => self.check_propagation(postfix, &expression.position),
+            Infix(infix) if infix.operator == Token::Coalesce => {
+                let left = self.check_expression(&mut infix.left);
+                let right = self.check_expression(&mut infix.right);
+                let value = self.unwrap_optional(&left, &infix.left.position);
+                self.expect_type(&value, &right, &infix.right.position);
+                value
+            }
+            Infix(infix) => {
+                let left = self.check_expression(&mut infix.left);
+                let right = self.check_expression(&mut infix.right);
+                self.check_infix(&infix.operator, left, right, &expression.position)
+            }
+            Call(call) => self.check_call(call, &expression.position),
+            Member(member) => self.check_member(member, false, &expression.position),
+            Index(index) => self.check_index(index, &expression.position),
+            Struct(s) => self.check_struct(s, &expression.position),
+            Match(m) => self.check_match(m, &expression.position),
+            Lambda(lambda) => self.check_lambda(lambda, &expression.position),
+        };
+
+        expression.typ = typ.clone();
+        return typ;
+    }
+
+    /// Declares the names of the pattern with the types of the matching parts of `typ`.
+    /// Only `refutable` patterns, those of match arms, can match variants.
+    fn check_pattern(
+        &mut self,
+        pattern: &ast::Pattern,
+        typ: Type,
+        refutable: bool,
+        position: &Span,
+    ) {
+        match pattern {
+            ast::Pattern::Identifier(name) if refutable && self.is_variant(name) => {
+                self.check_variant_pattern(name, &[], typ, position)
+            }
+            ast::Pattern::Identifier(name) => self.declare(name, typ),
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Tuple(patterns) => {
+                let elements: Vec<Type> = patterns.iter().map(|_| self.fresh()).collect();
+                let tuple = Type::Tuple(ast::TupleType {
+                    elements: elements.clone(),
+                });
+
+                if !self.unify(&typ, &tuple) {
+                    self.errors.push(Error::InvalidDestructuring {
+                        elements: patterns.len(),
+                        typ: self.resolve(&typ),
+                        position: position.clone(),
+                    });
+                }
+                for (pattern, element) in patterns.iter().zip(elements) {
+                    self.check_pattern(pattern, element, refutable, position);
+                }
+            }
+            ast::Pattern::Variant(variant) => {
+                if !refutable {
+                    self.errors.push(Error::RefutablePattern {
+                        position: position.clone(),
+                    });
+                }
+                self.check_variant_pattern(&variant.name, &variant.elements, typ, position);
+            }
+        }
+    }
+
+    fn check_variant_pattern(
+        &mut self,
+        name: &str,
+        patterns: &[ast::Pattern],
+        typ: Type,
+        position: &Span,
+    ) {
+        let Some((variant, elements)) = self.variant_type(name) else {
+            self.errors.push(Error::UndefinedName {
+                name: name.into(),
+                position: position.clone(),
+            });
+            for pattern in patterns {
+                self.check_pattern(pattern, Type::Unknown, true, position);
+            }
+            return;
+        };
+
+        // unlike values, patterns of variants never match the optional of their type
+        if !self.unify(&typ, &variant) {
+            self.errors.push(Error::TypeMismatch {
+                want: self.resolve(&typ),
+                got: self.resolve(&variant),
+                position: position.clone(),
+            });
+        }
+        if patterns.len() != elements.len() {
+            self.errors.push(Error::ArgumentCount {
+                want: elements.len(),
+                got: patterns.len(),
+                position: position.clone(),
+            });
+        }
+        let elements = elements.into_iter().chain(std::iter::repeat(Type::Unknown));
+        for (pattern, element) in patterns.iter().zip(elements) {
+            self.check_pattern(pattern, element, true, position);
+        }
+    }
+
+    /// Returns the value of the result of `result?`. Its error must be one that
+    /// the enclosing function can return.
+    fn check_propagation(&mut self, postfix: &mut ast::PostfixExpression, position: &Span) -> Type {
+        let typ = self.check_expression(&mut postfix.left);
+
+        let (value, error) = (self.fresh(), self.fresh());
+        let result = Type::Result(ast::ResultType {
+            value: Box::new(value.clone()),
+            error: Box::new(error.clone()),
+        });
+        if !self.unify(&typ, &result) {
+            self.errors.push(Error::NotResult {
+                typ: self.resolve(&typ),
+                position: postfix.left.position.clone(),
+            });
+            return Type::Unknown;
+        }
+
+        let returned = Type::Result(ast::ResultType {
+            value: Box::new(self.fresh()),
+            error: Box::new(error.clone()),
+        });
+        let return_type = self.return_type.clone();
+        if !self.unify(&return_type, &returned) {
+            self.errors.push(Error::InvalidPropagation {
+                error: self.resolve(&error),
+                return_type: self.resolve(&return_type),
+                position: position.clone(),
+            });
+        }
+        return value;
+    }
+
+    /// Checks every arm with the names of its pattern declared and returns the type
+    /// that the bodies of all arms have in common.
+    fn check_match(&mut self, m: &mut ast::MatchExpression, position: &Span) -> Type {
+        let subject = self.check_expression(&mut m.subject);
+
+        let typ = if m.arms.is_empty() {
+            Type::unit()
+        } else {
+            self.fresh()
+        };
+        for arm in &mut m.arms {
+            self.scopes.push(HashMap::new());
+            self.check_pattern(&arm.pattern, subject.clone(), true, &arm.position);
+            let body = self.check_expression(&mut arm.body);
+            self.expect_type(&typ, &body, &arm.body.position);
+            self.scopes.pop();
+        }
+
+        if self.expand(&subject) != Type::Unknown {
+            let patterns: Vec<&ast::Pattern> = m.arms.iter().map(|arm| &arm.pattern).collect();
+            if let Some(missing) = self.missing_pattern(&patterns, &subject) {
+                self.errors.push(Error::NonExhaustiveMatch {
+                    missing,
+                    position: position.clone(),
+                });
+            }
+        }
+
+        return typ;
+    }
+
+    /// Returns the type of the member. Methods of lists and maps are only members
+    /// if they are called.
+    fn check_member(
+        &mut self,
+        member: &mut ast::MemberExpression,
+        called: bool,
+        position: &Span,
+    ) -> Type {
+        let object = self.check_expression(&mut member.object);
+
+        let typ = match self.expand(&object) {
+            Type::Unknown => return Type::Unknown,
+            Type::Variable(_) => {
+                // the members of a type cannot be inferred from their use
+                self.errors.push(Error::CannotInfer {
+                    position: member.object.position.clone(),
+                });
+                return Type::Unknown;
+            }
+            Type::Tuple(tuple) => member
+                .name
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| tuple.elements.get(index).cloned()),
+            Type::List(element) if called => list_method(&member.name, *element),
+            Type::Map(map) if called => map_method(&member.name, map),
+            Type::Struct(structure) => structure
+                .fields
+                .into_iter()
+                .find(|field| field.name == member.name)
+                .map(|field| field.typ),
+            _ => None,
+        };
+
+        return match typ {
+            Some(typ) => typ,
+            None => {
+                self.errors.push(Error::UndefinedMember {
+                    typ: self.resolve(&object),
+                    name: member.name.clone(),
+                    position: position.clone(),
+                });
+                Type::Unknown
+            }
+        };
+    }
+
+    fn check_index(&mut self, index: &mut ast::IndexExpression, position: &Span) -> Type {
+        let object = self.check_expression(&mut index.object);
+        let typ = self.check_expression(&mut index.index);
+
+        return match self.expand(&object) {
+            Type::Unknown => Type::Unknown,
+            Type::Variable(_) => {
+                // the element type cannot be inferred from indexing
+                self.errors.push(Error::CannotInfer {
+                    position: index.object.position.clone(),
+                });
+                Type::Unknown
+            }
+            // indexing a string produces the character at the index as a string
+            Type::String => {
+                self.expect_type(&Type::Integer, &typ, &index.index.position);
+                Type::String
+            }
+            Type::List(element) => {
+                self.expect_type(&Type::Integer, &typ, &index.index.position);
+                *element
+            }
+            Type::Map(map) => {
+                self.expect_type(&map.key, &typ, &index.index.position);
+                *map.value
+            }
+            _ => {
+                self.errors.push(Error::NotIndexable {
+                    typ: self.resolve(&object),
+                    position: position.clone(),
+                });
+                Type::Unknown
+            }
+        };
+    }
+
+    /// Reports assignments to the characters of a string, which cannot be changed in place.
+    fn check_assignable(&mut self, target: &ast::Expression) {
+        match &target.kind {
+            ast::ExpressionKind::Member(member) => self.check_assignable(&member.object),
+            ast::ExpressionKind::Index(index) => {
+                if self.expand(&index.object.typ) == Type::String {
+                    self.errors.push(Error::NotAssignable {
+                        typ: Type::String,
+                        position: target.position.clone(),
+                    });
+                }
+                self.check_assignable(&index.object);
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks the field values of a struct literal against the fields of its type.
+    /// Fields with a default value can be omitted.
+    fn check_struct(&mut self, s: &mut ast::StructExpression, position: &Span) -> Type {
+        let mut values = vec![];
+        for field in &mut s.fields {
+            values.push(self.check_expression(&mut field.value));
+        }
+
+        let structure = match self.types.get(&s.name).map(|typ| self.expand(typ)) {
+            Some(Type::Struct(structure)) => structure,
+            Some(typ) => {
+                self.errors.push(Error::NotStruct {
+                    typ,
+                    position: position.clone(),
+                });
+                return Type::Unknown;
+            }
+            None => {
+                self.errors.push(Error::UndefinedType {
+                    name: s.name.clone(),
+                    position: position.clone(),
+                });
+                return Type::Unknown;
+            }
+        };
+
+        for (i, (field, got)) in s.fields.iter().zip(&values).enumerate() {
+            if s.fields[..i].iter().any(|f| f.name == field.name) {
+                self.errors.push(Error::DuplicateField {
+                    name: field.name.clone(),
+                    position: field.value.position.clone(),
+                });
+            }
+            match structure.fields.iter().find(|f| f.name == field.name) {
+                Some(want) => self.expect_type(&want.typ, got, &field.value.position),
+                None => self.errors.push(Error::UndefinedMember {
+                    typ: Type::Name(s.name.clone()),
+                    name: field.name.clone(),
+                    position: field.value.position.clone(),
+                }),
+            }
+        }
+
+        for field in &structure.fields {
+            if field.default.is_none() && !s.fields.iter().any(|f| f.name == field.name) {
+                self.errors.push(Error::MissingField {
+                    name: field.name.clone(),
+                    position: position.clone(),
+                });
+            }
+        }
+
+        return Type::Name(s.name.clone());
+    }
+
+    /// Checks an expression embedded in a string, which has to be convertible to a string.
+    fn check_interpolation(&mut self, expression: &mut ast::Expression) {
+        let typ = self.check_expression(expression);
+        match self.expand(&typ) {
+            Type::Boolean | Type::Integer | Type::Float | Type::String => {}
+            Type::Unknown | Type::Variable(_) => {}
+            typ => self.errors.push(Error::InvalidInterpolation {
+                typ,
+                position: expression.position.clone(),
+            }),
+        }
+    }
+
+    fn check_prefix(&mut self, operator: &Token, operand: Type, position: &Span) -> Type {
+        match operator {
+            Token::ExclamationMark => self.unify(&operand, &Type::Boolean),
+            Token::Tilde => self.unify(&operand, &Type::Integer),
+            _ => true,
+        };
+
+        return match self.check_operand(operator, &operand, position) {
+            Ok(typ) => typ,
+            Err(operand) => {
+                self.errors.push(Error::InvalidOperand {
+                    operator: operator.clone(),
+                    operand,
+                    position: position.clone(),
+                });
+                Type::Unknown
+            }
+        };
+    }
+
+    fn check_infix(&mut self, operator: &Token, left: Type, right: Type, position: &Span) -> Type {
+        if matches!(operator, Token::And | Token::Or) {
+            self.unify(&left, &Type::Boolean);
+        } else if is_bitwise(operator) {
+            self.unify(&left, &Type::Integer);
+        }
+
+        if !self.unify(&left, &right) {
+            self.errors.push(Error::InvalidOperands {
+                operator: operator.clone(),
+                left: self.resolve(&left),
+                right: self.resolve(&right),
+                position: position.clone(),
+            });
+            return Type::Unknown;
+        }
+
+        return match self.check_operand(operator, &left, position) {
+            Ok(_) if matches!(operator, Token::Equal | Token::NotEqual) => Type::Boolean,
+            Ok(_) if is_comparison(operator) => Type::Boolean,
+            Ok(typ) => typ,
+            Err(typ) => {
+                self.errors.push(Error::InvalidOperands {
+                    operator: operator.clone(),
+                    left: typ.clone(),
+                    right: typ,
+                    position: position.clone(),
+                });
+                Type::Unknown
+            }
+        };
+    }
+
+    fn check_call(&mut self, call: &mut ast::CallExpression, position: &Span) -> Type {
+        let callee = match &mut call.callee.kind {
+            ast::ExpressionKind::Member(member) => {
+                let typ = self.check_member(member, true, &call.callee.position);
+                call.callee.typ = typ.clone();
+                typ
+            }
+            _ => self.check_expression(&mut call.callee),
+        };
+        let mut arguments = vec![];
+        for argument in &mut call.arguments {
+            arguments.push(self.check_expression(argument));
+        }
+
+        return match self.expand(&callee) {
+            Type::Function(function) => {
+                if function.parameters.len() != arguments.len() {
+                    self.errors.push(Error::ArgumentCount {
+                        want: function.parameters.len(),
+                        got: arguments.len(),
+                        position: position.clone(),
+                    });
+                }
+                for ((parameter, argument), expression) in function
+                    .parameters
+                    .iter()
+                    .zip(&arguments)
+                    .zip(&call.arguments)
+                {
+                    self.expect_type(&parameter.typ, argument, &expression.position);
+                }
+
+                match function.return_type {
+                    Some(typ) => *typ,
+                    None => Type::unit(),
+                }
+            }
+            // the callee is inferred to be a function that accepts the arguments
+            Type::Variable(_) => {
+                let return_type = self.fresh();
+                let function = Type::Function(ast::FunctionType {
+                    parameters: arguments
+                        .into_iter()
+                        .map(|typ| ast::Parameter {
+                            name: String::new(),
+                            typ,
+                            position: Span::new(),
+                        })
+                        .collect(),
+                    return_type: Some(Box::new(return_type.clone())),
+                });
+                self.unify(&callee, &function);
+                return_type
+            }
+            Type::Unknown => Type::Unknown,
+            typ => {
+                self.errors.push(Error::NotCallable {
+                    typ,
+                    position: call.callee.position.clone(),
+                });
+                Type::Unknown
+            }
+        };
+    }
+
+    /// Returns the type of the operand if the operator accepts it and the rejected type otherwise.
+    /// The check is deferred until the end if the operand type is not inferred yet.
+    fn check_operand(
+        &mut self,
+        operator: &Token,
+        operand: &Type,
+        position: &Span,
+    ) -> Result<Type, Type> {
+        let typ = self.expand(operand);
+        return match typ {
+            Type::Unknown => Ok(Type::Unknown),
+            Type::Variable(_) => {
+                self.deferred
+                    .push((operator.clone(), typ.clone(), position.clone()));
+                Ok(typ)
+            }
+            typ if accepts(operator, &typ) => Ok(typ),
+            typ => Err(typ),
+        };
+    }
+
+    fn check_deferred(&mut self) {
+        for (operator, typ, position) in std::mem::take(&mut self.deferred) {
+            let typ = self.expand(&typ);
+            if !matches!(typ, Type::Variable(_)) && !accepts(&operator, &typ) {
+                self.errors.push(Error::InvalidOperands {
+                    operator,
+                    left: typ.clone(),
+                    right: typ,
+                    position,
+                });
+            }
+        }
+    }
+
+    /// Returns the type of the value of an optional type. Reports types that are not optional.
+    fn unwrap_optional(&mut self, optional: &Type, position: &Span) -> Type {
+        let value = self.fresh();
+        if !self.unify(optional, &Type::Optional(Box::new(value.clone()))) {
+            self.errors.push(Error::NotOptional {
+                typ: self.resolve(optional),
+                position: position.clone(),
+            });
+            return Type::Unknown;
+        }
+        return value;
+    }
+
+    /// Reports a type mismatch if the types cannot be unified. A value can be used
+    /// where an optional value of its type is expected, but not the other way around.
+    fn expect_type(&mut self, want: &Type, got: &Type, position: &Span) {
+        let unified = match self.expand(want) {
+            Type::Optional(value)
+                if !matches!(
+                    self.expand(got),
+                    Type::Optional(_) | Type::Variable(_) | Type::Unknown
+                ) =>
+            {
+                self.unify(&value, got)
+            }
+            _ => self.unify(want, got),
+        };
+        if !unified {
+            self.errors.push(Error::TypeMismatch {
+                want: self.resolve(want),
+                got: self.resolve(got),
+                position: position.clone(),
+            });
+        }
+    }
+
+    fn declare(&mut self, name: &str, typ: Type) {
+        self.scopes
+            .last_mut()
+            .expect("checker has no scope")
+            .insert(name.into(), typ);
+    }
+
+    fn lookup(&self, name: &str) -> Option<Type> {
+        return self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned();
+    }
+}
+
+/// Returns whether the operator is defined for operands of the type.
+fn accepts(operator: &Token, typ: &Type) -> bool {
+    return match operator {
+        Token::Plus => matches!(typ, Type::Integer | Type::Float | Type::String),
+        Token::Minus | Token::Asterisk | Token::Slash | Token::Percent => {
+            matches!(typ, Type::Integer | Type::Float)
+        }
+        operator if is_comparison(operator) => {
+            matches!(typ, Type::Integer | Type::Float | Type::String)
+        }
+        Token::ExclamationMark | Token::And | Token::Or => *typ == Type::Boolean,
+        operator if is_bitwise(operator) => *typ == Type::Integer,
+        Token::Equal | Token::NotEqual => true,
+        _ => false,
+    };
+}
+
+/// Returns the type of the method of a list with elements of the type.
+fn list_method(name: &str, element: Type) -> Option<Type> {
+    let (parameters, return_type) = match name {
+        "len" => (vec![], Some(Type::Integer)),
+        "push" => (
+            vec![ast::Parameter {
+                name: "element".into(),
+                typ: element,
+                position: Span::new(),
+            }],
+            None,
+        ),
+        "pop" => (vec![], Some(element)),
+        _ => return None,
+    };
+    return Some(Type::Function(ast::FunctionType {
+        parameters,
+        return_type: return_type.map(Box::new),
+    }));
+}
+
+/// Returns whether executing the statements always ends with a `return`, so that the end
+/// of a function body can only be reached without one if the function has no return value.
+/// The arms of a match are expressions, so a match never returns from the function.
+fn returns(statements: &[Box<ast::Statement>]) -> bool {
+    return statements.iter().any(|statement| match &statement.kind {
+        ast::StatementKind::Return(_) => true,
+        ast::StatementKind::Block(block) => returns(&block.statements),
+        ast::StatementKind::If(i) => if_returns(i),
+        // a loop that never ends cannot reach the end of the function either
+        ast::StatementKind::While(w) => {
+            matches!(w.condition.kind, ast::ExpressionKind::Boolean(true))
+                && !breaks(&w.body.statements)
+        }
+        _ => false,
+    });
+}
+
+fn if_returns(i: &ast::If) -> bool {
+    return returns(&i.consequence.statements)
+        && match &i.alternative {
+            Some(ast::Else::If(alternative)) => if_returns(alternative),
+            Some(ast::Else::Block(alternative)) => returns(&alternative.statements),
+            None => false,
+        };
+}
+
+/// Returns whether the statements contain a `break` out of the loop they belong to.
+fn breaks(statements: &[Box<ast::Statement>]) -> bool {
+    return statements.iter().any(|statement| match &statement.kind {
+        ast::StatementKind::Break => true,
+        ast::StatementKind::Block(block) => breaks(&block.statements),
+        ast::StatementKind::If(i) => if_breaks(i),
+        _ => false,
+    });
+}
+
+fn if_breaks(i: &ast::If) -> bool {
+    return breaks(&i.consequence.statements)
+        || match &i.alternative {
+            Some(ast::Else::If(alternative)) => if_breaks(alternative),
+            Some(ast::Else::Block(alternative)) => breaks(&alternative.statements),
+            None => false,
+        };
+}
+
+/// Returns the value of a literal key of a map literal, which tells it apart
+/// from the other keys of the map, or `None` if the key is not a literal.
+fn key_literal(key: &ast::Expression) -> Option<String> {
+    return match &key.kind {
+        ast::ExpressionKind::Boolean(b) => Some(b.to_string()),
+        ast::ExpressionKind::Integer(i) => lexer::integer_value(i).map(|i| i.to_string()),
+        ast::ExpressionKind::String(s) => Some(format!("{:?}", lexer::string_value(s))),
+        _ => None,
+    };
+}
+
+/// Returns the type of the method of a map with keys and values of the types.
+fn map_method(name: &str, map: ast::MapType) -> Option<Type> {
+    let key = ast::Parameter {
+        name: "key".into(),
+        typ: *map.key,
+        position: Span::new(),
+    };
+    let (parameters, return_type) = match name {
+        "len" => (vec![], Type::Integer),
+        "contains" => (vec![key], Type::Boolean),
+        "remove" => (vec![key], *map.value),
+        _ => return None,
+    };
+    return Some(Type::Function(ast::FunctionType {
+        parameters,
+        return_type: Some(Box::new(return_type)),
+    }));
+}
+
+/// Returns whether the operator orders its operands.
+fn is_comparison(operator: &Token) -> bool {
+    return matches!(
+        operator,
+        Token::LessThan | Token::GreaterThan | Token::LessThanOrEqual | Token::GreaterThanOrEqual
+    );
+}
+
+/// Returns whether the operator works on the bits of integers.
+fn is_bitwise(operator: &Token) -> bool {
+    return matches!(
+        operator,
+        Token::Ampersand
+            | Token::Pipe
+            | Token::Caret
+            | Token::Tilde
+            | Token::ShiftLeft
+            | Token::ShiftRight
+    );
+}
+
+/// Checks the file and fills in the `typ` of all of its expressions.
+pub fn check(file: &mut ast::File) -> Result<(), Vec<Error>> {
+    return Checker::new().check(file);
+}
+
+#[cfg(test)]
+#[path = "tests/checker.rs"]
+mod tests;
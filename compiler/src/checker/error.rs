@@ -0,0 +1,292 @@
+use std::fmt;
+
+use crate::ast::Type;
+use crate::fmt::{Format, Formatter};
+use crate::token::{Span, Token};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    UndefinedName {
+        name: String,
+        position: Span,
+    },
+    TypeMismatch {
+        want: Type,
+        got: Type,
+        position: Span,
+    },
+    InvalidOperand {
+        operator: Token,
+        operand: Type,
+        position: Span,
+    },
+    InvalidOperands {
+        operator: Token,
+        left: Type,
+        right: Type,
+        position: Span,
+    },
+    CannotInfer {
+        position: Span,
+    },
+    NotIterable {
+        typ: Type,
+        position: Span,
+    },
+    NotCallable {
+        typ: Type,
+        position: Span,
+    },
+    InvalidInterpolation {
+        typ: Type,
+        position: Span,
+    },
+    ArgumentCount {
+        want: usize,
+        got: usize,
+        position: Span,
+    },
+    UndefinedMember {
+        typ: Type,
+        name: String,
+        position: Span,
+    },
+    InvalidDestructuring {
+        elements: usize,
+        typ: Type,
+        position: Span,
+    },
+    UndefinedType {
+        name: String,
+        position: Span,
+    },
+    NotStruct {
+        typ: Type,
+        position: Span,
+    },
+    MissingField {
+        name: String,
+        position: Span,
+    },
+    DuplicateField {
+        name: String,
+        position: Span,
+    },
+    NotIndexable {
+        typ: Type,
+        position: Span,
+    },
+    NotAssignable {
+        typ: Type,
+        position: Span,
+    },
+    DuplicateVariant {
+        name: String,
+        position: Span,
+    },
+    /// Pattern of a declaration that does not match every value, like `Circle(r) := shape`.
+    RefutablePattern {
+        position: Span,
+    },
+    /// `missing` is an example of a value that no arm matches, like `Rect(_, _)`.
+    NonExhaustiveMatch {
+        missing: String,
+        position: Span,
+    },
+    /// Value that is unwrapped with `??` or `if x := value` but cannot be `none`.
+    NotOptional {
+        typ: Type,
+        position: Span,
+    },
+    /// Value whose error is propagated with `?` but that is not a result.
+    NotResult {
+        typ: Type,
+        position: Span,
+    },
+    /// `?` in a function that cannot return the error, because it does not return
+    /// a result with the same type of error.
+    InvalidPropagation {
+        error: Type,
+        return_type: Type,
+        position: Span,
+    },
+    /// Key that is written twice in a map literal, like `"a"` in `{"a": 1, "a": 2}`.
+    DuplicateKey {
+        key: String,
+        position: Span,
+    },
+    /// Map with keys of a type that cannot be hashed. Keys are `bool`, `int` or `string`.
+    InvalidKey {
+        typ: Type,
+        position: Span,
+    },
+    /// Function with a return value whose body can end without returning one.
+    MissingReturn {
+        typ: Type,
+        position: Span,
+    },
+    /// Integer literal whose value does not fit into an `int`.
+    IntegerOverflow {
+        literal: String,
+        position: Span,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fmt = &mut Formatter::default();
+        match self {
+            Error::UndefinedName { name, position } => {
+                write!(f, "undefined name: {} @ {}", name, position)
+            }
+            Error::TypeMismatch {
+                want,
+                got,
+                position,
+            } => write!(
+                f,
+                "type mismatch: {} @ {} (expected: {})",
+                got.fmt(fmt),
+                position,
+                want.fmt(fmt)
+            ),
+            Error::InvalidOperand {
+                operator,
+                operand,
+                position,
+            } => write!(
+                f,
+                "invalid operand for {}: {} @ {}",
+                operator,
+                operand.fmt(fmt),
+                position
+            ),
+            Error::InvalidOperands {
+                operator,
+                left,
+                right,
+                position,
+            } => write!(
+                f,
+                "invalid operands for {}: {} and {} @ {}",
+                operator,
+                left.fmt(fmt),
+                right.fmt(fmt),
+                position
+            ),
+            Error::CannotInfer { position } => write!(f, "cannot infer type @ {}", position),
+            Error::NotIterable { typ, position } => {
+                write!(f, "cannot iterate over {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::NotCallable { typ, position } => {
+                write!(f, "cannot call {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::InvalidInterpolation { typ, position } => {
+                write!(f, "cannot interpolate {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::ArgumentCount {
+                want,
+                got,
+                position,
+            } => write!(
+                f,
+                "wrong number of arguments: {} @ {} (expected: {})",
+                got, position, want
+            ),
+            Error::UndefinedMember {
+                typ,
+                name,
+                position,
+            } => write!(
+                f,
+                "undefined member of {}: {} @ {}",
+                typ.fmt(fmt),
+                name,
+                position
+            ),
+            Error::InvalidDestructuring {
+                elements,
+                typ,
+                position,
+            } => write!(
+                f,
+                "cannot destructure {} into {} elements @ {}",
+                typ.fmt(fmt),
+                elements,
+                position
+            ),
+            Error::UndefinedType { name, position } => {
+                write!(f, "undefined type: {} @ {}", name, position)
+            }
+            Error::NotStruct { typ, position } => {
+                write!(f, "not a struct: {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::MissingField { name, position } => {
+                write!(f, "missing field: {} @ {}", name, position)
+            }
+            Error::DuplicateField { name, position } => {
+                write!(f, "duplicate field: {} @ {}", name, position)
+            }
+            Error::NotIndexable { typ, position } => {
+                write!(f, "cannot index {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::NotAssignable { typ, position } => {
+                write!(
+                    f,
+                    "cannot assign to element of {} @ {}",
+                    typ.fmt(fmt),
+                    position
+                )
+            }
+            Error::DuplicateVariant { name, position } => {
+                write!(f, "duplicate variant: {} @ {}", name, position)
+            }
+            Error::RefutablePattern { position } => {
+                write!(f, "pattern of declaration can fail to match @ {}", position)
+            }
+            Error::NonExhaustiveMatch { missing, position } => {
+                write!(f, "match is not exhaustive: {} @ {}", missing, position)
+            }
+            Error::NotOptional { typ, position } => {
+                write!(f, "not an optional: {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::NotResult { typ, position } => {
+                write!(f, "not a result: {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::InvalidPropagation {
+                error,
+                return_type,
+                position,
+            } => {
+                write!(
+                    f,
+                    "cannot propagate error of type {} from function returning {} @ {}",
+                    error.fmt(fmt),
+                    return_type.fmt(fmt),
+                    position
+                )
+            }
+            Error::DuplicateKey { key, position } => {
+                write!(f, "duplicate key: {} @ {}", key, position)
+            }
+            Error::InvalidKey { typ, position } => {
+                write!(f, "invalid map key type: {} @ {}", typ.fmt(fmt), position)
+            }
+            Error::MissingReturn { typ, position } => {
+                write!(
+                    f,
+                    "missing return of {} at the end of function @ {}",
+                    typ.fmt(fmt),
+                    position
+                )
+            }
+            Error::IntegerOverflow { literal, position } => {
+                write!(
+                    f,
+                    "integer literal out of range: {} @ {}",
+                    literal, position
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use super::*;
+
+/// Pattern reduced to the values it matches. Names that refer to variants are variants,
+/// all other names and `_` match anything.
+#[derive(Clone, Debug)]
+enum Case {
+    Any,
+    Tuple(Vec<Case>),
+    Variant(String, Vec<Case>),
+}
+
+impl Checker {
+    /// Returns an example of a value of the type that none of the patterns matches,
+    /// written as a pattern like `Rect(_, _)`, or `None` if the patterns are exhaustive.
+    pub(super) fn missing_pattern(&self, patterns: &[&ast::Pattern], typ: &Type) -> Option<String> {
+        let rows = patterns
+            .iter()
+            .map(|pattern| vec![self.case(pattern)])
+            .collect();
+        return self
+            .missing(rows, std::slice::from_ref(typ))
+            .map(|mut witness| witness.remove(0));
+    }
+
+    fn case(&self, pattern: &ast::Pattern) -> Case {
+        return match pattern {
+            ast::Pattern::Identifier(name) if self.is_variant(name) => {
+                Case::Variant(name.clone(), vec![])
+            }
+            ast::Pattern::Identifier(_) | ast::Pattern::Wildcard => Case::Any,
+            ast::Pattern::Tuple(elements) => {
+                Case::Tuple(elements.iter().map(|element| self.case(element)).collect())
+            }
+            ast::Pattern::Variant(variant) => Case::Variant(
+                variant.name.clone(),
+                variant.elements.iter().map(|e| self.case(e)).collect(),
+            ),
+        };
+    }
+
+    /// Returns values of the types that none of the rows matches, one for each column.
+    ///
+    /// The first column is split by the variants or the elements of its type if a row
+    /// matches them. Otherwise only the rows that match anything can cover it.
+    fn missing(&self, rows: Vec<Vec<Case>>, types: &[Type]) -> Option<Vec<String>> {
+        let Some((typ, rest)) = types.split_first() else {
+            if rows.is_empty() {
+                return Some(vec![]);
+            }
+            return None;
+        };
+
+        let variants = match self.expand(typ) {
+            // an enum without variants has no values
+            Type::Enum(e) if e.variants.is_empty() => return None,
+            Type::Enum(e) => e.variants,
+            Type::Result(result) => result_variants(result),
+            Type::Tuple(tuple) if rows.iter().any(|row| matches!(row[0], Case::Tuple(_))) => {
+                return self.missing_tuple(rows, tuple.elements, rest);
+            }
+            _ => vec![],
+        };
+
+        let unmatched = variants.iter().find(|variant| {
+            !rows
+                .iter()
+                .any(|row| matches!(&row[0], Case::Variant(name, _) if *name == variant.name))
+        });
+        if variants.is_empty() || unmatched.is_some() {
+            let rows = rows
+                .into_iter()
+                .filter(|row| matches!(row[0], Case::Any))
+                .map(|row| row[1..].to_vec())
+                .collect();
+            let mut witness = self.missing(rows, rest)?;
+            let head = match unmatched {
+                Some(variant) => {
+                    variant_pattern(&variant.name, vec!["_".into(); variant.elements.len()])
+                }
+                None => "_".into(),
+            };
+            witness.insert(0, head);
+            return Some(witness);
+        }
+
+        for variant in variants {
+            let count = variant.elements.len();
+            let rows = rows
+                .iter()
+                .filter_map(|row| specialize(row.clone(), Some(&variant.name), count))
+                .collect();
+            let types: Vec<Type> = variant.elements.iter().chain(rest).cloned().collect();
+
+            if let Some(mut witness) = self.missing(rows, &types) {
+                let elements = witness.drain(..count).collect();
+                witness.insert(0, variant_pattern(&variant.name, elements));
+                return Some(witness);
+            }
+        }
+        return None;
+    }
+
+    fn missing_tuple(
+        &self,
+        rows: Vec<Vec<Case>>,
+        elements: Vec<Type>,
+        rest: &[Type],
+    ) -> Option<Vec<String>> {
+        let count = elements.len();
+        let rows = rows
+            .into_iter()
+            .filter_map(|row| specialize(row, None, count))
+            .collect();
+        let types: Vec<Type> = elements.into_iter().chain(rest.iter().cloned()).collect();
+
+        let mut witness = self.missing(rows, &types)?;
+        let elements: Vec<String> = witness.drain(..count).collect();
+        let tuple = match elements.as_slice() {
+            [element] => format!("({},)", element),
+            _ => format!("({})", elements.join(", ")),
+        };
+        witness.insert(0, tuple);
+        return Some(witness);
+    }
+}
+
+/// Returns `Ok` and `Err` of the built-in result type as the variants of an enum.
+fn result_variants(result: ast::ResultType) -> Vec<ast::Variant> {
+    return vec![
+        ast::Variant {
+            name: "Ok".into(),
+            elements: vec![*result.value],
+        },
+        ast::Variant {
+            name: "Err".into(),
+            elements: vec![*result.error],
+        },
+    ];
+}
+
+fn variant_pattern(name: &str, elements: Vec<String>) -> String {
+    if elements.is_empty() {
+        return name.into();
+    }
+    return format!("{}({})", name, elements.join(", "));
+}
+
+/// Replaces the first case of the row by its `count` elements if it matches the variant,
+/// or the tuple if `variant` is `None`. Returns `None` if the row matches a different variant.
+fn specialize(mut row: Vec<Case>, variant: Option<&str>, count: usize) -> Option<Vec<Case>> {
+    let elements = match (row.remove(0), variant) {
+        (Case::Any, _) => vec![Case::Any; count],
+        (Case::Tuple(elements), None) => elements,
+        (Case::Variant(name, elements), Some(variant)) if name == variant => elements,
+        _ => return None,
+    };
+    // a wrong number of elements is reported when checking the pattern
+    if elements.len() != count {
+        return Some([vec![Case::Any; count], row].concat());
+    }
+    return Some([elements, row].concat());
+}
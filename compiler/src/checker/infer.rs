@@ -0,0 +1,332 @@
+use std::collections::HashSet;
+
+use super::*;
+
+impl Checker {
+    /// Returns a new type variable.
+    pub(super) fn fresh(&mut self) -> Type {
+        self.substitution.push(None);
+        return Type::Variable(self.substitution.len() - 1);
+    }
+
+    /// Replaces every `Type::Unknown` in the type with a new type variable.
+    pub(super) fn instantiate(&mut self, typ: &mut Type) {
+        match typ {
+            Type::Unknown => *typ = self.fresh(),
+            Type::Tuple(tuple) => {
+                for element in &mut tuple.elements {
+                    self.instantiate(element);
+                }
+            }
+            Type::List(element) | Type::Optional(element) => self.instantiate(element),
+            Type::Result(result) => {
+                self.instantiate(&mut result.value);
+                self.instantiate(&mut result.error);
+            }
+            Type::Map(map) => {
+                self.instantiate(&mut map.key);
+                self.instantiate(&mut map.value);
+            }
+            Type::Struct(structure) => {
+                for field in &mut structure.fields {
+                    self.instantiate(&mut field.typ);
+                }
+            }
+            Type::Function(function) => {
+                for parameter in &mut function.parameters {
+                    self.instantiate(&mut parameter.typ);
+                }
+                if let Some(return_type) = &mut function.return_type {
+                    self.instantiate(return_type);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the type with all bound type variables replaced by their types.
+    pub(super) fn resolve(&self, typ: &Type) -> Type {
+        let mut typ = typ.clone();
+        self.map_variables(&mut typ, &mut |_| {});
+        return typ;
+    }
+
+    /// Replaces bound type variables in place and calls `unbound` for every variable left.
+    fn map_variables(&self, typ: &mut Type, unbound: &mut dyn FnMut(usize)) {
+        match typ {
+            Type::Variable(variable) => match &self.substitution[*variable] {
+                Some(bound) => {
+                    *typ = bound.clone();
+                    self.map_variables(typ, unbound);
+                }
+                None => unbound(*variable),
+            },
+            Type::Tuple(tuple) => {
+                for element in &mut tuple.elements {
+                    self.map_variables(element, unbound);
+                }
+            }
+            Type::List(element) | Type::Optional(element) => self.map_variables(element, unbound),
+            Type::Result(result) => {
+                self.map_variables(&mut result.value, unbound);
+                self.map_variables(&mut result.error, unbound);
+            }
+            Type::Map(map) => {
+                self.map_variables(&mut map.key, unbound);
+                self.map_variables(&mut map.value, unbound);
+            }
+            Type::Struct(structure) => {
+                for field in &mut structure.fields {
+                    self.map_variables(&mut field.typ, unbound);
+                }
+            }
+            Type::Function(function) => {
+                for parameter in &mut function.parameters {
+                    self.map_variables(&mut parameter.typ, unbound);
+                }
+                if let Some(return_type) = &mut function.return_type {
+                    self.map_variables(return_type, unbound);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the resolved type with declared type names replaced by the types they stand for.
+    pub(super) fn expand(&self, typ: &Type) -> Type {
+        let mut typ = self.resolve(typ);
+        // the limit stops at aliases that refer to themselves
+        for _ in 0..=self.types.len() {
+            match &typ {
+                Type::Name(name) => match self.types.get(name) {
+                    Some(declared) => typ = self.resolve(declared),
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        return typ;
+    }
+
+    /// Makes both types equal by binding type variables. Returns `false` if they cannot be equal.
+    /// `Type::Unknown` is the type of erroneous expressions and is compatible with every type.
+    pub(super) fn unify(&mut self, a: &Type, b: &Type) -> bool {
+        let a = self.resolve(a);
+        let b = self.resolve(b);
+
+        return match (&a, &b) {
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Variable(x), Type::Variable(y)) if x == y => true,
+            (Type::Variable(variable), typ) | (typ, Type::Variable(variable)) => {
+                if self.occurs(*variable, typ) {
+                    return false;
+                }
+                self.substitution[*variable] = Some(typ.clone());
+                true
+            }
+            (Type::Name(a), Type::Name(b)) if a == b => true,
+            // a declared type is compatible with the type it stands for
+            (Type::Name(_), _) | (_, Type::Name(_)) => {
+                let (x, y) = (self.expand(&a), self.expand(&b));
+                !matches!((&x, &y), (Type::Name(_), _) | (_, Type::Name(_))) && self.unify(&x, &y)
+            }
+            (Type::Tuple(a), Type::Tuple(b)) => {
+                a.elements.len() == b.elements.len()
+                    && a.elements
+                        .iter()
+                        .zip(&b.elements)
+                        .all(|(a, b)| self.unify(a, b))
+            }
+            (Type::List(a), Type::List(b)) | (Type::Optional(a), Type::Optional(b)) => {
+                self.unify(a, b)
+            }
+            (Type::Map(a), Type::Map(b)) => {
+                self.unify(&a.key, &b.key) && self.unify(&a.value, &b.value)
+            }
+            (Type::Result(a), Type::Result(b)) => {
+                self.unify(&a.value, &b.value) && self.unify(&a.error, &b.error)
+            }
+            (Type::Struct(a), Type::Struct(b)) => {
+                a.fields.len() == b.fields.len()
+                    && a.fields
+                        .iter()
+                        .zip(&b.fields)
+                        .all(|(a, b)| a.name == b.name && self.unify(&a.typ, &b.typ))
+            }
+            (Type::Function(a), Type::Function(b)) => {
+                let unit = Type::unit();
+                a.parameters.len() == b.parameters.len()
+                    && a.parameters
+                        .iter()
+                        .zip(&b.parameters)
+                        .all(|(a, b)| self.unify(&a.typ, &b.typ))
+                    && self.unify(
+                        a.return_type.as_deref().unwrap_or(&unit),
+                        b.return_type.as_deref().unwrap_or(&unit),
+                    )
+            }
+            (a, b) => a == b,
+        };
+    }
+
+    fn occurs(&self, variable: usize, typ: &Type) -> bool {
+        let mut typ = typ.clone();
+        let mut found = false;
+        self.map_variables(&mut typ, &mut |v| found |= v == variable);
+        return found;
+    }
+
+    /// Replaces all type variables in the checked statements by their inferred types
+    /// and reports the ones that could not be inferred.
+    pub(super) fn apply_statements(&mut self, statements: &mut [Box<ast::Statement>]) {
+        let mut reported = HashSet::new();
+        for statement in statements {
+            self.apply_statement(statement, &mut reported);
+        }
+    }
+
+    fn apply_statement(&mut self, statement: &mut ast::Statement, reported: &mut HashSet<usize>) {
+        use ast::StatementKind::*;
+
+        match &mut statement.kind {
+            Comment(_) => {}
+            Declaration(declaration) => self.apply_expression(&mut declaration.value, reported),
+            Assignment(assignment) => {
+                self.apply_expression(&mut assignment.target, reported);
+                self.apply_expression(&mut assignment.value, reported);
+            }
+            Block(block) => self.apply_block(block, reported),
+            TypeDeclaration(declaration) => {
+                if let Type::Struct(structure) = &mut declaration.typ {
+                    for field in &mut structure.fields {
+                        if let Some(default) = &mut field.default {
+                            self.apply_expression(default, reported);
+                        }
+                    }
+                }
+            }
+            FunctionDeclaration(function) => {
+                let mut typ = Type::Function(function.typ.clone());
+                self.apply_type(&mut typ, &statement.position, reported);
+                if let Type::Function(typ) = typ {
+                    function.typ = typ;
+                }
+
+                self.apply_block(&mut function.body, reported);
+            }
+            Return(ret) => self.apply_expression(&mut ret.value, reported),
+            If(i) => self.apply_if(i, reported),
+            While(w) => {
+                self.apply_expression(&mut w.condition, reported);
+                self.apply_block(&mut w.body, reported);
+            }
+            For(f) => {
+                self.apply_expression(&mut f.iterable, reported);
+                self.apply_block(&mut f.body, reported);
+            }
+            Break | Continue => {}
+            Expression(expression) => self.apply_expression(expression, reported),
+        }
+    }
+
+    fn apply_block(&mut self, block: &mut ast::Block, reported: &mut HashSet<usize>) {
+        for statement in &mut block.statements {
+            self.apply_statement(statement, reported);
+        }
+    }
+
+    fn apply_if(&mut self, i: &mut ast::If, reported: &mut HashSet<usize>) {
+        self.apply_expression(&mut i.condition, reported);
+        self.apply_block(&mut i.consequence, reported);
+        match &mut i.alternative {
+            Some(ast::Else::If(alternative)) => self.apply_if(alternative, reported),
+            Some(ast::Else::Block(alternative)) => self.apply_block(alternative, reported),
+            None => {}
+        }
+    }
+
+    fn apply_expression(
+        &mut self,
+        expression: &mut ast::Expression,
+        reported: &mut HashSet<usize>,
+    ) {
+        use ast::ExpressionKind::*;
+
+        match &mut expression.kind {
+            Prefix(prefix) => self.apply_expression(&mut prefix.right, reported),
+            Postfix(postfix) => self.apply_expression(&mut postfix.left, reported),
+            Infix(infix) => {
+                self.apply_expression(&mut infix.left, reported);
+                self.apply_expression(&mut infix.right, reported);
+            }
+            InterpolatedString(parts) => {
+                for part in parts {
+                    if let ast::StringPart::Expression(expression) = part {
+                        self.apply_expression(expression, reported);
+                    }
+                }
+            }
+            Tuple(elements) | List(elements) => {
+                for element in elements {
+                    self.apply_expression(element, reported);
+                }
+            }
+            Map(entries) => {
+                for entry in entries {
+                    self.apply_expression(&mut entry.key, reported);
+                    self.apply_expression(&mut entry.value, reported);
+                }
+            }
+            Call(call) => {
+                self.apply_expression(&mut call.callee, reported);
+                for argument in &mut call.arguments {
+                    self.apply_expression(argument, reported);
+                }
+            }
+            Member(member) => self.apply_expression(&mut member.object, reported),
+            Index(index) => {
+                self.apply_expression(&mut index.object, reported);
+                self.apply_expression(&mut index.index, reported);
+            }
+            Struct(s) => {
+                for field in &mut s.fields {
+                    self.apply_expression(&mut field.value, reported);
+                }
+            }
+            Match(m) => {
+                self.apply_expression(&mut m.subject, reported);
+                for arm in &mut m.arms {
+                    self.apply_expression(&mut arm.body, reported);
+                }
+            }
+            Lambda(lambda) => {
+                // unbound variables of the signature are reported with the type of the lambda
+                if let Type::Function(typ) = self.resolve(&Type::Function(lambda.typ.clone())) {
+                    lambda.typ = typ;
+                }
+                self.apply_block(&mut lambda.body, reported);
+            }
+            _ => {}
+        }
+
+        self.apply_type(&mut expression.typ, &expression.position, reported);
+        // the key of an empty map literal is inferred from its uses
+        if let (Map(_), Type::Map(map)) = (&expression.kind, &expression.typ) {
+            let key = (*map.key).clone();
+            self.check_key(&key, &expression.position);
+        }
+    }
+
+    fn apply_type(&mut self, typ: &mut Type, position: &Span, reported: &mut HashSet<usize>) {
+        let mut unbound = vec![];
+        self.map_variables(typ, &mut |variable| unbound.push(variable));
+
+        // a type with several new variables, like the type of `{}`, is reported once
+        let new = unbound.into_iter().filter(|v| reported.insert(*v)).count();
+        if new > 0 {
+            self.errors.push(Error::CannotInfer {
+                position: position.clone(),
+            });
+        }
+    }
+}
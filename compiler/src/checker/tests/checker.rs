@@ -0,0 +1,952 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+use crate::fmt::{Format, Formatter};
+use crate::parser::Parser;
+use crate::token::Span;
+
+#[test]
+fn check_expression_types() {
+    let cases: &[(&str, &[Type])] = &[
+        (
+            "1 + 2\n3.0 * 4.0\n\"a\" + \"b\"",
+            &[Type::Integer, Type::Float, Type::String],
+        ),
+        (
+            "1 < 2\n\"a\" != \"b\"\n!true",
+            &[Type::Boolean, Type::Boolean, Type::Boolean],
+        ),
+        (
+            "1 <= 2 && 1.5 >= 2.0\n\"a\" >= \"b\" || false",
+            &[Type::Boolean, Type::Boolean],
+        ),
+        (
+            "a := 1\nb := -a\nb % 2 == 0",
+            &[Type::Integer, Type::Integer, Type::Boolean],
+        ),
+        (
+            "t := (1, (\"a\", true))\n(a, (b, _)) := t\nt.1.0\n()",
+            &[
+                Type::Tuple(ast::TupleType {
+                    elements: vec![
+                        Type::Integer,
+                        Type::Tuple(ast::TupleType {
+                            elements: vec![Type::String, Type::Boolean],
+                        }),
+                    ],
+                }),
+                Type::Tuple(ast::TupleType {
+                    elements: vec![
+                        Type::Integer,
+                        Type::Tuple(ast::TupleType {
+                            elements: vec![Type::String, Type::Boolean],
+                        }),
+                    ],
+                }),
+                Type::String,
+                Type::unit(),
+            ],
+        ),
+        (
+            "fn f(x: float): bool {\n  return x > 0.0\n}\nf",
+            &[Type::Function(ast::FunctionType {
+                parameters: vec![ast::Parameter {
+                    name: "x".into(),
+                    typ: Type::Float,
+                    position: Span::from((5, 1, 0), (12, 1, 0)),
+                }],
+                return_type: Some(Box::new(Type::Boolean)),
+            })],
+        ),
+        (
+            "type P = { x: int, y: float = 0.5 }\nmut p := P{ x: 1 }\np.y += 1.0\np.y\nP{ x: p.x }.x",
+            &[Type::Name("P".into()), Type::Float, Type::Integer],
+        ),
+        (
+            "s := \"abc\"\ns[1] + s[2 - 1]",
+            &[Type::String, Type::String],
+        ),
+        (
+            "mut xs := [[1], []]\nxs[0].push(xs.pop()[0])\nxs[1].len()",
+            &[
+                Type::List(Box::new(Type::List(Box::new(Type::Integer)))),
+                Type::unit(),
+                Type::Integer,
+            ],
+        ),
+        (
+            "mut m := {\"a\": 1}\nm[\"b\"] = m[\"a\"]\nm.contains(\"c\")\nm.remove(\"a\") + m.len()",
+            &[
+                Type::Map(ast::MapType {
+                    key: Box::new(Type::String),
+                    value: Box::new(Type::Integer),
+                }),
+                Type::Boolean,
+                Type::Integer,
+            ],
+        ),
+        (
+            "enum Shape { Circle(float), Rect(float, float) }\ns := Rect(1.0, 2.0)\nmatch s {\n  Circle(r) => r\n  Rect(w, h) => w * h\n}",
+            &[Type::Name("Shape".into()), Type::Float],
+        ),
+        (
+            "n := 2.0\nf := fn (x: _): _ { return x * n }\nf(3.0)\n(fn () {\n})",
+            &[
+                Type::Float,
+                Type::Function(ast::FunctionType {
+                    parameters: vec![ast::Parameter {
+                        name: "x".into(),
+                        typ: Type::Float,
+                        position: Span::from((18, 2, 9), (21, 2, 9)),
+                    }],
+                    return_type: Some(Box::new(Type::Float)),
+                }),
+                Type::Float,
+                Type::Function(ast::FunctionType {
+                    parameters: vec![],
+                    return_type: None,
+                }),
+            ],
+        ),
+        (
+            "r := Ok(1)\nr = Err(\"no\")\nmatch r {\n  Ok(n) => n\n  Err(_) => 0\n}",
+            &[
+                Type::Result(ast::ResultType {
+                    value: Box::new(Type::Integer),
+                    error: Box::new(Type::String),
+                }),
+                Type::Integer,
+            ],
+        ),
+        (
+            "mut m := none\nm = 1\nm ?? 2\nm == none\nn := [none, 1.5]",
+            &[
+                Type::Optional(Box::new(Type::Integer)),
+                Type::Integer,
+                Type::Boolean,
+                Type::List(Box::new(Type::Optional(Box::new(Type::Float)))),
+            ],
+        ),
+    ];
+
+    for case in cases {
+        let mut file = Parser::new(case.0).parse().unwrap();
+        check(&mut file).unwrap();
+
+        let got: Vec<Type> = file
+            .statements
+            .iter()
+            .filter_map(|statement| match &statement.kind {
+                ast::StatementKind::Declaration(d) => Some(d.value.typ.clone()),
+                ast::StatementKind::Expression(e) => Some(e.typ.clone()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(case.1, got.as_slice(), "{}", case.0);
+    }
+}
+
+#[test]
+fn check_nested_expression_types() {
+    let mut file = Parser::new("x := 1\n(x + 2) * -x").parse().unwrap();
+    check(&mut file).unwrap();
+
+    let ast::StatementKind::Expression(expression) = &file.statements[1].kind else {
+        panic!("statement is not an expression");
+    };
+    let ast::ExpressionKind::Infix(product) = &expression.kind else {
+        panic!("expression is not an infix expression");
+    };
+    let ast::ExpressionKind::Infix(sum) = &product.left.kind else {
+        panic!("expression is not an infix expression");
+    };
+    assert_eq!(Type::Integer, sum.left.typ);
+    assert_eq!(Type::Integer, sum.right.typ);
+    assert_eq!(Type::Integer, product.left.typ);
+    assert_eq!(Type::Integer, product.right.typ);
+}
+
+#[test]
+fn infer_function_types() {
+    let cases = &[
+        (
+            "fn double(x: _): _ {\n  y := x\n  return y * 2\n}",
+            "fn (x: int): int",
+        ),
+        (
+            "fn positive(x: _): bool {\n  return x > 1.5\n}",
+            "fn (x: float): bool",
+        ),
+        (
+            "fn f(a: _, b: _) {\n  mut c := a\n  c = b\n  c = \"c\"\n}",
+            "fn (a: string, b: string)",
+        ),
+        (
+            "fn count(s: string, x: _): _ {\n  mut n := 0\n  for c in s {\n    if c == x {\n      n = n + 1\n    }\n  }\n  return n\n}",
+            "fn (s: string, x: string): int",
+        ),
+        (
+            "fn fib(n: _): _ {\n  if n < 2 {\n    return n\n  }\n  return fib(n - 1) + fib(n - 2)\n}\nfib(10)",
+            "fn (n: int): int",
+        ),
+        (
+            "fn describe(n: _, unit: _): _ {\n  return \"{n * 2.0} {unit}\" + unit\n}\ndescribe(1.5, \"m\")",
+            "fn (n: float, unit: string): string",
+        ),
+        (
+            "fn between(x: _, low: _, high: _): _ {\n  return low <= x && x <= high || x == 0\n}",
+            "fn (x: int, low: int, high: int): bool",
+        ),
+        (
+            "fn mask(x: _, bits: _): _ {\n  return x & ~(-1 << bits)\n}",
+            "fn (x: int, bits: int): int",
+        ),
+        (
+            "fn divmod(a: _, b: int): _ {\n  return (a / b, a % b)\n}\n(q, r) := divmod(7, 2)\nq + r",
+            "fn (a: int, b: int): (int, int)",
+        ),
+        (
+            "fn swap(t: (_, _)): _ {\n  (a, b) := t\n  return (b * 2.0, a + 1.0)\n}",
+            "fn (t: (float, float)): (float, float)",
+        ),
+        (
+            "fn sign(x: _): _ {\n  if x < 0 {\n    return -1\n  } else if x == 0 {\n    return 0\n  }\n  return 1\n}",
+            "fn (x: int): int",
+        ),
+        (
+            "fn origin(): _ {\n  return P{ x: 0 }\n}\ntype P = { x: int, y: int = 0 }",
+            "fn (): P",
+        ),
+        (
+            "fn norm(p: P): _ {\n  return p.x * p.x + p.y * p.y\n}\ntype P = { x: float, y: float }",
+            "fn (p: P): float",
+        ),
+        (
+            "fn next(id: Id): _ {\n  return id + 1\n}\ntype Id = int",
+            "fn (id: Id): int",
+        ),
+        (
+            "fn at(s: string, i: _): _ {\n  return s[i]\n}",
+            "fn (s: string, i: int): string",
+        ),
+        (
+            "fn evens(n: _): _ {\n  mut xs := []\n  for x in [0, 1, 2, 3] {\n    if x % 2 == 0 {\n      xs.push(x * n)\n    }\n  }\n  return xs\n}",
+            "fn (n: int): [int]",
+        ),
+        (
+            "fn count(words: [string]): _ {\n  mut counts := {}\n  for word in words {\n    if !counts.contains(word) {\n      counts[word] = 0\n    }\n    counts[word] += 1\n  }\n  return counts\n}",
+            "fn (words: [string]): {string: int}",
+        ),
+        (
+            "fn area(s: Shape): _ {\n  return match s {\n    Circle(r) => r * r * 3.0\n    (Rect(w, h)) => w * h\n    Empty => 0.0\n  }\n}\nenum Shape { Circle(float), Rect(float, float), Empty }",
+            "fn (s: Shape): float",
+        ),
+        (
+            "fn get(m: {string: _}, k: _): _? {\n  if m.contains(k) {\n    return m[k] * 2\n  }\n  return none\n}",
+            "fn (m: {string: int}, k: string): int?",
+        ),
+        (
+            "fn add(a: _, b: _): _!string {\n  return Ok(a? + b? + 1)\n}",
+            "fn (a: int!string, b: int!string): int!string",
+        ),
+        (
+            "fn apply(f: fn (a: int): _, x: _): _ {\n  g := fn (y: _): _ { return f(y) + x + 1 }\n  return g(x)\n}",
+            "fn (f: fn (a: int): int, x: int): int",
+        ),
+        (
+            "fn or_zero(x: _): _ {\n  if y := x {\n    return y\n  }\n  return x ?? 0\n}",
+            "fn (x: int?): int",
+        ),
+    ];
+
+    for case in cases {
+        let mut file = Parser::new(case.0).parse().unwrap();
+        check(&mut file).unwrap();
+
+        let ast::StatementKind::FunctionDeclaration(function) = &file.statements[0].kind else {
+            panic!("statement is not a function declaration");
+        };
+        let got = Type::Function(function.typ.clone()).fmt(&mut Formatter::default());
+
+        assert_eq!(case.1, got, "{}", case.0);
+        assert_no_unknown_types(&file.statements);
+    }
+}
+
+#[test]
+fn check_errors() {
+    let cases = &[
+        (
+            "print(1)",
+            vec![Error::TypeMismatch {
+                want: Type::String,
+                got: Type::Integer,
+                position: Span::from((6, 1, 0), (6, 1, 0)),
+            }],
+        ),
+        (
+            r#""a" - 1"#,
+            vec![Error::InvalidOperands {
+                operator: Token::Minus,
+                left: Type::String,
+                right: Type::Integer,
+                position: Span::from((0, 1, 0), (6, 1, 0)),
+            }],
+        ),
+        (
+            "1.0 & 2.0\n~\"a\"",
+            vec![
+                Error::InvalidOperands {
+                    operator: Token::Ampersand,
+                    left: Type::Float,
+                    right: Type::Float,
+                    position: Span::from((0, 1, 0), (8, 1, 0)),
+                },
+                Error::InvalidOperand {
+                    operator: Token::Tilde,
+                    operand: Type::String,
+                    position: Span::from((10, 2, 10), (13, 2, 10)),
+                },
+            ],
+        ),
+        (
+            "mut a := 1\na += 1.5\nmut s := \"a\"\ns -= \"b\"",
+            vec![
+                Error::InvalidOperands {
+                    operator: Token::Plus,
+                    left: Type::Integer,
+                    right: Type::Float,
+                    position: Span::from((11, 2, 11), (18, 2, 11)),
+                },
+                Error::InvalidOperands {
+                    operator: Token::Minus,
+                    left: Type::String,
+                    right: Type::String,
+                    position: Span::from((33, 4, 33), (40, 4, 33)),
+                },
+            ],
+        ),
+        (
+            "t := (1, 2)\n(a, b, c) := t\nt.2\nfn f(x: _) {\n  x.0\n}",
+            vec![
+                Error::InvalidDestructuring {
+                    elements: 3,
+                    typ: Type::Tuple(ast::TupleType {
+                        elements: vec![Type::Integer, Type::Integer],
+                    }),
+                    position: Span::from((12, 2, 12), (25, 2, 12)),
+                },
+                Error::UndefinedMember {
+                    typ: Type::Tuple(ast::TupleType {
+                        elements: vec![Type::Integer, Type::Integer],
+                    }),
+                    name: "2".into(),
+                    position: Span::from((27, 3, 27), (29, 3, 27)),
+                },
+                Error::CannotInfer {
+                    position: Span::from((46, 5, 44), (46, 5, 44)),
+                },
+                Error::CannotInfer {
+                    position: Span::from((31, 4, 31), (50, 6, 50)),
+                },
+            ],
+        ),
+        (
+            "1 && true",
+            vec![Error::InvalidOperands {
+                operator: Token::And,
+                left: Type::Integer,
+                right: Type::Boolean,
+                position: Span::from((0, 1, 0), (8, 1, 0)),
+            }],
+        ),
+        (
+            "-true\n!1",
+            vec![
+                Error::InvalidOperand {
+                    operator: Token::Minus,
+                    operand: Type::Boolean,
+                    position: Span::from((0, 1, 0), (4, 1, 0)),
+                },
+                Error::InvalidOperand {
+                    operator: Token::ExclamationMark,
+                    operand: Type::Integer,
+                    position: Span::from((6, 2, 6), (7, 2, 6)),
+                },
+            ],
+        ),
+        (
+            "mut a := 1\na = 1.0\nb + (c + 1)",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Float,
+                    position: Span::from((15, 2, 11), (17, 2, 11)),
+                },
+                Error::UndefinedName {
+                    name: "b".into(),
+                    position: Span::from((19, 3, 19), (19, 3, 19)),
+                },
+                Error::UndefinedName {
+                    name: "c".into(),
+                    position: Span::from((24, 3, 19), (24, 3, 19)),
+                },
+            ],
+        ),
+        (
+            "fn f(): string {\n  return 1 > 2\n}\nfn g() {\n  return 1\n}",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::String,
+                    got: Type::Boolean,
+                    position: Span::from((26, 2, 17), (30, 2, 17)),
+                },
+                Error::TypeMismatch {
+                    want: Type::unit(),
+                    got: Type::Integer,
+                    position: Span::from((52, 5, 43), (52, 5, 43)),
+                },
+            ],
+        ),
+        (
+            "if 1 {\n} else if x {\n}",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Boolean,
+                    got: Type::Integer,
+                    position: Span::from((3, 1, 0), (3, 1, 0)),
+                },
+                Error::UndefinedName {
+                    name: "x".into(),
+                    position: Span::from((17, 2, 7), (17, 2, 7)),
+                },
+            ],
+        ),
+        (
+            "while 1 {\n}\nfor c in 1.5 {\n  c\n}",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Boolean,
+                    got: Type::Integer,
+                    position: Span::from((6, 1, 0), (6, 1, 0)),
+                },
+                Error::NotIterable {
+                    typ: Type::Float,
+                    position: Span::from((21, 3, 12), (23, 3, 12)),
+                },
+            ],
+        ),
+        (
+            "fn f(a: int, b: string) {\n}\nf(1)\nf(1, 2)\nx := 1\nx()",
+            vec![
+                Error::ArgumentCount {
+                    want: 2,
+                    got: 1,
+                    position: Span::from((28, 3, 28), (31, 3, 28)),
+                },
+                Error::TypeMismatch {
+                    want: Type::String,
+                    got: Type::Integer,
+                    position: Span::from((38, 4, 33), (38, 4, 33)),
+                },
+                Error::NotCallable {
+                    typ: Type::Integer,
+                    position: Span::from((48, 6, 48), (48, 6, 48)),
+                },
+            ],
+        ),
+        (
+            "fn f() {\n}\n\"{f} {f()} {1 + 2.0}\"",
+            vec![
+                Error::InvalidInterpolation {
+                    typ: Type::Function(ast::FunctionType {
+                        parameters: vec![],
+                        return_type: None,
+                    }),
+                    position: Span::from((13, 3, 11), (13, 3, 11)),
+                },
+                Error::InvalidInterpolation {
+                    typ: Type::unit(),
+                    position: Span::from((17, 3, 11), (19, 3, 11)),
+                },
+                Error::InvalidOperands {
+                    operator: Token::Plus,
+                    left: Type::Integer,
+                    right: Type::Float,
+                    position: Span::from((23, 3, 11), (29, 3, 11)),
+                },
+            ],
+        ),
+        (
+            "fn f(x: _) {\n}",
+            vec![Error::CannotInfer {
+                position: Span::from((0, 1, 0), (13, 2, 13)),
+            }],
+        ),
+        (
+            "fn f(x: _, y: _) {\n  z := x - y\n  w := x == true\n}",
+            vec![Error::InvalidOperands {
+                operator: Token::Minus,
+                left: Type::Boolean,
+                right: Type::Boolean,
+                position: Span::from((26, 2, 19), (30, 2, 19)),
+            }],
+        ),
+        (
+            "type P = { x: int, x: int }\nfn f(q: Q) {\n}",
+            vec![
+                Error::DuplicateField {
+                    name: "x".into(),
+                    position: Span::from((0, 1, 0), (26, 1, 0)),
+                },
+                Error::UndefinedType {
+                    name: "Q".into(),
+                    position: Span::from((28, 2, 28), (41, 3, 41)),
+                },
+            ],
+        ),
+        (
+            "type P = { x: int, y: int = 0 }\nP{ y: 1, z: 2, y: 3 }\nQ{}\ntype N = int\nN{}",
+            vec![
+                Error::UndefinedMember {
+                    typ: Type::Name("P".into()),
+                    name: "z".into(),
+                    position: Span::from((44, 2, 32), (44, 2, 32)),
+                },
+                Error::DuplicateField {
+                    name: "y".into(),
+                    position: Span::from((50, 2, 32), (50, 2, 32)),
+                },
+                Error::MissingField {
+                    name: "x".into(),
+                    position: Span::from((32, 2, 32), (52, 2, 32)),
+                },
+                Error::UndefinedType {
+                    name: "Q".into(),
+                    position: Span::from((54, 3, 54), (56, 3, 54)),
+                },
+                Error::NotStruct {
+                    typ: Type::Integer,
+                    position: Span::from((71, 5, 71), (73, 5, 71)),
+                },
+            ],
+        ),
+        (
+            "type P = { x: int = \"a\" }\nmut p := P{}\np.x = 1.5\np.z",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::String,
+                    position: Span::from((20, 1, 0), (22, 1, 0)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Float,
+                    position: Span::from((45, 3, 39), (47, 3, 39)),
+                },
+                Error::UndefinedMember {
+                    typ: Type::Name("P".into()),
+                    name: "z".into(),
+                    position: Span::from((49, 4, 49), (51, 4, 49)),
+                },
+            ],
+        ),
+        (
+            "mut s := \"ab\"\ns[0] = s[1.5]\n1[0]",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Float,
+                    position: Span::from((23, 2, 14), (25, 2, 14)),
+                },
+                Error::NotAssignable {
+                    typ: Type::String,
+                    position: Span::from((14, 2, 14), (17, 2, 14)),
+                },
+                Error::NotIndexable {
+                    typ: Type::Integer,
+                    position: Span::from((28, 3, 28), (31, 3, 28)),
+                },
+            ],
+        ),
+        (
+            "xs := [1, \"a\"]\nxs.len\nxs.push()\n[]",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::String,
+                    position: Span::from((10, 1, 0), (12, 1, 0)),
+                },
+                Error::UndefinedMember {
+                    typ: Type::List(Box::new(Type::Integer)),
+                    name: "len".into(),
+                    position: Span::from((15, 2, 15), (20, 2, 15)),
+                },
+                Error::ArgumentCount {
+                    want: 1,
+                    got: 0,
+                    position: Span::from((22, 3, 22), (30, 3, 22)),
+                },
+                Error::CannotInfer {
+                    position: Span::from((32, 4, 32), (33, 4, 32)),
+                },
+            ],
+        ),
+        (
+            "m := {1: \"a\", \"b\": 2}\nm[true]\nm.push(1)\nfor e in m {\n  e.0 + \"x\"\n}\nn := {}",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::String,
+                    position: Span::from((14, 1, 0), (16, 1, 0)),
+                },
+                Error::TypeMismatch {
+                    want: Type::String,
+                    got: Type::Integer,
+                    position: Span::from((19, 1, 0), (19, 1, 0)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Boolean,
+                    position: Span::from((24, 2, 22), (27, 2, 22)),
+                },
+                Error::UndefinedMember {
+                    typ: Type::Map(ast::MapType {
+                        key: Box::new(Type::Integer),
+                        value: Box::new(Type::String),
+                    }),
+                    name: "push".into(),
+                    position: Span::from((30, 3, 30), (35, 3, 30)),
+                },
+                Error::InvalidOperands {
+                    operator: Token::Plus,
+                    left: Type::Integer,
+                    right: Type::String,
+                    position: Span::from((55, 5, 53), (63, 5, 53)),
+                },
+                Error::CannotInfer {
+                    position: Span::from((72, 7, 67), (73, 7, 67)),
+                },
+            ],
+        ),
+        (
+            "enum Shape { Circle(float), Empty, Circle }\nenum Opt { Some(Shape), Nothing }\ns := Some(Empty)\nmatch s {\n  Some(Circle(_)) => 1\n  Nothing => 2\n}\nmatch (s, 1) {\n  (Nothing, _) => 1\n}\n(Some(x), _) := (s, 1)\nmatch s {\n  Some(x, y) => 1\n  Empty => 2\n  Nope => 3\n  _ => 4\n}",
+            vec![
+                Error::DuplicateVariant {
+                    name: "Circle".into(),
+                    position: Span::from((0, 1, 0), (42, 1, 0)),
+                },
+                Error::NonExhaustiveMatch {
+                    missing: "Some(Empty)".into(),
+                    position: Span::from((95, 4, 95), (143, 7, 143)),
+                },
+                Error::NonExhaustiveMatch {
+                    missing: "(Some(_), _)".into(),
+                    position: Span::from((145, 8, 145), (180, 10, 180)),
+                },
+                Error::RefutablePattern {
+                    position: Span::from((182, 11, 182), (203, 11, 182)),
+                },
+                Error::ArgumentCount {
+                    want: 1,
+                    got: 2,
+                    position: Span::from((217, 13, 215), (231, 13, 215)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Name("Opt".into()),
+                    got: Type::Name("Shape".into()),
+                    position: Span::from((235, 14, 233), (244, 14, 233)),
+                },
+            ],
+        ),
+        (
+            "fn f(a: int!string): int {\n  return a?\n}\nfn g(a: int!string): bool!int {\n  b := 1?\n  c := a?\n  return match a {\n    Ok(_) => Ok(true)\n  }\n}",
+            vec![
+                Error::InvalidPropagation {
+                    error: Type::String,
+                    return_type: Type::Integer,
+                    position: Span::from((36, 2, 27), (37, 2, 27)),
+                },
+                Error::NotResult {
+                    typ: Type::Integer,
+                    position: Span::from((80, 5, 73), (80, 5, 73)),
+                },
+                Error::InvalidPropagation {
+                    error: Type::String,
+                    return_type: Type::Result(ast::ResultType {
+                        value: Box::new(Type::Boolean),
+                        error: Box::new(Type::Integer),
+                    }),
+                    position: Span::from((90, 6, 83), (91, 6, 83)),
+                },
+                Error::NonExhaustiveMatch {
+                    missing: "Err(_)".into(),
+                    position: Span::from((102, 7, 93), (136, 9, 134)),
+                },
+            ],
+        ),
+        (
+            "fn f(): int {\n  g := fn (x: int): bool {\n    return x\n  }\n  return g(true)\n}",
+            vec![
+                Error::TypeMismatch {
+                    want: Type::Boolean,
+                    got: Type::Integer,
+                    position: Span::from((52, 3, 41), (52, 3, 41)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Boolean,
+                    position: Span::from((69, 5, 58), (72, 5, 58)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Boolean,
+                    position: Span::from((67, 5, 58), (73, 5, 58)),
+                },
+            ],
+        ),
+        (
+            "x := none\n1 ?? 2\nfn f(a: int?): int {\n  if c := 1 {\n  }\n  return a + 1\n}\nfn g(a: int?): int {\n  return a\n}",
+            vec![
+                Error::NotOptional {
+                    typ: Type::Integer,
+                    position: Span::from((10, 2, 10), (10, 2, 10)),
+                },
+                Error::NotOptional {
+                    typ: Type::Integer,
+                    position: Span::from((48, 4, 38), (48, 4, 38)),
+                },
+                Error::InvalidOperands {
+                    operator: Token::Plus,
+                    left: Type::Optional(Box::new(Type::Integer)),
+                    right: Type::Integer,
+                    position: Span::from((65, 6, 56), (69, 6, 56)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Optional(Box::new(Type::Integer)),
+                    position: Span::from((103, 9, 94), (103, 9, 94)),
+                },
+                Error::CannotInfer {
+                    position: Span::from((5, 1, 0), (8, 1, 0)),
+                },
+            ],
+        ),
+        (
+            "a := {\"a\": 1, r\"a\": 2, \"b\": 3}\nb := {0x10: 1, 16: 2, true: 3}",
+            vec![
+                Error::DuplicateKey {
+                    key: "r\"a\"".into(),
+                    position: Span::from((14, 1, 0), (17, 1, 0)),
+                },
+                Error::DuplicateKey {
+                    key: "16".into(),
+                    position: Span::from((46, 2, 31), (47, 2, 31)),
+                },
+                Error::TypeMismatch {
+                    want: Type::Integer,
+                    got: Type::Boolean,
+                    position: Span::from((53, 2, 31), (56, 2, 31)),
+                },
+            ],
+        ),
+        (
+            "a := {1.5: 1}\nb := {[1]: 1}\nmut c := {}\nc[1.5] = 1\nfn f(d: {float: int}) {}",
+            vec![
+                Error::InvalidKey {
+                    typ: Type::Float,
+                    position: Span::from((51, 5, 51), (74, 5, 51)),
+                },
+                Error::InvalidKey {
+                    typ: Type::Float,
+                    position: Span::from((5, 1, 0), (12, 1, 0)),
+                },
+                Error::InvalidKey {
+                    typ: Type::List(Box::new(Type::Integer)),
+                    position: Span::from((19, 2, 14), (26, 2, 14)),
+                },
+                Error::InvalidKey {
+                    typ: Type::Float,
+                    position: Span::from((37, 3, 28), (38, 3, 28)),
+                },
+            ],
+        ),
+        (
+            "a := 9223372036854775808\nb := 0x7fff_ffff_ffff_ffff + 0x1_0000_0000_0000_0000\nc := -9223372036854775808\nd := -9223372036854775809",
+            vec![
+                Error::IntegerOverflow {
+                    literal: "9223372036854775808".into(),
+                    position: Span::from((5, 1, 0), (23, 1, 0)),
+                },
+                Error::IntegerOverflow {
+                    literal: "0x1_0000_0000_0000_0000".into(),
+                    position: Span::from((54, 2, 25), (76, 2, 25)),
+                },
+                Error::IntegerOverflow {
+                    literal: "9223372036854775809".into(),
+                    position: Span::from((110, 4, 104), (128, 4, 104)),
+                },
+            ],
+        ),
+        (
+            r#"
+fn f(): string {
+  a := 1
+}
+
+fn g(a: int): int {
+  if a > 0 {
+    return 1
+  } else if a < 0 {
+    return -1
+  }
+}
+
+fn h(a: bool): int {
+  if a {
+    return 1
+  } else {
+    return 0
+  }
+}
+
+fn i(): int {
+  while true {
+    if false {
+      break
+    }
+  }
+}
+
+fn j(): int {
+  while true {
+  }
+}
+
+enum E { A, B }
+
+fn k(e: E): int {
+  x := match e {
+    A => 1
+    B => 2
+  }
+  {
+    return x
+  }
+}
+
+fn l() {
+  f := fn (): int {
+    for x in [1] {
+      return x
+    }
+  }
+}
+"#,
+            vec![
+                Error::MissingReturn {
+                    typ: Type::String,
+                    position: Span::from((1, 2, 1), (27, 4, 27)),
+                },
+                Error::MissingReturn {
+                    typ: Type::Integer,
+                    position: Span::from((30, 6, 30), (114, 12, 114)),
+                },
+                Error::MissingReturn {
+                    typ: Type::Integer,
+                    position: Span::from((191, 22, 191), (257, 28, 257)),
+                },
+                Error::MissingReturn {
+                    typ: Type::Integer,
+                    position: Span::from((414, 48, 407), (469, 52, 467)),
+                },
+            ],
+        ),
+    ];
+
+    for case in cases {
+        let mut file = Parser::new(case.0).parse().unwrap();
+        assert_eq!(Err(case.1.clone()), check(&mut file), "{}", case.0);
+    }
+}
+
+fn assert_no_unknown_types(statements: &[Box<ast::Statement>]) {
+    fn assert_known(expression: &ast::Expression) {
+        assert!(
+            !matches!(expression.typ, Type::Unknown | Type::Variable(_)),
+            "type of expression @ {} is unknown",
+            expression.position
+        );
+        match &expression.kind {
+            ast::ExpressionKind::Prefix(prefix) => assert_known(&prefix.right),
+            ast::ExpressionKind::Postfix(postfix) => assert_known(&postfix.left),
+            ast::ExpressionKind::Infix(infix) => {
+                assert_known(&infix.left);
+                assert_known(&infix.right);
+            }
+            ast::ExpressionKind::Tuple(elements) | ast::ExpressionKind::List(elements) => {
+                elements.iter().for_each(assert_known)
+            }
+            ast::ExpressionKind::Member(member) => assert_known(&member.object),
+            ast::ExpressionKind::Index(index) => {
+                assert_known(&index.object);
+                assert_known(&index.index);
+            }
+            ast::ExpressionKind::Map(entries) => entries.iter().for_each(|e| {
+                assert_known(&e.key);
+                assert_known(&e.value);
+            }),
+            ast::ExpressionKind::Struct(s) => s.fields.iter().for_each(|f| assert_known(&f.value)),
+            ast::ExpressionKind::Match(m) => {
+                assert_known(&m.subject);
+                m.arms.iter().for_each(|arm| assert_known(&arm.body));
+            }
+            _ => {}
+        }
+    }
+
+    for statement in statements {
+        match &statement.kind {
+            ast::StatementKind::Declaration(d) => assert_known(&d.value),
+            ast::StatementKind::Assignment(a) => {
+                assert_known(&a.target);
+                assert_known(&a.value);
+            }
+            ast::StatementKind::Return(r) => assert_known(&r.value),
+            ast::StatementKind::Expression(e) => assert_known(e),
+            ast::StatementKind::Block(b) => assert_no_unknown_types(&b.statements),
+            ast::StatementKind::FunctionDeclaration(f) => {
+                assert_no_unknown_types(&f.body.statements)
+            }
+            ast::StatementKind::If(i) => {
+                let mut next = Some(i);
+                while let Some(i) = next {
+                    assert_known(&i.condition);
+                    assert_no_unknown_types(&i.consequence.statements);
+                    next = match &i.alternative {
+                        Some(ast::Else::If(alternative)) => Some(alternative),
+                        Some(ast::Else::Block(alternative)) => {
+                            assert_no_unknown_types(&alternative.statements);
+                            None
+                        }
+                        None => None,
+                    };
+                }
+            }
+            ast::StatementKind::While(w) => {
+                assert_known(&w.condition);
+                assert_no_unknown_types(&w.body.statements);
+            }
+            ast::StatementKind::For(f) => {
+                assert_known(&f.iterable);
+                assert_no_unknown_types(&f.body.statements);
+            }
+            ast::StatementKind::Comment(_)
+            | ast::StatementKind::TypeDeclaration(_)
+            | ast::StatementKind::Break
+            | ast::StatementKind::Continue => {}
+        }
+    }
+}
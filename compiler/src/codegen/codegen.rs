@@ -0,0 +1,250 @@
+mod error;
+mod function;
+mod jit;
+mod object;
+mod runtime;
+
+pub use error::*;
+pub use jit::*;
+pub use object::*;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use cranelift::codegen;
+use cranelift::prelude::*;
+use cranelift_module::{FuncId, Linkage, Module};
+
+use crate::ast;
+use crate::fmt::{Format, Formatter};
+use crate::resolver;
+use crate::token::Span;
+use function::FunctionTranslator;
+use runtime::Runtime;
+
+/// Value returned from executing compiled X code.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Output {
+    Unit,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Ok(Box<Output>),
+    Err(Box<Output>),
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Output::Unit => write!(f, "()"),
+            Output::Boolean(b) => write!(f, "{}", b),
+            Output::Integer(i) => write!(f, "{}", i),
+            Output::Float(x) => write!(f, "{}", x),
+            Output::String(s) => write!(f, "{}", s),
+            Output::Ok(value) => write!(f, "Ok({})", value),
+            Output::Err(error) => write!(f, "Err({})", error),
+        }
+    }
+}
+
+/// A function declared in the module together with its X signature.
+#[derive(Clone, Debug)]
+pub(crate) struct Function {
+    pub id: FuncId,
+    pub typ: ast::FunctionType,
+}
+
+/// A lambda that was translated into a closure, whose body is defined as a function
+/// of its own after the function that contains it.
+pub(crate) struct Lambda {
+    pub id: FuncId,
+    pub lambda: ast::LambdaExpression,
+    /// Bindings that the closure stores after the pointer to its function, in order.
+    pub captures: Vec<(String, ast::Type)>,
+}
+
+/// The lambdas of the file and the names of the bindings they capture by their span.
+#[derive(Default)]
+pub(crate) struct Lambdas {
+    pub captures: HashMap<Span, Vec<String>>,
+    pub pending: Vec<Lambda>,
+}
+
+/// Lowers an `ast::File` into Cranelift IR and defines the result in the module `M`.
+/// Shared by all backends, which only differ in the module they write into.
+pub(crate) struct Generator<M: Module> {
+    pub module: M,
+    pub functions: HashMap<String, Function>,
+    pub runtime: Runtime,
+    lambdas: Lambdas,
+
+    context: codegen::Context,
+    builder_context: FunctionBuilderContext,
+}
+
+impl<M: Module> Generator<M> {
+    pub fn new(mut module: M) -> Result<Self> {
+        let runtime = Runtime::declare(&mut module)?;
+        let context = module.make_context();
+        return Ok(Self {
+            module,
+            functions: HashMap::new(),
+            runtime,
+            lambdas: Lambdas::default(),
+
+            context,
+            builder_context: FunctionBuilderContext::new(),
+        });
+    }
+
+    /// Declares and defines every top-level function of the file and the lambdas in them.
+    pub fn generate(&mut self, file: &ast::File) -> Result<()> {
+        for capture in resolver::captures(file) {
+            self.lambdas
+                .captures
+                .entry(capture.closure)
+                .or_default()
+                .push(capture.name);
+        }
+
+        let mut declarations = vec![];
+        for statement in &file.statements {
+            match &statement.kind {
+                ast::StatementKind::Comment(_) | ast::StatementKind::TypeDeclaration(_) => {}
+                ast::StatementKind::FunctionDeclaration(function) => {
+                    self.declare_function(function, statement)?;
+                    declarations.push(function);
+                }
+                _ => {
+                    return Err(Error::Unsupported {
+                        what: "top-level statement".into(),
+                        position: statement.position.clone(),
+                    })
+                }
+            }
+        }
+
+        for function in declarations {
+            let id = self.functions[&function.name].id;
+            self.define(id, |translator| translator.translate(function))?;
+        }
+        while let Some(lambda) = self.lambdas.pending.pop() {
+            self.define(lambda.id, |translator| translator.translate_lambda(&lambda))?;
+        }
+
+        return Ok(());
+    }
+
+    fn declare_function(
+        &mut self,
+        function: &ast::FunctionDeclaration,
+        statement: &ast::Statement,
+    ) -> Result<()> {
+        let signature = signature(&self.module, &function.typ, false, &statement.position)?;
+        let id = self.module.declare_function(
+            &symbol_name(&function.name),
+            Linkage::Export,
+            &signature,
+        )?;
+        self.functions.insert(
+            function.name.clone(),
+            Function {
+                id,
+                typ: function.typ.clone(),
+            },
+        );
+
+        return Ok(());
+    }
+
+    /// Defines the declared function `id` with the IR that `translate` builds.
+    fn define(
+        &mut self,
+        id: FuncId,
+        translate: impl FnOnce(FunctionTranslator<M>) -> Result<()>,
+    ) -> Result<()> {
+        self.module.clear_context(&mut self.context);
+        self.context.func.signature = self
+            .module
+            .declarations()
+            .get_function_decl(id)
+            .signature
+            .clone();
+
+        let builder = FunctionBuilder::new(&mut self.context.func, &mut self.builder_context);
+        let translator = FunctionTranslator::new(
+            builder,
+            &mut self.module,
+            &self.functions,
+            &mut self.runtime,
+            &mut self.lambdas,
+        );
+        translate(translator)?;
+
+        self.module.define_function(id, &mut self.context)?;
+        self.module.clear_context(&mut self.context);
+
+        return Ok(());
+    }
+}
+
+/// Returns the linker symbol of the X function `name`.
+/// X functions live in their own namespace, so an X `main` does not clash with the C entry point.
+pub(crate) fn symbol_name(name: &str) -> String {
+    return format!("x.{}", name);
+}
+
+/// Returns the signature of functions of the X type. Functions of closures take
+/// the closure as their first parameter, which gives them access to their captures.
+pub(crate) fn signature<M: Module>(
+    module: &M,
+    typ: &ast::FunctionType,
+    closure: bool,
+    position: &Span,
+) -> Result<Signature> {
+    let pointer = module.target_config().pointer_type();
+    let mut signature = module.make_signature();
+    if closure {
+        signature.params.push(AbiParam::new(pointer));
+    }
+    for parameter in &typ.parameters {
+        let typ = cranelift_type(&parameter.typ, pointer).ok_or_else(|| Error::Unsupported {
+            what: format!("parameter type {}", type_name(&parameter.typ)),
+            position: position.clone(),
+        })?;
+        signature.params.push(AbiParam::new(typ));
+    }
+    if let Some(return_type) = &typ.return_type {
+        let typ = cranelift_type(return_type, pointer).ok_or_else(|| Error::Unsupported {
+            what: format!("return type {}", type_name(return_type)),
+            position: position.clone(),
+        })?;
+        signature.returns.push(AbiParam::new(typ));
+    }
+    return Ok(signature);
+}
+
+/// Returns the Cranelift type used to represent values of the X type.
+/// Strings are pointers to null-terminated sequences of bytes. Results are pointers to
+/// a tag, which is 0 for `Ok` and 1 for `Err`, followed by the 8 bytes of their value.
+/// Functions are pointers to closures, which hold a pointer to the function of the lambda
+/// followed by the 8 bytes of each captured value.
+pub(crate) fn cranelift_type(typ: &ast::Type, pointer: Type) -> Option<Type> {
+    return match typ {
+        ast::Type::Boolean => Some(types::I8),
+        ast::Type::Integer => Some(types::I64),
+        ast::Type::Float => Some(types::F64),
+        ast::Type::String | ast::Type::Result(_) | ast::Type::Function(_) => Some(pointer),
+        _ => None,
+    };
+}
+
+/// Returns the name of the X type as written in the source.
+pub(crate) fn type_name(typ: &ast::Type) -> String {
+    return typ.fmt(&mut Formatter::default());
+}
+
+#[cfg(test)]
+#[path = "tests/codegen.rs"]
+mod tests;
@@ -0,0 +1,86 @@
+use std::fmt;
+
+use crate::token::Span;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    UndefinedVariable {
+        name: String,
+        position: Span,
+    },
+    UndefinedFunction {
+        name: String,
+    },
+    InvalidLiteral {
+        literal: String,
+        position: Span,
+    },
+    TypeMismatch {
+        want: String,
+        got: String,
+        position: Span,
+    },
+    ArgumentCount {
+        name: String,
+        want: usize,
+        got: usize,
+        position: Span,
+    },
+    Unsupported {
+        what: String,
+        position: Span,
+    },
+    /// Raised by the compiled code when it runs.
+    DivisionByZero {
+        position: Span,
+    },
+    ModuleError(String),
+    LinkError(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UndefinedVariable { name, position } => {
+                write!(f, "undefined variable: {} @ {}", name, position)
+            }
+            Error::UndefinedFunction { name } => write!(f, "undefined function: {}", name),
+            Error::InvalidLiteral { literal, position } => {
+                write!(f, "invalid literal: {} @ {}", literal, position)
+            }
+            Error::TypeMismatch {
+                want,
+                got,
+                position,
+            } => write!(
+                f,
+                "type mismatch: {} @ {} (expected: {})",
+                got, position, want
+            ),
+            Error::ArgumentCount {
+                name,
+                want,
+                got,
+                position,
+            } => write!(
+                f,
+                "wrong number of arguments for {}: {} @ {} (expected: {})",
+                name, got, position, want
+            ),
+            Error::Unsupported { what, position } => {
+                write!(f, "unsupported: {} @ {}", what, position)
+            }
+            Error::DivisionByZero { position } => write!(f, "division by zero @ {}", position),
+            Error::ModuleError(e) => write!(f, "module error: {}", e),
+            Error::LinkError(e) => write!(f, "link error: {}", e),
+        }
+    }
+}
+
+impl From<cranelift_module::ModuleError> for Error {
+    fn from(e: cranelift_module::ModuleError) -> Self {
+        return Error::ModuleError(e.to_string());
+    }
+}
@@ -0,0 +1,1344 @@
+use std::collections::HashMap;
+
+use cranelift::prelude::*;
+use cranelift_module::{DataId, Linkage, Module};
+
+use super::{
+    cranelift_type, signature, type_name, Error, Function, Lambda, Lambdas, Result, Runtime,
+};
+use crate::ast;
+use crate::lexer;
+use crate::resolver::{PRINT, RESULT_VARIANTS};
+use crate::token::{Span, Token};
+
+/// Trap at the end of a function with a return value, which the checker makes unreachable.
+const MISSING_RETURN: TrapCode = TrapCode::unwrap_user(1);
+
+/// A translated value together with the X type of the value it represents.
+#[derive(Clone, Debug)]
+struct Typed {
+    value: Value,
+    typ: ast::Type,
+}
+
+/// Translates the body of a single function into Cranelift IR.
+pub(super) struct FunctionTranslator<'a, M: Module> {
+    builder: FunctionBuilder<'a>,
+    module: &'a mut M,
+    functions: &'a HashMap<String, Function>,
+    runtime: &'a mut Runtime,
+    lambdas: &'a mut Lambdas,
+
+    scopes: Vec<HashMap<String, (Variable, ast::Type)>>,
+    variables: usize,
+    return_type: Option<ast::Type>,
+    /// Blocks that `continue` and `break` jump to for each enclosing loop.
+    loops: Vec<(Block, Block)>,
+}
+
+impl<'a, M: Module> FunctionTranslator<'a, M> {
+    pub fn new(
+        builder: FunctionBuilder<'a>,
+        module: &'a mut M,
+        functions: &'a HashMap<String, Function>,
+        runtime: &'a mut Runtime,
+        lambdas: &'a mut Lambdas,
+    ) -> Self {
+        return Self {
+            builder,
+            module,
+            functions,
+            runtime,
+            lambdas,
+
+            scopes: vec![HashMap::new()],
+            variables: 0,
+            return_type: None,
+            loops: vec![],
+        };
+    }
+
+    pub fn translate(self, function: &ast::FunctionDeclaration) -> Result<()> {
+        return self.translate_body(&function.typ, &function.body, None);
+    }
+
+    /// Translates the body of a lambda, which loads its captures from the closure
+    /// that it is called with.
+    pub fn translate_lambda(self, lambda: &Lambda) -> Result<()> {
+        return self.translate_body(
+            &lambda.lambda.typ,
+            &lambda.lambda.body,
+            Some(&lambda.captures),
+        );
+    }
+
+    /// Translates `int main(void)` of an executable, which calls the X `main` and prints its result
+    /// to stdout like `x run` does. A runtime error or an `Err` result is printed to stderr instead
+    /// and exits with 1.
+    ///
+    /// `messages` holds the messages of the runtime errors and the table of their offsets and lengths,
+    /// if the code can raise any.
+    pub fn translate_entry(
+        mut self,
+        main: &Function,
+        messages: Option<(DataId, DataId)>,
+    ) -> Result<()> {
+        let entry = self.builder.create_block();
+        self.builder.switch_to_block(entry);
+        self.builder.seal_block(entry);
+
+        let function = self.module.declare_func_in_func(main.id, self.builder.func);
+        let call = self.builder.ins().call(function, &[]);
+
+        let pointer = self.module.target_config().pointer_type();
+        if let Some((messages, table)) = messages {
+            let address = self.error_address();
+            let code = self
+                .builder
+                .ins()
+                .load(types::I64, MemFlags::trusted(), address, 0);
+
+            let report_block = self.builder.create_block();
+            let exit_block = self.builder.create_block();
+            self.builder
+                .ins()
+                .brif(code, report_block, &[], exit_block, &[]);
+
+            self.builder.switch_to_block(report_block);
+            self.builder.seal_block(report_block);
+            // entry `n - 1` of the table holds the offset and the length of the message of error `n`
+            let table = self.module.declare_data_in_func(table, self.builder.func);
+            let table = self.builder.ins().global_value(pointer, table);
+            let offset = self.builder.ins().ishl_imm(code, 4);
+            let entry = self.builder.ins().iadd(table, offset);
+            let start = self
+                .builder
+                .ins()
+                .load(types::I64, MemFlags::trusted(), entry, -16);
+            let length = self
+                .builder
+                .ins()
+                .load(types::I64, MemFlags::trusted(), entry, -8);
+            let messages = self
+                .module
+                .declare_data_in_func(messages, self.builder.func);
+            let messages = self.builder.ins().global_value(pointer, messages);
+            let message = self.builder.ins().iadd(messages, start);
+
+            let stderr = self.builder.ins().iconst(types::I32, 2);
+            self.call_runtime(
+                "write",
+                &[types::I32, pointer, types::I64],
+                types::I64,
+                &[stderr, message, length],
+            )?;
+            let status = self.builder.ins().iconst(types::I32, 1);
+            self.builder.ins().return_(&[status]);
+
+            self.builder.switch_to_block(exit_block);
+            self.builder.seal_block(exit_block);
+        }
+
+        if let Some(typ) = main.typ.return_type.as_deref() {
+            let value = Typed {
+                value: self.builder.inst_results(call)[0],
+                typ: typ.clone(),
+            };
+            let string = self.display(value.clone(), &Span::new())?;
+            // an `Err` is the error of the program, so it goes to stderr and fails the process
+            if let ast::Type::Result(_) = typ {
+                let tag = self
+                    .builder
+                    .ins()
+                    .load(types::I64, MemFlags::trusted(), value.value, 0);
+                let error_block = self.builder.create_block();
+                let ok_block = self.builder.create_block();
+                self.builder
+                    .ins()
+                    .brif(tag, error_block, &[], ok_block, &[]);
+
+                self.builder.switch_to_block(error_block);
+                self.builder.seal_block(error_block);
+                self.print("x_print_error", string)?;
+                let status = self.builder.ins().iconst(types::I32, 1);
+                self.builder.ins().return_(&[status]);
+
+                self.builder.switch_to_block(ok_block);
+                self.builder.seal_block(ok_block);
+            }
+            self.print("x_print", string)?;
+        }
+        let status = self.builder.ins().iconst(types::I32, 0);
+        self.builder.ins().return_(&[status]);
+
+        self.builder.seal_all_blocks();
+        self.builder.finalize();
+
+        return Ok(());
+    }
+
+    fn translate_body(
+        mut self,
+        typ: &ast::FunctionType,
+        body: &ast::Block,
+        captures: Option<&[(String, ast::Type)]>,
+    ) -> Result<()> {
+        self.return_type = typ.return_type.as_deref().cloned();
+
+        let entry = self.builder.create_block();
+        self.builder.append_block_params_for_function_params(entry);
+        self.builder.switch_to_block(entry);
+        self.builder.seal_block(entry);
+
+        let mut parameters = self.builder.block_params(entry).to_vec();
+        if let Some(captures) = captures {
+            let closure = parameters.remove(0);
+            let pointer = self.module.target_config().pointer_type();
+            for (i, (name, typ)) in captures.iter().enumerate() {
+                let cranelift_type = cranelift_type(typ, pointer).expect("captures are translated");
+                let value = self.builder.ins().load(
+                    cranelift_type,
+                    MemFlags::trusted(),
+                    closure,
+                    8 * (i as i32 + 1),
+                );
+                let typ = typ.clone();
+                self.declare_variable(name, Typed { value, typ });
+            }
+            // parameters shadow captures of the same name
+            self.scopes.push(HashMap::new());
+        }
+        for (parameter, value) in typ.parameters.iter().zip(parameters) {
+            self.declare_variable(
+                &parameter.name,
+                Typed {
+                    value,
+                    typ: parameter.typ.clone(),
+                },
+            );
+        }
+
+        self.translate_statements(&body.statements)?;
+
+        // The checker rejects functions with a return value that can fall off their end.
+        match self.return_type {
+            Some(_) => {
+                self.builder.ins().trap(MISSING_RETURN);
+            }
+            None => {
+                self.builder.ins().return_(&[]);
+            }
+        }
+
+        self.builder.seal_all_blocks();
+        self.builder.finalize();
+
+        return Ok(());
+    }
+
+    fn translate_statements(&mut self, statements: &[Box<ast::Statement>]) -> Result<()> {
+        for statement in statements {
+            self.translate_statement(statement)?;
+        }
+        return Ok(());
+    }
+
+    fn translate_statement(&mut self, statement: &ast::Statement) -> Result<()> {
+        use ast::StatementKind::*;
+
+        match &statement.kind {
+            Comment(_) => {}
+            Declaration(declaration) => {
+                let ast::Pattern::Identifier(name) = &declaration.pattern else {
+                    return Err(Error::Unsupported {
+                        what: "destructuring".into(),
+                        position: statement.position.clone(),
+                    });
+                };
+                let value = self.translate_expression(&declaration.value)?;
+                self.declare_variable(name, value);
+            }
+            Assignment(assignment) => {
+                let ast::ExpressionKind::Identifier(name) = &assignment.target.kind else {
+                    return Err(Error::Unsupported {
+                        what: "member assignment".into(),
+                        position: statement.position.clone(),
+                    });
+                };
+                let (variable, typ) = self.lookup_variable(name, &statement.position)?;
+                let mut value = self.translate_expression(&assignment.value)?;
+                self.expect_type(&typ, &value, &assignment.value.position)?;
+                if let Some(operator) = &assignment.operator {
+                    let current = Typed {
+                        value: self.builder.use_var(variable),
+                        typ,
+                    };
+                    value =
+                        self.translate_operation(operator, current, value, &statement.position)?;
+                }
+                self.builder.def_var(variable, value.value);
+            }
+            Block(block) => self.translate_block(block)?,
+            // types only matter to the checker
+            TypeDeclaration(_) => {}
+            FunctionDeclaration(_) => {
+                return Err(Error::Unsupported {
+                    what: "nested function declaration".into(),
+                    position: statement.position.clone(),
+                })
+            }
+            Return(ret) => {
+                let value = self.translate_expression(&ret.value)?;
+                match self.return_type.clone() {
+                    Some(typ) => self.expect_type(&typ, &value, &ret.value.position)?,
+                    None => {
+                        return Err(Error::TypeMismatch {
+                            want: "no return value".into(),
+                            got: type_name(&value.typ),
+                            position: ret.value.position.clone(),
+                        })
+                    }
+                }
+                self.builder.ins().return_(&[value.value]);
+                self.switch_to_unreachable_block();
+            }
+            If(i) => self.translate_if(i)?,
+            While(w) => self.translate_while(w)?,
+            For(_) => {
+                return Err(Error::Unsupported {
+                    what: "for loop".into(),
+                    position: statement.position.clone(),
+                })
+            }
+            Break | Continue => {
+                let (header, exit) = *self.loops.last().ok_or_else(|| Error::Unsupported {
+                    what: "break or continue outside of a loop".into(),
+                    position: statement.position.clone(),
+                })?;
+                let target = if matches!(statement.kind, Break) {
+                    exit
+                } else {
+                    header
+                };
+                self.builder.ins().jump(target, &[]);
+                self.switch_to_unreachable_block();
+            }
+            Expression(expression) => match &expression.kind {
+                // calls of functions without a return value are only allowed as statements
+                ast::ExpressionKind::Call(call) => {
+                    self.translate_call_expression(call, &expression.position)?;
+                }
+                _ => {
+                    self.translate_expression(expression)?;
+                }
+            },
+        }
+
+        return Ok(());
+    }
+
+    fn translate_block(&mut self, block: &ast::Block) -> Result<()> {
+        self.scopes.push(HashMap::new());
+        let result = self.translate_statements(&block.statements);
+        self.scopes.pop();
+        return result;
+    }
+
+    fn translate_if(&mut self, i: &ast::If) -> Result<()> {
+        if i.binding.is_some() {
+            return Err(Error::Unsupported {
+                what: "optional".into(),
+                position: i.condition.position.clone(),
+            });
+        }
+        let condition = self.translate_expression(&i.condition)?;
+        self.expect_type(&ast::Type::Boolean, &condition, &i.condition.position)?;
+
+        let then_block = self.builder.create_block();
+        let else_block = self.builder.create_block();
+        let merge_block = self.builder.create_block();
+
+        self.builder
+            .ins()
+            .brif(condition.value, then_block, &[], else_block, &[]);
+
+        self.builder.switch_to_block(then_block);
+        self.builder.seal_block(then_block);
+        self.translate_block(&i.consequence)?;
+        self.builder.ins().jump(merge_block, &[]);
+
+        self.builder.switch_to_block(else_block);
+        self.builder.seal_block(else_block);
+        match &i.alternative {
+            Some(ast::Else::If(alternative)) => self.translate_if(alternative)?,
+            Some(ast::Else::Block(alternative)) => self.translate_block(alternative)?,
+            None => {}
+        }
+        self.builder.ins().jump(merge_block, &[]);
+
+        self.builder.switch_to_block(merge_block);
+        self.builder.seal_block(merge_block);
+
+        return Ok(());
+    }
+
+    fn translate_while(&mut self, w: &ast::While) -> Result<()> {
+        let header_block = self.builder.create_block();
+        let body_block = self.builder.create_block();
+        let exit_block = self.builder.create_block();
+
+        self.builder.ins().jump(header_block, &[]);
+
+        // The header is sealed after the body, which jumps back to it.
+        self.builder.switch_to_block(header_block);
+        let condition = self.translate_expression(&w.condition)?;
+        self.expect_type(&ast::Type::Boolean, &condition, &w.condition.position)?;
+        self.builder
+            .ins()
+            .brif(condition.value, body_block, &[], exit_block, &[]);
+
+        self.builder.switch_to_block(body_block);
+        self.builder.seal_block(body_block);
+        self.loops.push((header_block, exit_block));
+        let result = self.translate_block(&w.body);
+        self.loops.pop();
+        result?;
+        self.builder.ins().jump(header_block, &[]);
+        self.builder.seal_block(header_block);
+
+        self.builder.switch_to_block(exit_block);
+        self.builder.seal_block(exit_block);
+
+        return Ok(());
+    }
+
+    /// Statements after a `return`, `break` or `continue` are unreachable,
+    /// but still need a block to live in.
+    fn switch_to_unreachable_block(&mut self) {
+        let block = self.builder.create_block();
+        self.builder.switch_to_block(block);
+        self.builder.seal_block(block);
+    }
+
+    fn translate_expression(&mut self, expression: &ast::Expression) -> Result<Typed> {
+        use ast::ExpressionKind::*;
+
+        return match &expression.kind {
+            Identifier(name) => {
+                let (variable, typ) = self.lookup_variable(name, &expression.position)?;
+                Ok(Typed {
+                    value: self.builder.use_var(variable),
+                    typ,
+                })
+            }
+            Boolean(b) => Ok(Typed {
+                value: self.builder.ins().iconst(types::I8, *b as i64),
+                typ: ast::Type::Boolean,
+            }),
+            Integer(i) => {
+                let value = lexer::integer_value(i).ok_or_else(|| Error::InvalidLiteral {
+                    literal: i.into(),
+                    position: expression.position.clone(),
+                })?;
+                Ok(Typed {
+                    value: self.builder.ins().iconst(types::I64, value),
+                    typ: ast::Type::Integer,
+                })
+            }
+            Float(f) => {
+                let value = lexer::float_value(f).ok_or_else(|| Error::InvalidLiteral {
+                    literal: f.into(),
+                    position: expression.position.clone(),
+                })?;
+                Ok(Typed {
+                    value: self.builder.ins().f64const(value),
+                    typ: ast::Type::Float,
+                })
+            }
+            String(s) => self.string_literal(&lexer::string_value(s), &expression.position),
+            InterpolatedString(parts) => {
+                self.translate_interpolated_string(parts, &expression.position)
+            }
+            Tuple(_) | Member(_) => Err(Error::Unsupported {
+                what: "tuple".into(),
+                position: expression.position.clone(),
+            }),
+            List(_) => Err(Error::Unsupported {
+                what: "list".into(),
+                position: expression.position.clone(),
+            }),
+            Map(_) => Err(Error::Unsupported {
+                what: "map".into(),
+                position: expression.position.clone(),
+            }),
+            Match(_) => Err(Error::Unsupported {
+                what: "match".into(),
+                position: expression.position.clone(),
+            }),
+            NoneLiteral => Err(Error::Unsupported {
+                what: "optional".into(),
+                position: expression.position.clone(),
+            }),
+            Postfix(postfix) => self.translate_propagation(postfix, &expression.position),
+            Lambda(lambda) => self.translate_closure(lambda, &expression.position),
+            Index(_) => Err(Error::Unsupported {
+                what: "indexing".into(),
+                position: expression.position.clone(),
+            }),
+            Struct(_) => Err(Error::Unsupported {
+                what: "struct".into(),
+                position: expression.position.clone(),
+            }),
+
+            Prefix(prefix) => self.translate_prefix_expression(prefix, &expression.position),
+            Infix(infix) => self.translate_infix_expression(infix, &expression.position),
+            Call(call) => self
+                .translate_call_expression(call, &expression.position)?
+                .ok_or_else(|| Error::Unsupported {
+                    what: "use of a call without a return value".into(),
+                    position: expression.position.clone(),
+                }),
+        };
+    }
+
+    /// Stores the string in the module and returns a pointer to it.
+    /// Compiled strings end at their first null character, so they cannot contain one.
+    fn string_literal(&mut self, string: &str, position: &Span) -> Result<Typed> {
+        if string.contains('\0') {
+            return Err(Error::Unsupported {
+                what: "null character in a string".into(),
+                position: position.clone(),
+            });
+        }
+        let id = self.runtime.string(self.module, string)?;
+        let data = self.module.declare_data_in_func(id, self.builder.func);
+        let pointer = self.module.target_config().pointer_type();
+        return Ok(Typed {
+            value: self.builder.ins().global_value(pointer, data),
+            typ: ast::Type::String,
+        });
+    }
+
+    /// Converts the embedded values to strings and concatenates them with the text around them.
+    fn translate_interpolated_string(
+        &mut self,
+        parts: &[ast::StringPart],
+        position: &Span,
+    ) -> Result<Typed> {
+        let mut result: Option<Typed> = None;
+        for part in parts {
+            let string = match part {
+                ast::StringPart::Literal(literal) => {
+                    self.string_literal(&lexer::unescape(literal), position)?
+                }
+                ast::StringPart::Expression(expression) => {
+                    let value = self.translate_expression(expression)?;
+                    self.string_of(value, &expression.position)?
+                }
+            };
+            result = Some(match result {
+                Some(left) => Typed {
+                    value: self.concat(left.value, string.value)?,
+                    typ: ast::Type::String,
+                },
+                None => string,
+            });
+        }
+
+        return match result {
+            Some(result) => Ok(result),
+            None => self.string_literal("", position),
+        };
+    }
+
+    /// Converts the value to the string that the interpreter shows for it.
+    fn string_of(&mut self, value: Typed, position: &Span) -> Result<Typed> {
+        let pointer = self.module.target_config().pointer_type();
+        let value = match value.typ {
+            ast::Type::String => value.value,
+            ast::Type::Integer => {
+                self.call_runtime("x_string_from_int", &[types::I64], pointer, &[value.value])?
+            }
+            ast::Type::Float => self.call_runtime(
+                "x_string_from_float",
+                &[types::F64],
+                pointer,
+                &[value.value],
+            )?,
+            ast::Type::Boolean => {
+                let t = self.string_literal("true", position)?;
+                let f = self.string_literal("false", position)?;
+                self.builder.ins().select(value.value, t.value, f.value)
+            }
+            typ => {
+                return Err(Error::Unsupported {
+                    what: format!("interpolation of {}", type_name(&typ)),
+                    position: position.clone(),
+                })
+            }
+        };
+        return Ok(Typed {
+            value,
+            typ: ast::Type::String,
+        });
+    }
+
+    /// Converts the value to the string that `Output` shows for it,
+    /// which writes results as `Ok(value)` and `Err(error)`.
+    fn display(&mut self, value: Typed, position: &Span) -> Result<Value> {
+        let ast::Type::Result(typ) = &value.typ else {
+            return Ok(self.string_of(value, position)?.value);
+        };
+
+        let pointer = self.module.target_config().pointer_type();
+        let tag = self
+            .builder
+            .ins()
+            .load(types::I64, MemFlags::trusted(), value.value, 0);
+        let ok_block = self.builder.create_block();
+        let error_block = self.builder.create_block();
+        let merge_block = self.builder.create_block();
+        self.builder.append_block_param(merge_block, pointer);
+        self.builder
+            .ins()
+            .brif(tag, error_block, &[], ok_block, &[]);
+
+        for (block, name, typ) in [
+            (ok_block, "Ok", &typ.value),
+            (error_block, "Err", &typ.error),
+        ] {
+            self.builder.switch_to_block(block);
+            self.builder.seal_block(block);
+            let cranelift_type =
+                cranelift_type(typ, pointer).ok_or_else(|| Error::Unsupported {
+                    what: format!("result of {}", type_name(typ)),
+                    position: position.clone(),
+                })?;
+            let inner = Typed {
+                value: self
+                    .builder
+                    .ins()
+                    .load(cranelift_type, MemFlags::trusted(), value.value, 8),
+                typ: (**typ).clone(),
+            };
+            let inner = self.display(inner, position)?;
+            let open = self.string_literal(&format!("{}(", name), position)?;
+            let close = self.string_literal(")", position)?;
+            let string = self.concat(open.value, inner)?;
+            let string = self.concat(string, close.value)?;
+            self.builder.ins().jump(merge_block, &[string]);
+        }
+
+        self.builder.switch_to_block(merge_block);
+        self.builder.seal_block(merge_block);
+        return Ok(self.builder.block_params(merge_block)[0]);
+    }
+
+    fn concat(&mut self, left: Value, right: Value) -> Result<Value> {
+        let pointer = self.module.target_config().pointer_type();
+        return self.call_runtime(
+            "x_string_concat",
+            &[pointer, pointer],
+            pointer,
+            &[left, right],
+        );
+    }
+
+    fn translate_prefix_expression(
+        &mut self,
+        prefix: &ast::PrefixExpression,
+        position: &Span,
+    ) -> Result<Typed> {
+        // the literal of `-9223372036854775808` alone does not fit into an `int`
+        if let (Token::Minus, ast::ExpressionKind::Integer(i)) =
+            (&prefix.operator, &prefix.right.kind)
+        {
+            let value = lexer::negated_integer_value(i).ok_or_else(|| Error::InvalidLiteral {
+                literal: i.into(),
+                position: prefix.right.position.clone(),
+            })?;
+            return Ok(Typed {
+                value: self.builder.ins().iconst(types::I64, value),
+                typ: ast::Type::Integer,
+            });
+        }
+        let right = self.translate_expression(&prefix.right)?;
+        let ins = self.builder.ins();
+
+        let value = match (&prefix.operator, &right.typ) {
+            (Token::Minus, ast::Type::Integer) => ins.ineg(right.value),
+            (Token::Minus, ast::Type::Float) => ins.fneg(right.value),
+            (Token::Tilde, ast::Type::Integer) => ins.bnot(right.value),
+            (Token::ExclamationMark, ast::Type::Boolean) => {
+                ins.icmp_imm(IntCC::Equal, right.value, 0)
+            }
+            (operator, typ) => {
+                return Err(Error::Unsupported {
+                    what: format!("operator {} on {}", operator, type_name(typ)),
+                    position: position.clone(),
+                })
+            }
+        };
+        return Ok(Typed {
+            value,
+            typ: right.typ,
+        });
+    }
+
+    fn translate_infix_expression(
+        &mut self,
+        infix: &ast::InfixExpression,
+        position: &Span,
+    ) -> Result<Typed> {
+        if matches!(infix.operator, Token::And | Token::Or) {
+            return self.translate_logical_expression(infix);
+        }
+
+        let left = self.translate_expression(&infix.left)?;
+        let right = self.translate_expression(&infix.right)?;
+        self.expect_type(&left.typ, &right, &infix.right.position)?;
+
+        return self.translate_operation(&infix.operator, left, right, position);
+    }
+
+    /// Applies a binary operator to operands of the same type.
+    fn translate_operation(
+        &mut self,
+        operator: &Token,
+        left: Typed,
+        right: Typed,
+        position: &Span,
+    ) -> Result<Typed> {
+        use ast::Type::{Boolean, Float, Integer, String};
+
+        let typ = if is_comparison(operator) {
+            Boolean
+        } else {
+            left.typ.clone()
+        };
+        let (left, right, operand) = (left.value, right.value, left.typ);
+        let ins = self.builder.ins();
+
+        let value = match (operator, &operand) {
+            (Token::Plus, Integer) => ins.iadd(left, right),
+            (Token::Minus, Integer) => ins.isub(left, right),
+            (Token::Asterisk, Integer) => ins.imul(left, right),
+            (Token::Slash | Token::Percent, Integer) => {
+                self.translate_division(operator, left, right, position)
+            }
+            (Token::LessThan, Integer) => ins.icmp(IntCC::SignedLessThan, left, right),
+            (Token::GreaterThan, Integer) => ins.icmp(IntCC::SignedGreaterThan, left, right),
+            (Token::LessThanOrEqual, Integer) => {
+                ins.icmp(IntCC::SignedLessThanOrEqual, left, right)
+            }
+            (Token::GreaterThanOrEqual, Integer) => {
+                ins.icmp(IntCC::SignedGreaterThanOrEqual, left, right)
+            }
+            (Token::Ampersand, Integer) => ins.band(left, right),
+            (Token::Pipe, Integer) => ins.bor(left, right),
+            (Token::Caret, Integer) => ins.bxor(left, right),
+            (Token::ShiftLeft, Integer) => ins.ishl(left, right),
+            (Token::ShiftRight, Integer) => ins.sshr(left, right),
+            (Token::Equal, Integer | Boolean) => ins.icmp(IntCC::Equal, left, right),
+            (Token::NotEqual, Integer | Boolean) => ins.icmp(IntCC::NotEqual, left, right),
+
+            (Token::Plus, Float) => ins.fadd(left, right),
+            (Token::Minus, Float) => ins.fsub(left, right),
+            (Token::Asterisk, Float) => ins.fmul(left, right),
+            (Token::Slash, Float) => ins.fdiv(left, right),
+            (Token::Percent, Float) => self.call_runtime(
+                "fmod",
+                &[types::F64, types::F64],
+                types::F64,
+                &[left, right],
+            )?,
+            (Token::LessThan, Float) => ins.fcmp(FloatCC::LessThan, left, right),
+            (Token::GreaterThan, Float) => ins.fcmp(FloatCC::GreaterThan, left, right),
+            (Token::LessThanOrEqual, Float) => ins.fcmp(FloatCC::LessThanOrEqual, left, right),
+            (Token::GreaterThanOrEqual, Float) => {
+                ins.fcmp(FloatCC::GreaterThanOrEqual, left, right)
+            }
+            (Token::Equal, Float) => ins.fcmp(FloatCC::Equal, left, right),
+            (Token::NotEqual, Float) => ins.fcmp(FloatCC::NotEqual, left, right),
+
+            (Token::Plus, String) => self.concat(left, right)?,
+            // strings compare byte by byte like in the interpreter
+            (operator, String) if is_comparison(operator) => {
+                let pointer = self.module.target_config().pointer_type();
+                let ordering = self.call_runtime(
+                    "x_string_compare",
+                    &[pointer, pointer],
+                    types::I64,
+                    &[left, right],
+                )?;
+                let condition = match operator {
+                    Token::LessThan => IntCC::SignedLessThan,
+                    Token::GreaterThan => IntCC::SignedGreaterThan,
+                    Token::LessThanOrEqual => IntCC::SignedLessThanOrEqual,
+                    Token::GreaterThanOrEqual => IntCC::SignedGreaterThanOrEqual,
+                    Token::Equal => IntCC::Equal,
+                    _ => IntCC::NotEqual,
+                };
+                self.builder.ins().icmp_imm(condition, ordering, 0)
+            }
+
+            (operator, typ) => {
+                return Err(Error::Unsupported {
+                    what: format!("operator {} on {}", operator, type_name(typ)),
+                    position: position.clone(),
+                })
+            }
+        };
+
+        return Ok(Typed { value, typ });
+    }
+
+    /// Divides integers like the interpreter does: a zero divisor raises a runtime error
+    /// and `i64::MIN / -1`, which `sdiv` and `srem` trap on, wraps around.
+    fn translate_division(
+        &mut self,
+        operator: &Token,
+        left: Value,
+        right: Value,
+        position: &Span,
+    ) -> Value {
+        let error_block = self.builder.create_block();
+        let divide_block = self.builder.create_block();
+        self.builder
+            .ins()
+            .brif(right, divide_block, &[], error_block, &[]);
+
+        self.builder.switch_to_block(error_block);
+        self.builder.seal_block(error_block);
+        self.raise(Error::DivisionByZero {
+            position: position.clone(),
+        });
+
+        self.builder.switch_to_block(divide_block);
+        self.builder.seal_block(divide_block);
+        let minus_one = self.builder.ins().icmp_imm(IntCC::Equal, right, -1);
+        let one = self.builder.ins().iconst(types::I64, 1);
+        let divisor = self.builder.ins().select(minus_one, one, right);
+        if *operator == Token::Slash {
+            let quotient = self.builder.ins().sdiv(left, divisor);
+            let negated = self.builder.ins().ineg(left);
+            return self.builder.ins().select(minus_one, negated, quotient);
+        } else {
+            let remainder = self.builder.ins().srem(left, divisor);
+            let zero = self.builder.ins().iconst(types::I64, 0);
+            return self.builder.ins().select(minus_one, zero, remainder);
+        }
+    }
+
+    /// Translates `&&` and `||`, which branch around the right operand
+    /// if the left one already decides the result.
+    fn translate_logical_expression(&mut self, infix: &ast::InfixExpression) -> Result<Typed> {
+        let left = self.translate_expression(&infix.left)?;
+        self.expect_type(&ast::Type::Boolean, &left, &infix.left.position)?;
+
+        let right_block = self.builder.create_block();
+        let merge_block = self.builder.create_block();
+        self.builder.append_block_param(merge_block, types::I8);
+
+        if infix.operator == Token::And {
+            self.builder
+                .ins()
+                .brif(left.value, right_block, &[], merge_block, &[left.value]);
+        } else {
+            self.builder
+                .ins()
+                .brif(left.value, merge_block, &[left.value], right_block, &[]);
+        }
+
+        self.builder.switch_to_block(right_block);
+        self.builder.seal_block(right_block);
+        let right = self.translate_expression(&infix.right)?;
+        self.expect_type(&ast::Type::Boolean, &right, &infix.right.position)?;
+        self.builder.ins().jump(merge_block, &[right.value]);
+
+        self.builder.switch_to_block(merge_block);
+        self.builder.seal_block(merge_block);
+
+        return Ok(Typed {
+            value: self.builder.block_params(merge_block)[0],
+            typ: ast::Type::Boolean,
+        });
+    }
+
+    /// Calls a top-level function directly and a function value through its closure.
+    /// Returns `None` if the function has no return value.
+    fn translate_call_expression(
+        &mut self,
+        call: &ast::CallExpression,
+        position: &Span,
+    ) -> Result<Option<Typed>> {
+        let name = match &call.callee.kind {
+            ast::ExpressionKind::Identifier(name)
+                if self.lookup_variable(name, position).is_err() =>
+            {
+                name
+            }
+            _ => return self.translate_closure_call(call, position),
+        };
+        if !self.functions.contains_key(name) && RESULT_VARIANTS.contains(&name.as_str()) {
+            return self.translate_result(name, call, position).map(Some);
+        }
+        if !self.functions.contains_key(name) && name == PRINT {
+            self.translate_print(call, position)?;
+            return Ok(None);
+        }
+        let function = self
+            .functions
+            .get(name)
+            .ok_or_else(|| Error::UndefinedFunction { name: name.clone() })?;
+
+        let arguments = self.translate_arguments(name, &function.typ, call, position)?;
+
+        let callee = self
+            .module
+            .declare_func_in_func(function.id, self.builder.func);
+        let call = self.builder.ins().call(callee, &arguments);
+        let result = self.builder.inst_results(call).first().copied();
+        self.check_error();
+
+        return Ok(result.map(|value| Typed {
+            value,
+            typ: *function
+                .typ
+                .return_type
+                .clone()
+                .expect("function returns a value"),
+        }));
+    }
+
+    /// Calls the function of the closure that the callee evaluates to with the closure
+    /// as its first argument.
+    fn translate_closure_call(
+        &mut self,
+        call: &ast::CallExpression,
+        position: &Span,
+    ) -> Result<Option<Typed>> {
+        let closure = self.translate_expression(&call.callee)?;
+        let ast::Type::Function(typ) = closure.typ else {
+            return Err(Error::TypeMismatch {
+                want: "function".into(),
+                got: type_name(&closure.typ),
+                position: call.callee.position.clone(),
+            });
+        };
+        let mut arguments = vec![closure.value];
+        arguments.extend(self.translate_arguments("fn", &typ, call, position)?);
+
+        let signature = signature(&*self.module, &typ, true, position)?;
+        let signature = self.builder.import_signature(signature);
+        let pointer = self.module.target_config().pointer_type();
+        let function = self
+            .builder
+            .ins()
+            .load(pointer, MemFlags::trusted(), closure.value, 0);
+        let call = self
+            .builder
+            .ins()
+            .call_indirect(signature, function, &arguments);
+        let result = self.builder.inst_results(call).first().copied();
+        self.check_error();
+
+        return Ok(result.map(|value| Typed {
+            value,
+            typ: *typ.return_type.clone().expect("function returns a value"),
+        }));
+    }
+
+    fn translate_arguments(
+        &mut self,
+        name: &str,
+        typ: &ast::FunctionType,
+        call: &ast::CallExpression,
+        position: &Span,
+    ) -> Result<Vec<Value>> {
+        if typ.parameters.len() != call.arguments.len() {
+            return Err(Error::ArgumentCount {
+                name: name.into(),
+                want: typ.parameters.len(),
+                got: call.arguments.len(),
+                position: position.clone(),
+            });
+        }
+
+        let mut arguments = vec![];
+        for (parameter, argument) in typ.parameters.iter().zip(&call.arguments) {
+            let value = self.translate_expression(argument)?;
+            self.expect_type(&parameter.typ, &value, &argument.position)?;
+            arguments.push(value.value);
+        }
+        return Ok(arguments);
+    }
+
+    /// Creates the closure of a lambda, which holds a pointer to the function of the lambda
+    /// and copies of the bindings the lambda captures. The function is defined later.
+    fn translate_closure(
+        &mut self,
+        lambda: &ast::LambdaExpression,
+        position: &Span,
+    ) -> Result<Typed> {
+        let pointer = self.module.target_config().pointer_type();
+        let names = self.lambdas.captures.get(position).cloned();
+        let mut captures = vec![];
+        let mut values = vec![];
+        for name in names.unwrap_or_default() {
+            let (variable, typ) = self.lookup_variable(&name, position)?;
+            if cranelift_type(&typ, pointer).is_none() {
+                return Err(Error::Unsupported {
+                    what: format!("capture of {}", type_name(&typ)),
+                    position: position.clone(),
+                });
+            }
+            values.push(self.builder.use_var(variable));
+            captures.push((name, typ));
+        }
+
+        let signature = signature(&*self.module, &lambda.typ, true, position)?;
+        let id = self.module.declare_anonymous_function(&signature)?;
+        let function = self.module.declare_func_in_func(id, self.builder.func);
+        let function = self.builder.ins().func_addr(pointer, function);
+
+        let closure = self.allocate(8 * (values.len() as i64 + 1))?;
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), function, closure, 0);
+        for (i, value) in values.into_iter().enumerate() {
+            self.builder
+                .ins()
+                .store(MemFlags::trusted(), value, closure, 8 * (i as i32 + 1));
+        }
+
+        self.lambdas.pending.push(Lambda {
+            id,
+            lambda: lambda.clone(),
+            captures,
+        });
+        return Ok(Typed {
+            value: closure,
+            typ: ast::Type::Function(lambda.typ.clone()),
+        });
+    }
+
+    /// Allocates the result `Ok(value)` or `Err(error)`. The type of the other variant
+    /// is unknown until the result is returned or passed to a function.
+    fn translate_result(
+        &mut self,
+        name: &str,
+        call: &ast::CallExpression,
+        position: &Span,
+    ) -> Result<Typed> {
+        let [argument] = call.arguments.as_slice() else {
+            return Err(Error::ArgumentCount {
+                name: name.into(),
+                want: 1,
+                got: call.arguments.len(),
+                position: position.clone(),
+            });
+        };
+        let value = self.translate_expression(argument)?;
+        let pointer = self.module.target_config().pointer_type();
+        if cranelift_type(&value.typ, pointer).is_none() {
+            return Err(Error::Unsupported {
+                what: format!("result of {}", type_name(&value.typ)),
+                position: argument.position.clone(),
+            });
+        }
+
+        let result = self.allocate(16)?;
+        let tag = self
+            .builder
+            .ins()
+            .iconst(types::I64, (name == "Err") as i64);
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), tag, result, 0);
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), value.value, result, 8);
+
+        let (value, error) = match name {
+            "Ok" => (value.typ, ast::Type::Unknown),
+            _ => (ast::Type::Unknown, value.typ),
+        };
+        return Ok(Typed {
+            value: result,
+            typ: ast::Type::Result(ast::ResultType {
+                value: Box::new(value),
+                error: Box::new(error),
+            }),
+        });
+    }
+
+    /// Writes the string and a newline to stdout through the runtime library.
+    fn translate_print(&mut self, call: &ast::CallExpression, position: &Span) -> Result<()> {
+        let [argument] = call.arguments.as_slice() else {
+            return Err(Error::ArgumentCount {
+                name: PRINT.into(),
+                want: 1,
+                got: call.arguments.len(),
+                position: position.clone(),
+            });
+        };
+        let value = self.translate_expression(argument)?;
+        self.expect_type(&ast::Type::String, &value, &argument.position)?;
+        return self.print("x_print", value.value);
+    }
+
+    /// Calls the function `name` of the runtime library that writes the string and a newline
+    /// to stdout or stderr.
+    fn print(&mut self, name: &str, string: Value) -> Result<()> {
+        let pointer = self.module.target_config().pointer_type();
+        let mut signature = self.module.make_signature();
+        signature.params.push(AbiParam::new(pointer));
+        let id = self
+            .module
+            .declare_function(name, Linkage::Import, &signature)?;
+        let function = self.module.declare_func_in_func(id, self.builder.func);
+        self.builder.ins().call(function, &[string]);
+        return Ok(());
+    }
+
+    /// Translates `result?`, which returns an `Err` from the function as it is
+    /// and evaluates to the value of an `Ok`.
+    fn translate_propagation(
+        &mut self,
+        postfix: &ast::PostfixExpression,
+        position: &Span,
+    ) -> Result<Typed> {
+        let result = self.translate_expression(&postfix.left)?;
+        let ast::Type::Result(typ) = &result.typ else {
+            return Err(Error::TypeMismatch {
+                want: "result".into(),
+                got: type_name(&result.typ),
+                position: postfix.left.position.clone(),
+            });
+        };
+        // the error is returned in the same allocation, so only its type has to match
+        let error = ast::Type::Result(ast::ResultType {
+            value: Box::new(ast::Type::Unknown),
+            error: typ.error.clone(),
+        });
+        match &self.return_type {
+            Some(return_type @ ast::Type::Result(_)) if is_compatible(return_type, &error) => {}
+            return_type => {
+                return Err(Error::TypeMismatch {
+                    want: return_type
+                        .as_ref()
+                        .map_or("no return value".into(), type_name),
+                    got: type_name(&error),
+                    position: position.clone(),
+                })
+            }
+        }
+        let pointer = self.module.target_config().pointer_type();
+        let value = cranelift_type(&typ.value, pointer).ok_or_else(|| Error::Unsupported {
+            what: format!("result of {}", type_name(&typ.value)),
+            position: postfix.left.position.clone(),
+        })?;
+
+        let tag = self
+            .builder
+            .ins()
+            .load(types::I64, MemFlags::trusted(), result.value, 0);
+        let error_block = self.builder.create_block();
+        let ok_block = self.builder.create_block();
+        self.builder
+            .ins()
+            .brif(tag, error_block, &[], ok_block, &[]);
+
+        self.builder.switch_to_block(error_block);
+        self.builder.seal_block(error_block);
+        self.builder.ins().return_(&[result.value]);
+
+        self.builder.switch_to_block(ok_block);
+        self.builder.seal_block(ok_block);
+        return Ok(Typed {
+            value: self
+                .builder
+                .ins()
+                .load(value, MemFlags::trusted(), result.value, 8),
+            typ: (*typ.value).clone(),
+        });
+    }
+
+    /// Calls a function of the runtime library, which the JIT provides itself
+    /// and executables are linked with. `fmod` comes from libm instead, as Cranelift
+    /// has no floating point remainder instruction, and `malloc` from libc.
+    fn call_runtime(
+        &mut self,
+        name: &str,
+        parameters: &[Type],
+        return_type: Type,
+        arguments: &[Value],
+    ) -> Result<Value> {
+        let mut signature = self.module.make_signature();
+        for parameter in parameters {
+            signature.params.push(AbiParam::new(*parameter));
+        }
+        signature.returns.push(AbiParam::new(return_type));
+
+        let id = self
+            .module
+            .declare_function(name, Linkage::Import, &signature)?;
+        let function = self.module.declare_func_in_func(id, self.builder.func);
+
+        let call = self.builder.ins().call(function, arguments);
+        return Ok(self.builder.inst_results(call)[0]);
+    }
+
+    /// Allocates `size` bytes on the heap. Like strings, they are never freed.
+    fn allocate(&mut self, size: i64) -> Result<Value> {
+        let pointer = self.module.target_config().pointer_type();
+        let size = self.builder.ins().iconst(pointer, size);
+        return self.call_runtime("malloc", &[pointer], pointer, &[size]);
+    }
+
+    /// Reports the runtime error to the caller through `x.error` and returns from the function.
+    fn raise(&mut self, error: Error) {
+        self.runtime.errors.push(error);
+        let code = self.runtime.errors.len() as i64;
+
+        let address = self.error_address();
+        let code = self.builder.ins().iconst(types::I64, code);
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), code, address, 0);
+        self.return_zero();
+    }
+
+    /// Returns from the function if the call that was just made raised a runtime error.
+    fn check_error(&mut self) {
+        let address = self.error_address();
+        let code = self
+            .builder
+            .ins()
+            .load(types::I64, MemFlags::trusted(), address, 0);
+
+        let error_block = self.builder.create_block();
+        let continue_block = self.builder.create_block();
+        self.builder
+            .ins()
+            .brif(code, error_block, &[], continue_block, &[]);
+
+        self.builder.switch_to_block(error_block);
+        self.builder.seal_block(error_block);
+        self.return_zero();
+
+        self.builder.switch_to_block(continue_block);
+        self.builder.seal_block(continue_block);
+    }
+
+    fn error_address(&mut self) -> Value {
+        let error = self
+            .module
+            .declare_data_in_func(self.runtime.error, self.builder.func);
+        let pointer = self.module.target_config().pointer_type();
+        return self.builder.ins().global_value(pointer, error);
+    }
+
+    /// Returns the zero value of the return type of the function, if it has one.
+    /// Only used while a runtime error is pending, which makes callers ignore the value.
+    fn return_zero(&mut self) {
+        let pointer = self.module.target_config().pointer_type();
+        match self
+            .return_type
+            .as_ref()
+            .and_then(|typ| cranelift_type(typ, pointer))
+        {
+            Some(typ) => {
+                let zero = self.zero(typ);
+                self.builder.ins().return_(&[zero]);
+            }
+            None => {
+                self.builder.ins().return_(&[]);
+            }
+        }
+    }
+
+    fn declare_variable(&mut self, name: &str, value: Typed) {
+        let typ = self.builder.func.dfg.value_type(value.value);
+        let variable = Variable::new(self.variables);
+        self.variables += 1;
+
+        self.builder.declare_var(variable, typ);
+        self.builder.def_var(variable, value.value);
+        self.scopes
+            .last_mut()
+            .expect("function has no scope")
+            .insert(name.into(), (variable, value.typ));
+    }
+
+    fn lookup_variable(&self, name: &str, position: &Span) -> Result<(Variable, ast::Type)> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(variable) = scope.get(name) {
+                return Ok(variable.clone());
+            }
+        }
+
+        return Err(Error::UndefinedVariable {
+            name: name.into(),
+            position: position.clone(),
+        });
+    }
+
+    fn expect_type(&self, want: &ast::Type, value: &Typed, position: &Span) -> Result<()> {
+        if !is_compatible(want, &value.typ) {
+            return Err(Error::TypeMismatch {
+                want: type_name(want),
+                got: type_name(&value.typ),
+                position: position.clone(),
+            });
+        }
+        return Ok(());
+    }
+
+    fn zero(&mut self, typ: Type) -> Value {
+        if typ == types::F64 {
+            return self.builder.ins().f64const(0.0);
+        } else {
+            return self.builder.ins().iconst(typ, 0);
+        }
+    }
+}
+
+/// Returns whether a value of type `got` can be used as a value of type `want`.
+/// The unknown type of the other variant of `Ok(value)` and `Err(error)` is compatible with every type.
+fn is_compatible(want: &ast::Type, got: &ast::Type) -> bool {
+    return match (want, got) {
+        (ast::Type::Unknown, _) | (_, ast::Type::Unknown) => true,
+        (ast::Type::Result(want), ast::Type::Result(got)) => {
+            is_compatible(&want.value, &got.value) && is_compatible(&want.error, &got.error)
+        }
+        // parameter names do not matter
+        (ast::Type::Function(want), ast::Type::Function(got)) => {
+            want.parameters.len() == got.parameters.len()
+                && want
+                    .parameters
+                    .iter()
+                    .zip(&got.parameters)
+                    .all(|(want, got)| is_compatible(&want.typ, &got.typ))
+                && match (&want.return_type, &got.return_type) {
+                    (Some(want), Some(got)) => is_compatible(want, got),
+                    (want, got) => want.is_none() && got.is_none(),
+                }
+        }
+        _ => want == got,
+    };
+}
+
+/// Returns whether the operator compares its operands and results in a `bool`.
+fn is_comparison(operator: &Token) -> bool {
+    return matches!(
+        operator,
+        Token::Equal
+            | Token::NotEqual
+            | Token::LessThan
+            | Token::GreaterThan
+            | Token::LessThanOrEqual
+            | Token::GreaterThanOrEqual
+    );
+}
@@ -0,0 +1,162 @@
+use std::ffi::{c_char, CStr};
+
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::default_libcall_names;
+
+use super::*;
+use crate::token::Span;
+
+/// Compiles X programs into memory and executes them in-process.
+pub struct Jit {
+    generator: Generator<JITModule>,
+}
+
+impl Jit {
+    pub fn new() -> Result<Self> {
+        let mut builder = JITBuilder::new(default_libcall_names())?;
+        builder.symbol("fmod", fmod as *const u8);
+        for (name, function) in runtime::symbols() {
+            builder.symbol(name, function);
+        }
+
+        return Ok(Self {
+            generator: Generator::new(JITModule::new(builder))?,
+        });
+    }
+
+    /// Compiles all functions of the file and makes them ready for execution.
+    pub fn compile(&mut self, file: &ast::File) -> Result<()> {
+        self.generator.generate(file)?;
+        self.generator.module.finalize_definitions()?;
+        return Ok(());
+    }
+
+    /// Executes the compiled function `name`, which must not take any parameters.
+    pub fn run(&mut self, name: &str) -> Result<Output> {
+        let function = self
+            .generator
+            .functions
+            .get(name)
+            .ok_or_else(|| Error::UndefinedFunction { name: name.into() })?;
+
+        if !function.typ.parameters.is_empty() {
+            return Err(Error::Unsupported {
+                what: format!("running function {} with parameters", name),
+                position: Span::new(),
+            });
+        }
+
+        let code = self.generator.module.get_finalized_function(function.id);
+        let (error, _) = self
+            .generator
+            .module
+            .get_finalized_data(self.generator.runtime.error);
+        let error = error as *mut i64;
+
+        // SAFETY: the signature of the compiled function is derived from `function.typ`,
+        // which is matched exhaustively against the supported return types below.
+        // `error` points to the 8 bytes of `x.error`, which the compiled code only uses while it runs.
+        // The returned value is only read if the function did not raise an error.
+        unsafe {
+            *error = 0;
+            let errors = &self.generator.runtime.errors;
+            let raised = || match *error {
+                0 => Ok(()),
+                n => Err(errors[n as usize - 1].clone()),
+            };
+            return match function.typ.return_type.as_deref() {
+                None => {
+                    let f = std::mem::transmute::<*const u8, extern "C" fn()>(code);
+                    f();
+                    raised()?;
+                    Ok(Output::Unit)
+                }
+                Some(ast::Type::Boolean) => {
+                    let f = std::mem::transmute::<*const u8, extern "C" fn() -> i8>(code);
+                    let b = f();
+                    raised()?;
+                    Ok(Output::Boolean(b != 0))
+                }
+                Some(ast::Type::Integer) => {
+                    let f = std::mem::transmute::<*const u8, extern "C" fn() -> i64>(code);
+                    let i = f();
+                    raised()?;
+                    Ok(Output::Integer(i))
+                }
+                Some(ast::Type::Float) => {
+                    let f = std::mem::transmute::<*const u8, extern "C" fn() -> f64>(code);
+                    let x = f();
+                    raised()?;
+                    Ok(Output::Float(x))
+                }
+                Some(ast::Type::String) => {
+                    let f =
+                        std::mem::transmute::<*const u8, extern "C" fn() -> *const c_char>(code);
+                    let s = f();
+                    raised()?;
+                    Ok(string_output(s))
+                }
+                Some(ast::Type::Result(typ)) => {
+                    let f = std::mem::transmute::<*const u8, extern "C" fn() -> *const u8>(code);
+                    let result = f();
+                    raised()?;
+                    result_output(result, typ)
+                }
+                Some(typ) => Err(Error::Unsupported {
+                    what: format!("return type {}", type_name(typ)),
+                    position: Span::new(),
+                }),
+            };
+        }
+    }
+}
+
+/// Reads the string that `s` points to.
+///
+/// # Safety
+///
+/// `s` must point to a null-terminated string that compiled code created.
+unsafe fn string_output(s: *const c_char) -> Output {
+    assert!(!s.is_null(), "compiled code returned a null string");
+    return Output::String(CStr::from_ptr(s).to_string_lossy().into());
+}
+
+/// Reads the result that `result` points to.
+///
+/// # Safety
+///
+/// `result` must point to a result of the type that compiled code allocated.
+unsafe fn result_output(result: *const u8, typ: &ast::ResultType) -> Result<Output> {
+    assert!(!result.is_null(), "compiled code returned a null result");
+    let (typ, wrap): (_, fn(Box<Output>) -> Output) = match *(result as *const i64) {
+        0 => (&*typ.value, Output::Ok),
+        _ => (&*typ.error, Output::Err),
+    };
+    let value = result.add(8);
+    let output = match typ {
+        ast::Type::Boolean => Output::Boolean(*(value as *const i8) != 0),
+        ast::Type::Integer => Output::Integer(*(value as *const i64)),
+        ast::Type::Float => Output::Float(*(value as *const f64)),
+        ast::Type::String => string_output(*(value as *const *const c_char)),
+        ast::Type::Result(inner) => result_output(*(value as *const *const u8), inner)?,
+        typ => {
+            return Err(Error::Unsupported {
+                what: format!("result of {}", type_name(typ)),
+                position: Span::new(),
+            })
+        }
+    };
+    return Ok(wrap(Box::new(output)));
+}
+
+/// The host process is not guaranteed to link libm, so the JIT provides its own `fmod`.
+extern "C" fn fmod(x: f64, y: f64) -> f64 {
+    return x % y;
+}
+
+/// Compiles the file and executes its `main` function.
+pub fn run(file: &ast::File) -> Result<Output> {
+    let mut jit = Jit::new()?;
+    jit.compile(file)?;
+    return jit.run("main");
+}
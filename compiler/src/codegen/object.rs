@@ -0,0 +1,154 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use cranelift::codegen::settings::Flags;
+use cranelift_module::{default_libcall_names, DataDescription, DataId};
+use cranelift_object::{ObjectBuilder, ObjectModule};
+
+use super::*;
+use crate::token::Span;
+
+/// Compiles X programs ahead of time into a relocatable object file.
+pub struct Object {
+    generator: Generator<ObjectModule>,
+}
+
+impl Object {
+    /// Creates an object for the host target. `name` is recorded as the object's file symbol.
+    pub fn new(name: &str) -> Result<Self> {
+        let mut flags = settings::builder();
+        flags
+            .set("is_pic", "true")
+            .map_err(|e| Error::ModuleError(e.to_string()))?;
+
+        let isa = cranelift_native::builder()
+            .map_err(|e| Error::ModuleError(e.into()))?
+            .finish(Flags::new(flags))
+            .map_err(|e| Error::ModuleError(e.to_string()))?;
+
+        let builder = ObjectBuilder::new(isa, name, default_libcall_names())?;
+        return Ok(Self {
+            generator: Generator::new(ObjectModule::new(builder))?,
+        });
+    }
+
+    /// Compiles all functions of the file and adds a C `main` entry shim that calls the X `main`.
+    pub fn compile(&mut self, file: &ast::File) -> Result<()> {
+        self.generator.generate(file)?;
+        self.define_entry()?;
+        return Ok(());
+    }
+
+    /// Returns the bytes of the finished object file.
+    pub fn emit(self) -> Result<Vec<u8>> {
+        return self
+            .generator
+            .module
+            .finish()
+            .emit()
+            .map_err(|e| Error::ModuleError(e.to_string()));
+    }
+
+    /// Defines `int main(void)`, which calls the X `main` and prints its result.
+    fn define_entry(&mut self) -> Result<()> {
+        let function = self
+            .generator
+            .functions
+            .get("main")
+            .ok_or_else(|| Error::UndefinedFunction {
+                name: "main".into(),
+            })?
+            .clone();
+
+        if !function.typ.parameters.is_empty() {
+            return Err(Error::Unsupported {
+                what: "main function with parameters".into(),
+                position: Span::new(),
+            });
+        }
+
+        let messages = self.define_error_messages()?;
+
+        let module = &mut self.generator.module;
+        let mut signature = module.make_signature();
+        signature.returns.push(AbiParam::new(types::I32));
+        let id = module.declare_function("main", Linkage::Export, &signature)?;
+
+        return self.generator.define(id, |translator| {
+            translator.translate_entry(&function, messages)
+        });
+    }
+
+    /// Defines the messages of the runtime errors that the compiled code can raise,
+    /// followed by a table with the offset and the length of each message.
+    /// Returns `None` if the code cannot raise any.
+    fn define_error_messages(&mut self) -> Result<Option<(DataId, DataId)>> {
+        let errors = &self.generator.runtime.errors;
+        if errors.is_empty() {
+            return Ok(None);
+        }
+
+        let mut messages = vec![];
+        let mut table = vec![];
+        for error in errors {
+            let message = format!("{}\n", error);
+            table.extend((messages.len() as i64).to_ne_bytes());
+            table.extend((message.len() as i64).to_ne_bytes());
+            messages.extend(message.into_bytes());
+        }
+
+        let module = &mut self.generator.module;
+        let mut ids = vec![];
+        for (name, contents) in [("x.error.messages", messages), ("x.error.table", table)] {
+            let id = module.declare_data(name, Linkage::Local, false, false)?;
+            let mut description = DataDescription::new();
+            description.define(contents.into_boxed_slice());
+            description.set_align(8);
+            module.define_data(id, &description)?;
+            ids.push(id);
+        }
+
+        return Ok(Some((ids[0], ids[1])));
+    }
+}
+
+/// Compiles the file into the bytes of a relocatable object file.
+pub fn build(file: &ast::File, name: &str) -> Result<Vec<u8>> {
+    let mut object = Object::new(name)?;
+    object.compile(file)?;
+    return object.emit();
+}
+
+/// Links an object file produced by `build` into an executable using the system `cc`,
+/// which compiles the runtime library from the source passed on stdin.
+pub fn link(object: &Path, output: &Path) -> Result<()> {
+    let mut child = Command::new("cc")
+        .arg(object)
+        .args(["-x", "c", "-"])
+        .arg("-o")
+        .arg(output)
+        .arg("-lm")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::LinkError(e.to_string()))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(runtime::SOURCE.as_bytes())
+        .map_err(|e| Error::LinkError(e.to_string()))?;
+    let result = child
+        .wait_with_output()
+        .map_err(|e| Error::LinkError(e.to_string()))?;
+
+    if !result.status.success() {
+        return Err(Error::LinkError(
+            String::from_utf8_lossy(&result.stderr).trim().into(),
+        ));
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,108 @@
+// Runtime library of executables built from X programs.
+// The JIT provides the same functions in runtime.rs, and both have to agree with the interpreter.
+// Strings are null-terminated and live until the process exits, so they are never freed.
+
+#include <math.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static char *copy(const char *s, size_t length) {
+    char *result = malloc(length + 1);
+    memcpy(result, s, length);
+    result[length] = 0;
+    return result;
+}
+
+char *x_string_concat(const char *a, const char *b) {
+    size_t n = strlen(a), m = strlen(b);
+    char *result = malloc(n + m + 1);
+    memcpy(result, a, n);
+    memcpy(result + n, b, m + 1);
+    return result;
+}
+
+// Returns -1, 0 or 1 if `a` is less than, equal to or greater than `b`.
+int64_t x_string_compare(const char *a, const char *b) {
+    int c = strcmp(a, b);
+    return (c > 0) - (c < 0);
+}
+
+char *x_string_from_int(int64_t i) {
+    char buffer[32];
+    int length = snprintf(buffer, sizeof buffer, "%lld", (long long)i);
+    return copy(buffer, length);
+}
+
+// Writes the shortest digits that read back as `x` without an exponent, like Rust's `{}`.
+char *x_string_from_float(double x) {
+    if (isnan(x)) {
+        return copy("NaN", 3);
+    }
+    if (isinf(x)) {
+        return x < 0 ? copy("-inf", 4) : copy("inf", 3);
+    }
+
+    char scientific[32];
+    for (int precision = 0; precision < 17; precision++) {
+        snprintf(scientific, sizeof scientific, "%.*e", precision, x);
+        if (strtod(scientific, NULL) == x) {
+            break;
+        }
+    }
+
+    // `scientific` looks like -1.2345e+05
+    char digits[20];
+    int count = 0;
+    char *c = scientific;
+    int negative = *c == '-';
+    if (negative) {
+        c++;
+    }
+    for (; *c != 'e'; c++) {
+        if (*c != '.') {
+            digits[count++] = *c;
+        }
+    }
+    // number of digits before the decimal point
+    int point = atoi(c + 1) + 1;
+
+    char *result = malloc(count + abs(point) + 4);
+    char *r = result;
+    if (negative) {
+        *r++ = '-';
+    }
+    if (point <= 0) {
+        *r++ = '0';
+        *r++ = '.';
+        for (int i = 0; i < -point; i++) {
+            *r++ = '0';
+        }
+        memcpy(r, digits, count);
+        r += count;
+    } else if (point >= count) {
+        memcpy(r, digits, count);
+        r += count;
+        for (int i = count; i < point; i++) {
+            *r++ = '0';
+        }
+    } else {
+        memcpy(r, digits, point);
+        r += point;
+        *r++ = '.';
+        memcpy(r, digits + point, count - point);
+        r += count - point;
+    }
+    *r = 0;
+    return result;
+}
+
+void x_print(const char *s) {
+    puts(s);
+}
+
+void x_print_error(const char *s) {
+    fputs(s, stderr);
+    fputc('\n', stderr);
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+
+use cranelift_module::{DataDescription, DataId, Linkage, Module};
+
+use super::{Error, Result};
+
+/// Source of the runtime library that executables are linked with.
+/// It defines the same functions as the ones below, which the JIT provides to compiled code.
+pub(crate) const SOURCE: &str = include_str!("runtime.c");
+
+/// State that compiled functions share to report runtime errors to the code that started them,
+/// and the strings they use.
+///
+/// A function that raises an error stores its number in `x.error` and returns.
+/// Every caller checks the number after a call and returns as well,
+/// until the error reaches the JIT or the entry point of an executable.
+pub(crate) struct Runtime {
+    /// Data object with the number of the pending runtime error, or 0 if there is none.
+    pub error: DataId,
+    /// Errors that the compiled code can raise. Number `n` stands for `errors[n - 1]`.
+    pub errors: Vec<Error>,
+    /// Data objects of the string literals, which are stored once per module.
+    strings: HashMap<String, DataId>,
+}
+
+impl Runtime {
+    pub fn declare<M: Module>(module: &mut M) -> Result<Self> {
+        let error = module.declare_data("x.error", Linkage::Local, true, false)?;
+        let mut description = DataDescription::new();
+        description.define_zeroinit(8);
+        module.define_data(error, &description)?;
+
+        return Ok(Self {
+            error,
+            errors: vec![],
+            strings: HashMap::new(),
+        });
+    }
+
+    /// Returns the data object that holds the string as a null-terminated sequence of bytes.
+    pub fn string<M: Module>(&mut self, module: &mut M, string: &str) -> Result<DataId> {
+        if let Some(id) = self.strings.get(string) {
+            return Ok(*id);
+        }
+
+        let id = module.declare_anonymous_data(false, false)?;
+        let mut description = DataDescription::new();
+        let mut bytes = string.as_bytes().to_vec();
+        bytes.push(0);
+        description.define(bytes.into_boxed_slice());
+        module.define_data(id, &description)?;
+
+        self.strings.insert(string.into(), id);
+        return Ok(id);
+    }
+}
+
+/// Returns the functions of the runtime library by their symbol names.
+pub(crate) fn symbols() -> [(&'static str, *const u8); 6] {
+    return [
+        ("x_string_concat", string_concat as *const u8),
+        ("x_string_compare", string_compare as *const u8),
+        ("x_string_from_int", string_from_int as *const u8),
+        ("x_string_from_float", string_from_float as *const u8),
+        ("x_print", print as *const u8),
+        ("x_print_error", print_error as *const u8),
+    ];
+}
+
+// Strings are null-terminated and live until the process exits, so they are never freed.
+
+extern "C" fn string_concat(a: *const c_char, b: *const c_char) -> *const c_char {
+    // SAFETY: compiled code only passes pointers to null-terminated strings.
+    let (a, b) = unsafe { (CStr::from_ptr(a), CStr::from_ptr(b)) };
+    let mut bytes = a.to_bytes().to_vec();
+    bytes.extend(b.to_bytes());
+    return into_raw(bytes);
+}
+
+/// Returns -1, 0 or 1 if `a` is less than, equal to or greater than `b`.
+extern "C" fn string_compare(a: *const c_char, b: *const c_char) -> i64 {
+    // SAFETY: compiled code only passes pointers to null-terminated strings.
+    let (a, b) = unsafe { (CStr::from_ptr(a), CStr::from_ptr(b)) };
+    return a.cmp(b) as i64;
+}
+
+extern "C" fn string_from_int(i: i64) -> *const c_char {
+    return into_raw(i.to_string().into_bytes());
+}
+
+extern "C" fn string_from_float(x: f64) -> *const c_char {
+    return into_raw(x.to_string().into_bytes());
+}
+
+/// Writes the string and a newline to stdout, which `x run` prints the result of `main` to as well.
+extern "C" fn print(s: *const c_char) {
+    // SAFETY: compiled code only passes pointers to null-terminated strings.
+    let s = unsafe { CStr::from_ptr(s) };
+    println!("{}", s.to_string_lossy());
+}
+
+/// Writes the string and a newline to stderr.
+extern "C" fn print_error(s: *const c_char) {
+    // SAFETY: compiled code only passes pointers to null-terminated strings.
+    let s = unsafe { CStr::from_ptr(s) };
+    eprintln!("{}", s.to_string_lossy());
+}
+
+fn into_raw(bytes: Vec<u8>) -> *const c_char {
+    return CString::new(bytes)
+        .expect("strings do not contain null bytes")
+        .into_raw();
+}
@@ -0,0 +1,636 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+use crate::interp;
+use crate::parser::Parser;
+use crate::token::Span;
+
+#[test]
+fn run_expressions() {
+    let cases = &[
+        ("int", "1 + 2 * 3", Output::Integer(7)),
+        ("int", "(1 + 2) * 3", Output::Integer(9)),
+        ("int", "7 / 2 - 10 % 4", Output::Integer(1)),
+        ("int", "-5 + 2", Output::Integer(-3)),
+        ("float", "1.5 * 2.0 - 0.5", Output::Float(2.5)),
+        ("float", "7.5 % 2.0", Output::Float(1.5)),
+        ("int", "0xFF + 0o17 + 0b1010 + 1_000", Output::Integer(1280)),
+        ("float", "2.5e3 * 1e-3", Output::Float(2.5)),
+        ("bool", "1 < 2", Output::Boolean(true)),
+        ("bool", "1.0 > 2.0", Output::Boolean(false)),
+        ("bool", "3 == 3", Output::Boolean(true)),
+        ("bool", "true != false", Output::Boolean(true)),
+        ("bool", "!(1 == 2)", Output::Boolean(true)),
+        (
+            "int",
+            "0b1100 & 0b1010 | 0b0001 ^ 0b0011",
+            Output::Integer(0b1010),
+        ),
+        ("int", "~0 << 4 >> 2", Output::Integer(-4)),
+        ("int", "1 << 65", Output::Integer(2)),
+        (
+            "bool",
+            "1 <= 1 && 2.5 >= 3.0 || 2 >= 1",
+            Output::Boolean(true),
+        ),
+        ("bool", "false && 1 / 0 == 0", Output::Boolean(false)),
+        ("bool", "true || 1 / 0 == 0", Output::Boolean(true)),
+        ("string", r#""a" + "b""#, Output::String("ab".into())),
+        (
+            "string",
+            r#""{1 + 2} {2.5} {1 < 2}!""#,
+            Output::String("3 2.5 true!".into()),
+        ),
+        ("bool", r#""ab" < "b" && "a" == "a""#, Output::Boolean(true)),
+    ];
+
+    for case in cases {
+        let source = format!("fn main(): {} {{\n  return {}\n}}", case.0, case.1);
+        assert_output_equal(&[(&source, case.2.clone())]);
+    }
+}
+
+#[test]
+fn run_statements() {
+    let cases = &[
+        (
+            r#"
+fn main(): int {
+  mut a := 1 + 2
+  a = a * 2
+  return a
+}
+"#,
+            Output::Integer(6),
+        ),
+        (
+            r#"
+fn main(): int {
+  mut a := 10
+  a += 5
+  a -= 1
+  a *= 3
+  a /= 4
+  a %= 6
+  return a
+}
+"#,
+            Output::Integer(4),
+        ),
+        (
+            r#"
+fn main(): int {
+  a := 1
+  {
+    a := 2
+  }
+  return a
+}
+"#,
+            Output::Integer(1),
+        ),
+        (
+            r#"
+fn main(): int {
+  return 1
+  return 2
+}
+"#,
+            Output::Integer(1),
+        ),
+        (
+            r#"
+fn main() {
+  a := 1
+}
+"#,
+            Output::Unit,
+        ),
+        (
+            r#"
+fn main(): int {
+  mut a := 0
+  if a > 0 {
+    a = 1
+  } else {
+    a = 2
+  }
+  return a
+}
+"#,
+            Output::Integer(2),
+        ),
+        (
+            r#"
+fn main(): int {
+  mut i := 0
+  mut product := 1
+  while i < 5 {
+    i = i + 1
+    product = product * i
+  }
+  return product
+}
+"#,
+            Output::Integer(120),
+        ),
+        (
+            r#"
+fn main(): float {
+  log(1)
+  return average(1.0, 2.5)
+}
+
+fn average(a: float, b: float): float {
+  return (a + b) / 2.0
+}
+
+fn log(level: int) {
+}
+"#,
+            Output::Float(1.75),
+        ),
+        (
+            r#"
+fn main(): int!string {
+  return sum("one", "one")
+}
+
+fn parse(s: string): int!string {
+  if s == "one" {
+    return Ok(1)
+  }
+  return Err("bad {s}")
+}
+
+fn sum(a: string, b: string): int!string {
+  x := parse(a)?
+  y := parse(b)?
+  return Ok(x + y)
+}
+"#,
+            Output::Ok(Box::new(Output::Integer(2))),
+        ),
+        (
+            r#"
+fn main(): int!string {
+  return sum("one", "two")
+}
+
+fn parse(s: string): int!string {
+  if s == "one" {
+    return Ok(1)
+  }
+  return Err("bad {s}")
+}
+
+fn sum(a: string, b: string): int!string {
+  x := parse(a)?
+  y := parse(b)?
+  return Ok(x + y)
+}
+"#,
+            Output::Err(Box::new(Output::String("bad two".into()))),
+        ),
+    ];
+
+    assert_output_equal(cases);
+}
+
+#[test]
+fn run_errors() {
+    let cases = &[
+        (
+            r#"
+fn main(): int {
+  return a
+}
+"#,
+            Error::UndefinedVariable {
+                name: "a".into(),
+                position: Span::from((27, 3, 18), (27, 3, 18)),
+            },
+        ),
+        (
+            r#"
+fn main(): int {
+  return 1 + 2.0
+}
+"#,
+            Error::TypeMismatch {
+                want: "int".into(),
+                got: "float".into(),
+                position: Span::from((31, 3, 18), (33, 3, 18)),
+            },
+        ),
+        (
+            r#"
+fn foo() {
+}
+"#,
+            Error::UndefinedFunction {
+                name: "main".into(),
+            },
+        ),
+        (
+            r#"
+fn main(): int {
+  return f()
+}
+
+fn f() {
+}
+"#,
+            Error::Unsupported {
+                what: "use of a call without a return value".into(),
+                position: Span::from((27, 3, 18), (29, 3, 18)),
+            },
+        ),
+        (
+            r#"
+fn main(): int {
+  a := 0
+  return 1 + divide(1, a)
+}
+
+fn divide(a: int, b: int): int {
+  return a / b
+}
+"#,
+            Error::DivisionByZero {
+                position: Span::from((98, 8, 89), (102, 8, 89)),
+            },
+        ),
+        (
+            r#"
+fn main(): int {
+  return half(4)?
+}
+
+fn half(a: int): int!string {
+  return Ok(a / 2)
+}
+"#,
+            Error::TypeMismatch {
+                want: "int".into(),
+                got: "_!string".into(),
+                position: Span::from((27, 3, 18), (34, 3, 18)),
+            },
+        ),
+        (
+            r#"
+fn main(): int {
+  f := fn (a: int): int { return a }
+  return f(true)
+}
+"#,
+            Error::TypeMismatch {
+                want: "int".into(),
+                got: "bool".into(),
+                position: Span::from((66, 4, 55), (69, 4, 55)),
+            },
+        ),
+    ];
+
+    for case in cases {
+        let file = Parser::new(case.0).parse().unwrap();
+        assert_eq!(Err(case.1.clone()), run(&file));
+    }
+}
+
+#[test]
+fn build_executable() {
+    let source = r#"
+fn main(): int {
+  a := 6 * 7
+  return a % 256
+}
+"#;
+    let output = build_and_run(source, "executable");
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("42\n", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn build_output() {
+    let cases = [
+        ("fn main(): int { return 300 }", Some(0), "300\n"),
+        ("fn main(): float { return 2.5 }", Some(0), "2.5\n"),
+        ("fn main(): bool { return true }", Some(0), "true\n"),
+        ("fn main() { a := 1 }", Some(0), ""),
+        (
+            r#"
+fn main() {
+  a := 42
+
+  if a > 9000 {
+    print("It's over 9000.")
+  } else {
+    print("It's only {a}.")
+  }
+  print("bye")
+}
+"#,
+            Some(0),
+            "It's only 42.\nbye\n",
+        ),
+        (
+            "fn main(): int!string!string { return Ok(Err(\"bad\")) }",
+            Some(0),
+            "Ok(Err(bad))\n",
+        ),
+    ];
+
+    for (i, case) in cases.iter().enumerate() {
+        let output = build_and_run(case.0, &format!("output-{}", i));
+        assert_eq!(case.1, output.status.code(), "{}", case.0);
+        assert_eq!(
+            case.2,
+            String::from_utf8_lossy(&output.stdout),
+            "{}",
+            case.0
+        );
+    }
+}
+
+#[test]
+fn build_string() {
+    let source = r#"
+fn main(): string {
+  return "{42} {1.0} {0.1 + 0.2} {1e21} {-1.5e-7} {-0.0} {1.0 / 0.0} {2.5e-3 * 3.0} {true}"
+}
+"#;
+    let output = build_and_run(source, "string");
+
+    let file = Parser::new(source).parse().unwrap();
+    let want = interp::run(&file).unwrap();
+    assert_eq!(
+        format!("{}\n", want),
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn build_error_result() {
+    let source = r#"
+fn main(): float!string {
+  print("before")
+  return Err("bad")
+}
+"#;
+    let output = build_and_run(source, "error-result");
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!("before\n", String::from_utf8_lossy(&output.stdout));
+    assert_eq!("Err(bad)\n", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn build_runtime_error() {
+    let source = r#"
+fn main(): int {
+  a := 6 * 7
+  return a / (a - 42)
+}
+"#;
+    let output = build_and_run(source, "runtime-error");
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "division by zero @ 4:9 - 4:20\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn build_missing_return() {
+    let source = r#"
+fn main(): string {
+  a := 1
+}
+"#;
+    let output = build_and_run(source, "missing-return");
+
+    // the trap at the end of `main` kills the process instead of returning a made-up string
+    assert_eq!(None, output.status.code());
+    assert!(output.stdout.is_empty());
+}
+
+/// The interpreter defines the semantics of X, so compiled code has to agree with it.
+#[test]
+fn run_matches_interpreter() {
+    let sources = &[
+        r#"
+fn main(): int {
+  mut a := 17
+  a = a * 3 - a / 4 + a % 5
+  {
+    mut a := -a
+    a = 0
+  }
+  return a
+}
+"#,
+        r#"
+fn main(): float {
+  mut x := 0.5
+  x = x * x + 7.25 % 2.0 - -x
+  return x / 3.0
+}
+"#,
+        r#"
+fn main(): bool {
+  a := 3 > 2
+  b := !(1.5 < 0.5) == a
+  return b != false
+}
+"#,
+        r#"
+fn main(): int {
+  {
+    return 9223372036854775807 + 1
+  }
+  return 0
+}
+"#,
+        r#"
+fn main(): int {
+  mut a := 7
+  if a % 2 == 0 {
+    a = a / 2
+  } else if a > 5 {
+    if a > 6 {
+      return a * 3 + 1
+    }
+    a = 0
+  }
+  return a
+}
+"#,
+        r#"
+fn main(): int {
+  mut n := 27
+  mut steps := 0
+  while true {
+    if n == 1 {
+      break
+    }
+    steps = steps + 1
+    if n % 2 == 0 {
+      n = n / 2
+      continue
+    }
+    n = 3 * n + 1
+  }
+  return steps
+}
+"#,
+        r#"
+fn gcd(a: int, b: int): int {
+  if b == 0 {
+    return a
+  }
+  return gcd(b, a % b)
+}
+
+fn main(): int {
+  return gcd(1071, 462) * gcd(17, 5)
+}
+"#,
+        r#"
+fn main(): string {
+  name := "x"
+  mut s := "{name}: "
+  mut i := 0
+  while i < 3 {
+    s += "{i * 2}" + ","
+    i += 1
+  }
+  return s + "{1.0} {0.1 + 0.2} {1e21} {-1.5e-7} {-0.0} {1.0 / 0.0} {r"{raw}"} {name < s}"
+}
+"#,
+        r#"
+fn main(): int {
+  min := -9223372036854775807 - 1
+  return min / -1 + min % -1
+}
+"#,
+        r#"
+fn main(): int {
+  min := -9223372036854775808
+  return min + -0x7fff_ffff_ffff_ffff
+}
+"#,
+        r#"
+fn main(): int {
+  mut n := 3
+  mut sum := 0
+  while true {
+    sum += 100 % n
+    n -= 1
+  }
+  return sum
+}
+"#,
+        r#"
+fn main(): float!string {
+  a := divide(1.0, 4.0)?
+  return divide(a, 0.0)
+}
+
+fn divide(a: float, b: float): float!string {
+  if b == 0.0 {
+    return Err("cannot divide {a} by zero")
+  }
+  return Ok(a / b)
+}
+"#,
+        r#"
+fn adder(n: int): fn (x: int): int {
+  return fn (x: int): int { return x + n }
+}
+
+fn twice(f: fn (x: int): int, x: int): int {
+  return f(f(x))
+}
+
+fn main(): string {
+  mut factor := 10
+  scale := fn (x: int): int {
+    return x * factor
+  }
+  factor = 100
+  add := adder(1)
+  square := twice(fn (x: int): int { return x * x }, 3)
+  prefix := "n="
+  show := fn (x: int): string {
+    format := fn (): string { return "{prefix}{x}" }
+    return format()
+  }
+  return show(scale(2) + twice(add, 5) + square)
+}
+"#,
+        r#"
+fn main(): int {
+  divide := fn (a: int, b: int): int { return a / b }
+  return divide(1, 0)
+}
+"#,
+    ];
+
+    for source in sources {
+        let file = Parser::new(source).parse().unwrap();
+
+        let want = interp::run(&file).map_err(|e| e.to_string());
+        let got = run(&file).map(interp_value).map_err(|e| e.to_string());
+
+        assert_eq!(want, got, "{}", source);
+    }
+}
+
+fn interp_value(output: Output) -> interp::Value {
+    return match output {
+        Output::Unit => interp::Value::Unit,
+        Output::Boolean(b) => interp::Value::Boolean(b),
+        Output::Integer(i) => interp::Value::Integer(i),
+        Output::Float(f) => interp::Value::Float(f),
+        Output::String(s) => interp::Value::String(s),
+        Output::Ok(value) => interp::Value::Variant {
+            typ: "result".into(),
+            name: "Ok".into(),
+            elements: vec![interp_value(*value)],
+        },
+        Output::Err(error) => interp::Value::Variant {
+            typ: "result".into(),
+            name: "Err".into(),
+            elements: vec![interp_value(*error)],
+        },
+    };
+}
+
+/// Builds the source into an executable in a temporary directory named after `name` and runs it.
+fn build_and_run(source: &str, name: &str) -> std::process::Output {
+    let file = Parser::new(source).parse().unwrap();
+
+    let directory = std::env::temp_dir().join(format!("x-build-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let object = directory.join("main.o");
+    let executable = directory.join("main");
+
+    std::fs::write(&object, build(&file, "main").unwrap()).unwrap();
+    link(&object, &executable).unwrap();
+
+    let output = std::process::Command::new(&executable).output().unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    return output;
+}
+
+fn assert_output_equal(cases: &[(&str, Output)]) {
+    for case in cases {
+        let file = Parser::new(case.0).parse().unwrap();
+        assert_eq!(Ok(case.1.clone()), run(&file), "{}", case.0);
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
 
+use crate::parser::Precedence;
+use crate::token::Token;
+
 impl Format for Expression {
     fn fmt(&self, fmt: &mut Formatter) -> String {
         use ExpressionKind::*;
@@ -9,27 +12,167 @@ impl Format for Expression {
             Boolean(b) => format!("{}", b),
             Integer(i) => format!("{}", i),
             Float(f) => format!("{}", f),
-            String(s) => format!("\"{}\"", s),
+            String(s) => s.clone(),
+            InterpolatedString(parts) => format!("\"{}\"", fmt.join(parts.iter(), "")),
+            Tuple(elements) if elements.len() == 1 => format!("({},)", elements[0].fmt(fmt)),
+            Tuple(elements) => format!("({})", fmt.join(elements.iter(), ", ")),
+            List(elements) => list(('[', ']'), elements, fmt),
+            Map(entries) => list(('{', '}'), entries, fmt),
+            NoneLiteral => "none".into(),
 
             Prefix(p) => p.fmt(fmt),
+            Postfix(p) => p.fmt(fmt),
             Infix(i) => i.fmt(fmt),
+            Call(c) => c.fmt(fmt),
+            Member(m) => m.fmt(fmt),
+            Index(i) => i.fmt(fmt),
+            Struct(s) => s.fmt(fmt),
+            Match(m) => m.fmt(fmt),
+            Lambda(l) => l.fmt(fmt),
+        };
+    }
+}
+
+impl Format for StringPart {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return match self {
+            StringPart::Literal(literal) => literal.clone(),
+            StringPart::Expression(expression) => format!("{{{}}}", expression.fmt(fmt)),
         };
     }
 }
 
 impl Format for PrefixExpression {
     fn fmt(&self, fmt: &mut Formatter) -> String {
-        return format!("{}{}", self.operator, self.right.fmt(fmt));
+        let right = operand(&self.right, &Precedence::Prefix, false, fmt);
+        return format!("{}{}", self.operator, right);
+    }
+}
+
+impl Format for LambdaExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return format!("{} {}", self.typ.fmt(fmt), self.body.fmt(fmt));
+    }
+}
+
+impl Format for PostfixExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        let left = operand(&self.left, &Precedence::Postfix, false, fmt);
+        return format!("{}{}", left, self.operator);
     }
 }
 
 impl Format for InfixExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        let precedence = Precedence::of(&self.operator);
+        let right_associative = self.operator == Token::Coalesce;
+        let left = operand(&self.left, &precedence, right_associative, fmt);
+        let right = operand(&self.right, &precedence, !right_associative, fmt);
+        return format!("{} {} {}", left, self.operator, right);
+    }
+}
+
+impl Format for CallExpression {
     fn fmt(&self, fmt: &mut Formatter) -> String {
         return format!(
-            "{} {} {}",
-            self.left.fmt(fmt),
-            self.operator,
-            self.right.fmt(fmt)
+            "{}({})",
+            self.callee.fmt(fmt),
+            fmt.join(self.arguments.iter(), ", ")
         );
     }
 }
+
+impl Format for MemberExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        let object = operand(&self.object, &Precedence::Index, false, fmt);
+        return format!("{}.{}", object, self.name);
+    }
+}
+
+impl Format for IndexExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        let object = operand(&self.object, &Precedence::Index, false, fmt);
+        return format!("{}[{}]", object, self.index.fmt(fmt));
+    }
+}
+
+impl Format for StructExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        if self.fields.is_empty() {
+            return format!("{}{{}}", self.name);
+        }
+        return format!("{}{{ {} }}", self.name, fmt.join(self.fields.iter(), ", "));
+    }
+}
+
+impl Format for FieldValue {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return format!("{}: {}", self.name, self.value.fmt(fmt));
+    }
+}
+
+impl Format for MatchExpression {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        let mut buffer = format!("match {} {{\n", self.subject.fmt(fmt));
+        fmt.push_indent();
+        for arm in &self.arms {
+            buffer.push_str(&format!("{}{}\n", fmt.indent(), arm.fmt(fmt)));
+        }
+        fmt.pop_indent();
+        buffer.push_str(&format!("{}}}", fmt.indent()));
+
+        return buffer;
+    }
+}
+
+impl Format for MatchArm {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return format!("{} => {}", self.pattern.fmt(fmt), self.body.fmt(fmt));
+    }
+}
+
+impl Format for MapEntry {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return format!("{}: {}", self.key.fmt(fmt), self.value.fmt(fmt));
+    }
+}
+
+/// Formats a list or map literal on one line if it fits into the line width after
+/// the indentation and with one element per line otherwise.
+fn list<T: Format>(delimiters: (char, char), elements: &[T], fmt: &mut Formatter) -> String {
+    let (open, close) = delimiters;
+    let line = format!("{}{}{}", open, fmt.join(elements.iter(), ", "), close);
+    if fmt.indent().len() + line.len() <= fmt.line_width && !line.contains('\n') {
+        return line;
+    }
+
+    let mut buffer = format!("{}\n", open);
+    fmt.push_indent();
+    for element in elements {
+        buffer.push_str(&format!("{}{},\n", fmt.indent(), element.fmt(fmt)));
+    }
+    fmt.pop_indent();
+    buffer.push_str(&format!("{}{}", fmt.indent(), close));
+
+    return buffer;
+}
+
+/// Formats the operand of an operator with the given precedence and wraps it in parentheses
+/// if it would bind differently without them. Operators are left-associative except for `??`,
+/// so the operand on the other side of an operator with the same precedence needs them as well.
+fn operand(
+    expression: &Expression,
+    precedence: &Precedence,
+    parenthesize_equal: bool,
+    fmt: &mut Formatter,
+) -> String {
+    let inner = match &expression.kind {
+        ExpressionKind::Infix(infix) => Precedence::of(&infix.operator),
+        ExpressionKind::Prefix(_) => Precedence::Prefix,
+        _ => return expression.fmt(fmt),
+    };
+    if inner < *precedence || (parenthesize_equal && inner == *precedence) {
+        return format!("({})", expression.fmt(fmt));
+    }
+    return expression.fmt(fmt);
+}
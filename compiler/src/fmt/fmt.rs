@@ -23,6 +23,8 @@ where
 #[derive(Clone, Debug)]
 pub struct Formatter {
     pub verbose: bool,
+    /// Width up to which list literals are kept on a single line.
+    pub line_width: usize,
 
     indent_string: String,
     indent_level: usize,
@@ -32,6 +34,7 @@ impl Default for Formatter {
     fn default() -> Self {
         return Formatter {
             verbose: false,
+            line_width: 80,
 
             indent_string: "  ".into(),
             indent_level: 0,
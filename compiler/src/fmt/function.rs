@@ -23,4 +23,4 @@ impl Format for Return {
     fn fmt(&self, fmt: &mut Formatter) -> String {
         return format!("return {}", self.value.fmt(fmt));
     }
-}
\ No newline at end of file
+}
@@ -12,7 +12,13 @@ impl Format for Statement {
                 Assignment(a) => a.fmt(fmt),
                 Block(b) => b.fmt(fmt),
                 FunctionDeclaration(f) => f.fmt(fmt),
+                TypeDeclaration(t) => t.fmt(fmt),
                 Return(r) => r.fmt(fmt),
+                If(i) => i.fmt(fmt),
+                While(w) => w.fmt(fmt),
+                For(f) => f.fmt(fmt),
+                Break => "break".into(),
+                Continue => "continue".into(),
                 Expression(e) => e.fmt(fmt),
             }
         );
@@ -23,16 +29,22 @@ impl Format for Vec<Box<Statement>> {
     fn fmt(&self, fmt: &mut Formatter) -> String {
         let mut buffer = String::new();
 
+        let mut inline = false;
         let mut iter = self.iter().peekable();
         while let Some(statement) = iter.next() {
-            buffer.push_str(&statement.fmt(fmt));
+            match &statement.kind {
+                StatementKind::Comment(comment) if inline => buffer.push_str(&comment.fmt(fmt)),
+                _ => buffer.push_str(&statement.fmt(fmt)),
+            }
 
+            inline = false;
             if let Some(next) = iter.peek() {
                 if matches!(next.kind, StatementKind::Comment(_))
                     && next.position.start.line == statement.position.end.line
                 {
                     // keep comments on the same line as the statement
-                    buffer.push_str(" ")
+                    buffer.push_str(" ");
+                    inline = true;
                 } else if next.position.start.line - statement.position.end.line >= 2 {
                     // keep a maximum of one empty line
                     buffer.push_str("\n\n");
@@ -63,15 +75,54 @@ impl Format for Declaration {
         if self.mutable {
             buffer.push_str("mut ")
         }
-        buffer.push_str(&format!("{} := {}", self.name, self.value.fmt(fmt)));
+        buffer.push_str(&format!(
+            "{} := {}",
+            self.pattern.fmt(fmt),
+            self.value.fmt(fmt)
+        ));
 
         return buffer;
     }
 }
 
+impl Format for Pattern {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return match self {
+            Pattern::Identifier(name) => name.clone(),
+            Pattern::Wildcard => "_".into(),
+            Pattern::Tuple(elements) if elements.len() == 1 => {
+                format!("({},)", elements[0].fmt(fmt))
+            }
+            Pattern::Tuple(elements) => format!("({})", fmt.join(elements.iter(), ", ")),
+            Pattern::Variant(variant) => format!(
+                "{}({})",
+                variant.name,
+                fmt.join(variant.elements.iter(), ", ")
+            ),
+        };
+    }
+}
+
 impl Format for Assignment {
     fn fmt(&self, fmt: &mut Formatter) -> String {
-        return format!("{} = {}", self.name, self.value.fmt(fmt));
+        return match &self.operator {
+            Some(operator) => format!(
+                "{} {}= {}",
+                self.target.fmt(fmt),
+                operator,
+                self.value.fmt(fmt)
+            ),
+            None => format!("{} = {}", self.target.fmt(fmt), self.value.fmt(fmt)),
+        };
+    }
+}
+
+impl Format for TypeDeclaration {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return match &self.typ {
+            Type::Enum(e) => format!("enum {} {}", self.name, types::variants(e, fmt)),
+            typ => format!("type {} = {}", self.name, typ.fmt(fmt)),
+        };
     }
 }
 
@@ -80,11 +131,58 @@ impl Format for Block {
         let mut buffer = String::new();
 
         buffer.push_str("{\n");
-        fmt.push_indent();
-        buffer.push_str(&self.statements.fmt(fmt));
-        fmt.pop_indent();
-        buffer.push_str("\n}");
+        if !self.statements.is_empty() {
+            fmt.push_indent();
+            buffer.push_str(&self.statements.fmt(fmt));
+            fmt.pop_indent();
+            buffer.push('\n');
+        }
+        buffer.push_str(&format!("{}}}", fmt.indent()));
 
         return buffer;
     }
 }
+
+impl Format for If {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        let mut buffer = String::new();
+
+        buffer.push_str("if ");
+        if let Some(binding) = &self.binding {
+            buffer.push_str(&format!("{} := ", binding.fmt(fmt)));
+        }
+        buffer.push_str(&format!(
+            "{} {}",
+            self.condition.fmt(fmt),
+            self.consequence.fmt(fmt)
+        ));
+        match &self.alternative {
+            Some(Else::If(alternative)) => {
+                buffer.push_str(&format!(" else {}", alternative.fmt(fmt)))
+            }
+            Some(Else::Block(alternative)) => {
+                buffer.push_str(&format!(" else {}", alternative.fmt(fmt)))
+            }
+            None => {}
+        }
+
+        return buffer;
+    }
+}
+
+impl Format for While {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return format!("while {} {}", self.condition.fmt(fmt), self.body.fmt(fmt));
+    }
+}
+
+impl Format for For {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        return format!(
+            "for {} in {} {}",
+            self.variable,
+            self.iterable.fmt(fmt),
+            self.body.fmt(fmt)
+        );
+    }
+}
@@ -58,6 +58,393 @@ mut a := true
     assert_fmt_equal(source, want);
 }
 
+#[test]
+fn fmt_if_statement() {
+    let source = r#"fn main() {
+  a := 42
+
+  if a>9000 {
+    b := "It's over 9000." // over 9000
+  }   else if a < 0 {
+  b := "It's negative."
+  } else {
+    b := "It's only 42."
+  }
+}
+"#;
+    let want = r#"fn main() {
+  a := 42
+
+  if a > 9000 {
+    b := "It's over 9000." // over 9000
+  } else if a < 0 {
+    b := "It's negative."
+  } else {
+    b := "It's only 42."
+  }
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_loop_statements() {
+    let source = r#"fn main() {
+  mut i := 0
+  while i<10 {
+  i = i + 1
+    if i % 2 == 0 { 
+      continue
+    }
+  }
+  for c   in "abc" {
+    break
+  }
+}
+"#;
+    let want = r#"fn main() {
+  mut i := 0
+  while i < 10 {
+    i = i + 1
+    if i % 2 == 0 {
+      continue
+    }
+  }
+  for c in "abc" {
+    break
+  }
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_call_expression() {
+    let source = r#"print( "Hello World" )
+add(1,2 * 3)
+f(
+  a
+  g()
+)
+"#;
+    let want = r#"print("Hello World")
+add(1, 2 * 3)
+f(a, g())
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_interpolated_string() {
+    let source = r#"print("It's only {a}.")
+"{ a+1 } and {f( "{b}" )}"
+"#;
+    let want = r#"print("It's only {a}.")
+"{a + 1} and {f("{b}")}"
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_strings() {
+    let source = r###"a  := "line\n\t\"quoted\" \u{1F600}"
+b := r#"raw \n "string""#
+c :=   "multi
+  line {a}\{"
+"###;
+    let want = r###"a := "line\n\t\"quoted\" \u{1F600}"
+b := r#"raw \n "string""#
+c := "multi
+  line {a}\{"
+"###;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_numbers() {
+    let source = "a :=  0xFF + 0o17 +  0b1010\nb := 1_000_000 *  2\nc := 6.02e23 / 1e-9\n";
+    let want = "a := 0xFF + 0o17 + 0b1010\nb := 1_000_000 * 2\nc := 6.02e23 / 1e-9\n";
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_logical_expressions() {
+    let source = r#"a := x>=0&&x<=10 || !(y == 1)
+b := (a || false) && (1 - (2 - 3)) * 4 < 5
+c := ((1 + 2) * 3)
+"#;
+    let want = r#"a := x >= 0 && x <= 10 || !(y == 1)
+b := (a || false) && (1 - (2 - 3)) * 4 < 5
+c := (1 + 2) * 3
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_bitwise_expressions() {
+    let source = r#"a := ~x&0xFF|y^1<<2
+b := (a | 1) & ~(a >> 2)
+c := a & (1 == 1)
+"#;
+    let want = r#"a := ~x & 0xFF | y ^ 1 << 2
+b := (a | 1) & ~(a >> 2)
+c := a & (1 == 1)
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_compound_assignments() {
+    let source = r#"mut a := 1
+a+=2
+a -=  3
+a*= a + 1
+a /=2
+a%= 5
+"#;
+    let want = r#"mut a := 1
+a += 2
+a -= 3
+a *= a + 1
+a /= 2
+a %= 5
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_tuples() {
+    let source = r#"fn pair(): (int, string) {
+  return (1,"a")
+}
+mut (a,(b ,_)) := (pair( ), (( 1 ),))
+c := (a + 1).0 + -(b).1
+"#;
+    let want = r#"fn pair(): (int, string) {
+  return (1, "a")
+}
+mut (a, (b, _)) := (pair(), (1,))
+c := (a + 1).0 + -b.1
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_structs() {
+    let source = r#"type Point = {x:int,y : int=0}
+type Empty = {  }
+fn main() {
+  mut p := Point{x:1}
+  p.x += Point{ x: 2,y:3 }.y
+  if p.x == 4 {
+    e := Empty{ }
+  }
+}
+"#;
+    let want = r#"type Point = { x: int, y: int = 0 }
+type Empty = {  }
+fn main() {
+  mut p := Point{ x: 1 }
+  p.x += Point{ x: 2, y: 3 }.y
+  if p.x == 4 {
+    e := Empty{}
+  }
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_index_expressions() {
+    let source = r#"c := ("a" + s)[ i+1 ]
+d := -(s[0]).len
+"#;
+    let want = r#"c := ("a" + s)[i + 1]
+d := -s[0].len
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_lists() {
+    let source = r#"fn main() {
+  mut xs := [ 1,2 ,
+    3]
+  names := ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india"]
+  xs.push(xs[0])
+  grid := [[1, 2], [3, 4], ["a very long string that does not fit into the line width", "b"]]
+}
+"#;
+    let want = r#"fn main() {
+  mut xs := [1, 2, 3]
+  names := [
+    "alpha",
+    "bravo",
+    "charlie",
+    "delta",
+    "echo",
+    "foxtrot",
+    "golf",
+    "hotel",
+    "india",
+  ]
+  xs.push(xs[0])
+  grid := [
+    [1, 2],
+    [3, 4],
+    ["a very long string that does not fit into the line width", "b"],
+  ]
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_maps() {
+    let source = r#"fn count(scores: {string:[int]}): int {
+  return scores.len()
+}
+
+fn main() {
+  mut ages := {"alice":30,
+    "bob" : 25}
+  ages["carol"] = ages["bob"]
+  empty := {}
+  scores := {"alice": [90, 85, 77], "bob": [60, 75, 80], "carol": [100, 95, 98], "dave": []}
+}
+"#;
+    let want = r#"fn count(scores: {string: [int]}): int {
+  return scores.len()
+}
+
+fn main() {
+  mut ages := {"alice": 30, "bob": 25}
+  ages["carol"] = ages["bob"]
+  empty := {}
+  scores := {
+    "alice": [90, 85, 77],
+    "bob": [60, 75, 80],
+    "carol": [100, 95, 98],
+    "dave": [],
+  }
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_enums() {
+    let source = r#"enum Shape {Circle(float),
+  Rect(float,float)
+  Empty}
+
+enum Never {
+}
+
+fn area(shape: Shape): float {
+  return match shape { Circle(r) => 3.14 * r * r, Rect(w,h) => w * h
+    Empty => 0.0 }
+}
+"#;
+    let want = r#"enum Shape { Circle(float), Rect(float, float), Empty }
+
+enum Never {}
+
+fn area(shape: Shape): float {
+  return match shape {
+    Circle(r) => 3.14 * r * r
+    Rect(w, h) => w * h
+    Empty => 0.0
+  }
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_optionals() {
+    let source = r#"fn first(xs: [int]  ?): int?{
+  if x:=xs{
+    return x[0]
+  }
+  return none
+}
+a := (b??c)??d
+e := b??(c??d)
+f := (b ?? 1)+2 > b??3
+"#;
+    let want = r#"fn first(xs: [int]?): int? {
+  if x := xs {
+    return x[0]
+  }
+  return none
+}
+a := (b ?? c) ?? d
+e := b ?? c ?? d
+f := (b ?? 1) + 2 > b ?? 3
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_results() {
+    let source = r#"fn half(x: int  !  string): int!string{
+  y := (-parse(x)?)?
+  return match  y {
+    Ok(z) => Ok(z / 2)
+    Err(e)=>Err(e)
+  }
+}
+"#;
+    let want = r#"fn half(x: int!string): int!string {
+  y := (-parse(x)?)?
+  return match y {
+    Ok(z) => Ok(z / 2)
+    Err(e) => Err(e)
+  }
+}
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
+#[test]
+fn fmt_lambdas() {
+    let source = r#"fn adder(n: int): fn (x: int): int {
+  return fn(x:int):int{ return x + n }
+}
+ys := map(xs, fn (x: int): int { y := x * 2; return y })
+"#;
+    let want = r#"fn adder(n: int): fn (x: int): int {
+  return fn (x: int): int {
+    return x + n
+  }
+}
+ys := map(xs, fn (x: int): int {
+  y := x * 2
+  return y
+})
+"#;
+
+    assert_fmt_equal(source, want);
+}
+
 fn assert_fmt_equal(source: &str, want: &str) {
     let mut parser = Parser::new(source);
     let file = parser.parse().unwrap();
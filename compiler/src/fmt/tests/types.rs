@@ -5,12 +5,52 @@ use pretty_assertions::assert_eq;
 #[test]
 fn fmt_type() {
     let cases = &[
+        (
+            Type::List(Box::new(Type::List(Box::new(Type::Name("P".into()))))),
+            "[[P]]",
+        ),
+        (
+            Type::Map(ast::MapType {
+                key: Box::new(Type::String),
+                value: Box::new(Type::List(Box::new(Type::Integer))),
+            }),
+            "{string: [int]}",
+        ),
+        (
+            Type::Enum(ast::EnumType {
+                variants: vec![
+                    ast::Variant {
+                        name: "Some".into(),
+                        elements: vec![Type::Integer, Type::String],
+                    },
+                    ast::Variant {
+                        name: "None".into(),
+                        elements: vec![],
+                    },
+                ],
+            }),
+            "enum { Some(int, string), None }",
+        ),
         (
             Type::Tuple(ast::TupleType {
                 elements: vec![Type::String, Type::Boolean],
             }),
             "(string, bool)",
         ),
+        (
+            Type::Result(ast::ResultType {
+                value: Box::new(Type::Optional(Box::new(Type::Integer))),
+                error: Box::new(Type::List(Box::new(Type::String))),
+            }),
+            "int?![string]",
+        ),
+        (
+            Type::Optional(Box::new(Type::Map(ast::MapType {
+                key: Box::new(Type::String),
+                value: Box::new(Type::Optional(Box::new(Type::Float))),
+            }))),
+            "{string: float?}?",
+        ),
         (
             Type::Struct(ast::StructType {
                 fields: vec![
@@ -37,6 +77,7 @@ fn fmt_type() {
                 parameters: vec![ast::Parameter {
                     name: "foo".into(),
                     typ: Type::String,
+                    position: Span::new(),
                 }],
                 return_type: None,
             }),
@@ -47,6 +88,7 @@ fn fmt_type() {
                 parameters: vec![ast::Parameter {
                     name: "bar".into(),
                     typ: Type::Integer,
+                    position: Span::new(),
                 }],
                 return_type: Some(Box::new(Type::Tuple(ast::TupleType {
                     elements: vec![Type::String, Type::Boolean],
@@ -54,6 +96,17 @@ fn fmt_type() {
             }),
             "fn (bar: int): (string, bool)",
         ),
+        (
+            Type::Function(ast::FunctionType {
+                parameters: vec![ast::Parameter {
+                    name: "baz".into(),
+                    typ: Type::Unknown,
+                    position: Span::new(),
+                }],
+                return_type: Some(Box::new(Type::Unknown)),
+            }),
+            "fn (baz: _): _",
+        ),
     ];
 
     for case in cases {
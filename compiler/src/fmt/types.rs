@@ -3,7 +3,7 @@ use super::*;
 impl Format for Type {
     fn fmt(&self, fmt: &mut Formatter) -> String {
         return match self {
-            Type::Unknown => "UNKNOWN".into(),
+            Type::Unknown | Type::Variable(_) => "_".into(),
             Type::Name(name) => name.into(),
 
             Type::Boolean => "bool".into(),
@@ -12,8 +12,13 @@ impl Format for Type {
             Type::String => "string".into(),
 
             Type::Tuple(t) => t.fmt(fmt),
+            Type::List(element) => format!("[{}]", element.fmt(fmt)),
+            Type::Map(map) => format!("{{{}: {}}}", map.key.fmt(fmt), map.value.fmt(fmt)),
             Type::Struct(s) => s.fmt(fmt),
+            Type::Enum(e) => format!("enum {}", variants(e, fmt)),
             Type::Function(f) => f.fmt(fmt),
+            Type::Optional(inner) => format!("{}?", inner.fmt(fmt)),
+            Type::Result(result) => format!("{}!{}", result.value.fmt(fmt), result.error.fmt(fmt)),
         };
     }
 }
@@ -43,6 +48,24 @@ impl Format for Field {
     }
 }
 
+impl Format for Variant {
+    fn fmt(&self, fmt: &mut Formatter) -> String {
+        if self.elements.is_empty() {
+            return self.name.clone();
+        }
+        return format!("{}({})", self.name, fmt.join(self.elements.iter(), ", "));
+    }
+}
+
+/// Formats the variants of an enum type in braces, which is the part after `enum Name`
+/// in a declaration.
+pub(super) fn variants(e: &EnumType, fmt: &mut Formatter) -> String {
+    if e.variants.is_empty() {
+        return "{}".into();
+    }
+    return format!("{{ {} }}", fmt.join(e.variants.iter(), ", "));
+}
+
 impl Format for FunctionType {
     fn fmt(&self, fmt: &mut Formatter) -> String {
         let mut buffer = String::new();
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use super::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Binding {
+    pub value: Value,
+    pub mutable: bool,
+}
+
+/// Stack of lexical scopes of the function that is currently executing.
+/// Lookups that miss every scope of the function fall back to the global scope.
+#[derive(Clone, Debug, Default)]
+pub struct Environment {
+    globals: HashMap<String, Binding>,
+    scopes: Vec<HashMap<String, Binding>>,
+}
+
+impl Environment {
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Replaces the scopes of the current function with a single empty scope
+    /// and returns the previous ones so they can be restored after a call.
+    pub fn enter_function(&mut self) -> Vec<HashMap<String, Binding>> {
+        return std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+    }
+
+    pub fn leave_function(&mut self, scopes: Vec<HashMap<String, Binding>>) {
+        self.scopes = scopes;
+    }
+
+    /// Binds `name` in the innermost scope, shadowing any previous binding.
+    pub fn declare(&mut self, name: &str, value: Value, mutable: bool) {
+        let scope = self.scopes.last_mut().unwrap_or(&mut self.globals);
+        scope.insert(name.into(), Binding { value, mutable });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Binding> {
+        return self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.globals.get(name));
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Binding> {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(binding) = scope.get_mut(name) {
+                return Some(binding);
+            }
+        }
+        return self.globals.get_mut(name);
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use super::Value;
+use crate::token::Span;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    UndefinedVariable {
+        name: String,
+        position: Span,
+    },
+    UndefinedFunction {
+        name: String,
+    },
+    ImmutableAssignment {
+        name: String,
+        position: Span,
+    },
+    /// Call of a method like `push` that changes a variable not declared with `mut` in place.
+    ImmutableMutation {
+        name: String,
+        method: String,
+        position: Span,
+    },
+    InvalidOperands {
+        operator: String,
+        operands: String,
+        position: Span,
+    },
+    InvalidLiteral {
+        literal: String,
+        position: Span,
+    },
+    DivisionByZero {
+        position: Span,
+    },
+    InvalidCondition {
+        typ: String,
+        position: Span,
+    },
+    NotIterable {
+        typ: String,
+        position: Span,
+    },
+    NotCallable {
+        typ: String,
+        position: Span,
+    },
+    ArgumentCount {
+        name: String,
+        want: usize,
+        got: usize,
+    },
+    UndefinedMember {
+        typ: String,
+        name: String,
+        position: Span,
+    },
+    InvalidDestructuring {
+        elements: usize,
+        typ: String,
+        position: Span,
+    },
+    UndefinedType {
+        name: String,
+        position: Span,
+    },
+    MissingField {
+        name: String,
+        position: Span,
+    },
+    NotIndexable {
+        typ: String,
+        position: Span,
+    },
+    NotAssignable {
+        typ: String,
+        position: Span,
+    },
+    /// Assignment to an expression that is not a variable, a member or an element, like `f() = 1`,
+    /// which the parser rejects in source code.
+    InvalidAssignmentTarget {
+        position: Span,
+    },
+    IndexOutOfBounds {
+        index: i64,
+        length: usize,
+        position: Span,
+    },
+    EmptyList {
+        position: Span,
+    },
+    KeyNotFound {
+        key: String,
+        position: Span,
+    },
+    /// Value of a type that cannot be hashed used as a map key.
+    InvalidKey {
+        typ: String,
+        position: Span,
+    },
+    /// No arm of a match or pattern of a declaration matches the value.
+    NoMatch {
+        value: String,
+        position: Span,
+    },
+    /// Error of a result that `?` returns from the enclosing function.
+    Propagated {
+        error: Value,
+        position: Span,
+    },
+    /// Value of `?` that is not a result.
+    NotResult {
+        typ: String,
+        position: Span,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UndefinedVariable { name, position } => {
+                write!(f, "undefined variable: {} @ {}", name, position)
+            }
+            Error::UndefinedFunction { name } => write!(f, "undefined function: {}", name),
+            Error::ImmutableAssignment { name, position } => {
+                write!(
+                    f,
+                    "cannot assign twice to immutable variable: {} @ {}",
+                    name, position
+                )
+            }
+            Error::ImmutableMutation {
+                name,
+                method,
+                position,
+            } => write!(
+                f,
+                "cannot change immutable variable with {}: {} @ {}",
+                method, name, position
+            ),
+            Error::InvalidOperands {
+                operator,
+                operands,
+                position,
+            } => write!(
+                f,
+                "invalid operands for {}: {} @ {}",
+                operator, operands, position
+            ),
+            Error::InvalidLiteral { literal, position } => {
+                write!(f, "invalid literal: {} @ {}", literal, position)
+            }
+            Error::DivisionByZero { position } => write!(f, "division by zero @ {}", position),
+            Error::InvalidCondition { typ, position } => {
+                write!(f, "condition is not a bool: {} @ {}", typ, position)
+            }
+            Error::NotIterable { typ, position } => {
+                write!(f, "cannot iterate over {} @ {}", typ, position)
+            }
+            Error::NotCallable { typ, position } => {
+                write!(f, "cannot call {} @ {}", typ, position)
+            }
+            Error::ArgumentCount { name, want, got } => write!(
+                f,
+                "wrong number of arguments for {}: {} (expected: {})",
+                name, got, want
+            ),
+            Error::UndefinedMember {
+                typ,
+                name,
+                position,
+            } => write!(f, "undefined member of {}: {} @ {}", typ, name, position),
+            Error::InvalidDestructuring {
+                elements,
+                typ,
+                position,
+            } => write!(
+                f,
+                "cannot destructure {} into {} elements @ {}",
+                typ, elements, position
+            ),
+            Error::UndefinedType { name, position } => {
+                write!(f, "undefined type: {} @ {}", name, position)
+            }
+            Error::MissingField { name, position } => {
+                write!(f, "missing field: {} @ {}", name, position)
+            }
+            Error::NotIndexable { typ, position } => {
+                write!(f, "cannot index {} @ {}", typ, position)
+            }
+            Error::NotAssignable { typ, position } => {
+                write!(f, "cannot assign to element of {} @ {}", typ, position)
+            }
+            Error::InvalidAssignmentTarget { position } => {
+                write!(f, "invalid assignment target @ {}", position)
+            }
+            Error::IndexOutOfBounds {
+                index,
+                length,
+                position,
+            } => write!(
+                f,
+                "index out of bounds: {} @ {} (length: {})",
+                index, position, length
+            ),
+            Error::EmptyList { position } => write!(f, "pop from empty list @ {}", position),
+            Error::KeyNotFound { key, position } => {
+                write!(f, "key not found: {} @ {}", key, position)
+            }
+            Error::InvalidKey { typ, position } => {
+                write!(f, "cannot use {} as a map key @ {}", typ, position)
+            }
+            Error::NoMatch { value, position } => {
+                write!(f, "no pattern matches {} @ {}", value, position)
+            }
+            Error::Propagated { error, position } => {
+                write!(
+                    f,
+                    "error propagated outside of a function: {} @ {}",
+                    error, position
+                )
+            }
+            Error::NotResult { typ, position } => {
+                write!(f, "not a result: {} @ {}", typ, position)
+            }
+        }
+    }
+}
@@ -0,0 +1,1158 @@
+mod environment;
+mod error;
+mod value;
+
+pub use environment::*;
+pub use error::*;
+pub use value::*;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+
+use crate::ast;
+use crate::lexer;
+use crate::resolver::{self, PRINT, RESULT_VARIANTS};
+use crate::token::{Span, Token};
+
+/// How execution continues after a statement.
+enum Flow {
+    Next,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+/// Where the value of an expression like `a.b[i]` starts: a variable,
+/// which is read without copying it, or the value of any other expression.
+enum Root<'a> {
+    Variable(&'a str, &'a Span),
+    Value(Value),
+}
+
+/// Member or evaluated index of an expression like `a.b[i]`, with the position of the expression.
+enum Step<'a> {
+    Member(&'a ast::MemberExpression, &'a Span),
+    Index(Value, &'a Span),
+}
+
+/// Evaluates an `ast::File` by walking the tree. This is the reference semantics of X.
+#[derive(Clone, Debug, Default)]
+pub struct Interpreter {
+    environment: Environment,
+    /// Declared types by their name.
+    types: HashMap<String, ast::Type>,
+    /// Names of the bindings that each lambda captures by the span of the lambda.
+    captures: HashMap<Span, Vec<String>>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Executes the top-level statements of the file in the global scope.
+    pub fn load(&mut self, file: &ast::File) -> Result<()> {
+        for capture in resolver::captures(file) {
+            self.captures
+                .entry(capture.closure)
+                .or_default()
+                .push(capture.name);
+        }
+        // variants of the built-in result type, which declared variants can shadow
+        for name in RESULT_VARIANTS {
+            let constructor = Value::Constructor {
+                typ: "result".into(),
+                name: name.into(),
+                elements: 1,
+            };
+            self.environment.declare(name, constructor, false);
+        }
+        self.environment
+            .declare(PRINT, Value::Builtin(PRINT), false);
+        // types and variants can be used before their declaration at the top level
+        for statement in &file.statements {
+            if let ast::StatementKind::TypeDeclaration(declaration) = &statement.kind {
+                self.types
+                    .insert(declaration.name.clone(), declaration.typ.clone());
+                self.declare_variants(declaration);
+            }
+        }
+        for statement in &file.statements {
+            self.execute(statement)?;
+        }
+        return Ok(());
+    }
+
+    /// Calls the global function `name` with the given arguments.
+    pub fn call(&mut self, name: &str, arguments: Vec<Value>) -> Result<Value> {
+        let function = match self.environment.get(name) {
+            Some(Binding {
+                value: Value::Function(function),
+                ..
+            }) => function.clone(),
+            _ => return Err(Error::UndefinedFunction { name: name.into() }),
+        };
+
+        return self.call_function(&function, arguments);
+    }
+
+    fn call_function(
+        &mut self,
+        function: &Rc<ast::FunctionDeclaration>,
+        arguments: Vec<Value>,
+    ) -> Result<Value> {
+        if function.typ.parameters.len() != arguments.len() {
+            return Err(Error::ArgumentCount {
+                name: function.name.clone(),
+                want: function.typ.parameters.len(),
+                got: arguments.len(),
+            });
+        }
+
+        let caller = self.environment.enter_function();
+        // a nested function can call itself, although it cannot use the other locals
+        // of the function that declared it
+        let global = matches!(
+            self.environment.get(&function.name),
+            Some(Binding { value: Value::Function(f), .. }) if Rc::ptr_eq(f, function)
+        );
+        if !global {
+            let value = Value::Function(function.clone());
+            self.environment.declare(&function.name, value, false);
+        }
+        for (parameter, argument) in function.typ.parameters.iter().zip(arguments) {
+            self.environment.declare(&parameter.name, argument, false);
+        }
+
+        let result = self.execute_statements(&function.body.statements);
+        self.environment.leave_function(caller);
+
+        return returned_value(result);
+    }
+
+    /// Calls a lambda with the locals it captured declared in the scope around its parameters.
+    fn call_closure(&mut self, closure: &Closure, arguments: Vec<Value>) -> Result<Value> {
+        let parameters = &closure.lambda.typ.parameters;
+        if parameters.len() != arguments.len() {
+            return Err(Error::ArgumentCount {
+                name: "fn".into(),
+                want: parameters.len(),
+                got: arguments.len(),
+            });
+        }
+
+        let caller = self.environment.enter_function();
+        for (name, binding) in &closure.captures {
+            self.environment.declare(name, binding.value.clone(), false);
+        }
+        self.environment.push_scope();
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            self.environment.declare(&parameter.name, argument, false);
+        }
+
+        let result = self.execute_statements(&closure.lambda.body.statements);
+        self.environment.leave_function(caller);
+
+        return returned_value(result);
+    }
+
+    fn execute_statements(&mut self, statements: &[Box<ast::Statement>]) -> Result<Flow> {
+        for statement in statements {
+            match self.execute(statement)? {
+                Flow::Next => {}
+                flow => return Ok(flow),
+            }
+        }
+        return Ok(Flow::Next);
+    }
+
+    fn execute(&mut self, statement: &ast::Statement) -> Result<Flow> {
+        use ast::StatementKind::*;
+
+        match &statement.kind {
+            Comment(_) => {}
+            Declaration(declaration) => {
+                let value = self.evaluate(&declaration.value)?;
+                self.declare_pattern(
+                    &declaration.pattern,
+                    value,
+                    declaration.mutable,
+                    &statement.position,
+                )?;
+            }
+            Assignment(assignment) => {
+                let mut value = self.evaluate(&assignment.value)?;
+                let place = match &assignment.target.kind {
+                    // assigning to a key that is not in a map inserts it
+                    ast::ExpressionKind::Index(index) if assignment.operator.is_none() => self
+                        .element(
+                            index,
+                            true,
+                            &assignment.target.position,
+                            &statement.position,
+                        )?,
+                    _ => self.place(&assignment.target, &statement.position)?,
+                };
+                if let Some(operator) = &assignment.operator {
+                    let current = place.clone();
+                    value = evaluate_infix(operator, current, value, &statement.position)?;
+                }
+                *place = value;
+            }
+            Block(block) => return self.execute_block(block),
+            FunctionDeclaration(function) => {
+                let value = Value::Function(Rc::new(function.clone()));
+                self.environment.declare(&function.name, value, false);
+            }
+            TypeDeclaration(declaration) => {
+                self.types
+                    .insert(declaration.name.clone(), declaration.typ.clone());
+                self.declare_variants(declaration);
+            }
+            Return(ret) => {
+                let value = self.evaluate(&ret.value)?;
+                return Ok(Flow::Return(value));
+            }
+            If(i) => return self.execute_if(i),
+            While(w) => {
+                while self.evaluate_condition(&w.condition)? {
+                    match self.execute_block(&w.body)? {
+                        Flow::Next | Flow::Continue => {}
+                        Flow::Break => break,
+                        flow => return Ok(flow),
+                    }
+                }
+            }
+            For(f) => return self.execute_for(f),
+            Break => return Ok(Flow::Break),
+            Continue => return Ok(Flow::Continue),
+            Expression(expression) => {
+                self.evaluate(expression)?;
+            }
+        }
+
+        return Ok(Flow::Next);
+    }
+
+    fn declare_pattern(
+        &mut self,
+        pattern: &ast::Pattern,
+        value: Value,
+        mutable: bool,
+        position: &Span,
+    ) -> Result<()> {
+        match (pattern, value) {
+            (ast::Pattern::Identifier(name), value) => {
+                self.environment.declare(name, value, mutable);
+            }
+            (ast::Pattern::Wildcard, _) => {}
+            (ast::Pattern::Tuple(patterns), Value::Tuple(values))
+                if patterns.len() == values.len() =>
+            {
+                for (pattern, value) in patterns.iter().zip(values) {
+                    self.declare_pattern(pattern, value, mutable, position)?;
+                }
+            }
+            (ast::Pattern::Tuple(patterns), value) => {
+                return Err(Error::InvalidDestructuring {
+                    elements: patterns.len(),
+                    typ: value.type_name(),
+                    position: position.clone(),
+                });
+            }
+            (pattern @ ast::Pattern::Variant(_), value) => {
+                let mut bindings = vec![];
+                if !self.match_pattern(pattern, &value, &mut bindings) {
+                    return Err(Error::NoMatch {
+                        value: value.to_string(),
+                        position: position.clone(),
+                    });
+                }
+                for (name, value) in bindings {
+                    self.environment.declare(&name, value, mutable);
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Returns whether the value matches the pattern and collects the values of its names.
+    /// A name matches a variant without elements if it refers to one.
+    fn match_pattern(
+        &self,
+        pattern: &ast::Pattern,
+        value: &Value,
+        bindings: &mut Vec<(String, Value)>,
+    ) -> bool {
+        return match (pattern, value) {
+            (ast::Pattern::Identifier(name), value) if self.is_variant(name) => {
+                matches!(value, Value::Variant { name: n, .. } if n == name)
+            }
+            (ast::Pattern::Identifier(name), value) => {
+                bindings.push((name.clone(), value.clone()));
+                true
+            }
+            (ast::Pattern::Wildcard, _) => true,
+            (ast::Pattern::Tuple(patterns), Value::Unit) => patterns.is_empty(),
+            (ast::Pattern::Tuple(patterns), Value::Tuple(values)) => {
+                patterns.len() == values.len()
+                    && patterns
+                        .iter()
+                        .zip(values)
+                        .all(|(pattern, value)| self.match_pattern(pattern, value, bindings))
+            }
+            (ast::Pattern::Variant(variant), Value::Variant { name, elements, .. }) => {
+                variant.name == *name
+                    && variant.elements.len() == elements.len()
+                    && variant
+                        .elements
+                        .iter()
+                        .zip(elements)
+                        .all(|(pattern, value)| self.match_pattern(pattern, value, bindings))
+            }
+            _ => false,
+        };
+    }
+
+    fn is_variant(&self, name: &str) -> bool {
+        return match self.environment.get(name) {
+            Some(Binding {
+                value: Value::Variant { name: n, .. } | Value::Constructor { name: n, .. },
+                ..
+            }) => n == name,
+            _ => false,
+        };
+    }
+
+    /// Declares the variants of an enum type. Variants with elements are constructors.
+    fn declare_variants(&mut self, declaration: &ast::TypeDeclaration) {
+        let ast::Type::Enum(e) = &declaration.typ else {
+            return;
+        };
+        for variant in &e.variants {
+            let value = if variant.elements.is_empty() {
+                Value::Variant {
+                    typ: declaration.name.clone(),
+                    name: variant.name.clone(),
+                    elements: vec![],
+                }
+            } else {
+                Value::Constructor {
+                    typ: declaration.name.clone(),
+                    name: variant.name.clone(),
+                    elements: variant.elements.len(),
+                }
+            };
+            self.environment.declare(&variant.name, value, false);
+        }
+    }
+
+    /// Returns the value of an `Ok` result. The `Err` result itself is returned
+    /// from the enclosing function by `call_function`.
+    fn evaluate_propagation(
+        &mut self,
+        postfix: &ast::PostfixExpression,
+        position: &Span,
+    ) -> Result<Value> {
+        return match self.evaluate(&postfix.left)? {
+            Value::Variant {
+                typ,
+                name,
+                mut elements,
+            } if typ == "result" && name == "Ok" => Ok(elements.remove(0)),
+            error @ Value::Variant { .. } if error.type_name() == "result" => {
+                Err(Error::Propagated {
+                    error,
+                    position: position.clone(),
+                })
+            }
+            value => Err(Error::NotResult {
+                typ: value.type_name(),
+                position: postfix.left.position.clone(),
+            }),
+        };
+    }
+
+    fn evaluate_match(&mut self, m: &ast::MatchExpression, position: &Span) -> Result<Value> {
+        let subject = self.evaluate(&m.subject)?;
+
+        for arm in &m.arms {
+            let mut bindings = vec![];
+            if !self.match_pattern(&arm.pattern, &subject, &mut bindings) {
+                continue;
+            }
+
+            self.environment.push_scope();
+            for (name, value) in bindings {
+                self.environment.declare(&name, value, false);
+            }
+            let value = self.evaluate(&arm.body);
+            self.environment.pop_scope();
+            return value;
+        }
+
+        return Err(Error::NoMatch {
+            value: subject.to_string(),
+            position: position.clone(),
+        });
+    }
+
+    /// Returns the value that is changed by assigning to the target,
+    /// which is a variable or a member of a value stored in a variable.
+    fn place(&mut self, target: &ast::Expression, position: &Span) -> Result<&mut Value> {
+        return match &target.kind {
+            ast::ExpressionKind::Identifier(name) => {
+                let binding =
+                    self.environment
+                        .get_mut(name)
+                        .ok_or_else(|| Error::UndefinedVariable {
+                            name: name.clone(),
+                            position: position.clone(),
+                        })?;
+                if !binding.mutable {
+                    return Err(Error::ImmutableAssignment {
+                        name: name.clone(),
+                        position: position.clone(),
+                    });
+                }
+                Ok(&mut binding.value)
+            }
+            ast::ExpressionKind::Member(member) => {
+                let object = self.place(&member.object, position)?;
+                let typ = object.type_name();
+                let value = match object {
+                    Value::Tuple(elements) => member
+                        .name
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| elements.get_mut(index)),
+                    Value::Struct { fields, .. } => fields
+                        .iter_mut()
+                        .find(|(name, _)| *name == member.name)
+                        .map(|(_, value)| value),
+                    _ => None,
+                };
+                value.ok_or_else(|| Error::UndefinedMember {
+                    typ,
+                    name: member.name.clone(),
+                    position: target.position.clone(),
+                })
+            }
+            ast::ExpressionKind::Index(index) => {
+                self.element(index, false, &target.position, position)
+            }
+            _ => Err(Error::InvalidAssignmentTarget {
+                position: target.position.clone(),
+            }),
+        };
+    }
+
+    /// Returns the element of a list or the value of a map that is changed by assigning
+    /// to the index expression. A missing key is inserted into the map if `insert` is set.
+    fn element(
+        &mut self,
+        index: &ast::IndexExpression,
+        insert: bool,
+        target: &Span,
+        position: &Span,
+    ) -> Result<&mut Value> {
+        let i = self.evaluate(&index.index)?;
+        let object = self.place(&index.object, position)?;
+        return match (object, i) {
+            (Value::List(elements), Value::Integer(i)) => {
+                let i = checked_index(i, elements.len(), target)?;
+                Ok(&mut elements[i])
+            }
+            (Value::Map(entries), key) => {
+                let key = map_key(key, &index.index.position)?;
+                match insert {
+                    true => Ok(entries.entry(key).or_insert(Value::Unit)),
+                    false => match entries.get_index_of(&key) {
+                        Some(i) => Ok(&mut entries[i]),
+                        None => Err(key_not_found(&key, target)),
+                    },
+                }
+            }
+            (object @ Value::String(_), _) => Err(Error::NotAssignable {
+                typ: object.type_name(),
+                position: target.clone(),
+            }),
+            (object, _) => Err(Error::NotIndexable {
+                typ: object.type_name(),
+                position: target.clone(),
+            }),
+        };
+    }
+
+    fn execute_block(&mut self, block: &ast::Block) -> Result<Flow> {
+        self.environment.push_scope();
+        let flow = self.execute_statements(&block.statements);
+        self.environment.pop_scope();
+        return flow;
+    }
+
+    fn execute_if(&mut self, i: &ast::If) -> Result<Flow> {
+        if let Some(binding) = &i.binding {
+            let value = self.evaluate(&i.condition)?;
+            if value != Value::None {
+                self.environment.push_scope();
+                let flow = self
+                    .declare_pattern(binding, value, false, &i.condition.position)
+                    .and_then(|_| self.execute_block(&i.consequence));
+                self.environment.pop_scope();
+                return flow;
+            }
+        } else if self.evaluate_condition(&i.condition)? {
+            return self.execute_block(&i.consequence);
+        }
+        return match &i.alternative {
+            Some(ast::Else::If(alternative)) => self.execute_if(alternative),
+            Some(ast::Else::Block(alternative)) => self.execute_block(alternative),
+            None => Ok(Flow::Next),
+        };
+    }
+
+    fn execute_for(&mut self, f: &ast::For) -> Result<Flow> {
+        let elements: Vec<Value> = match self.evaluate(&f.iterable)? {
+            Value::String(s) => s.chars().map(|c| Value::String(c.into())).collect(),
+            Value::List(elements) => elements,
+            Value::Map(entries) => entries
+                .into_iter()
+                .map(|(key, value)| Value::Tuple(vec![key.value(), value]))
+                .collect(),
+            value => {
+                return Err(Error::NotIterable {
+                    typ: value.type_name(),
+                    position: f.iterable.position.clone(),
+                })
+            }
+        };
+
+        for element in elements {
+            self.environment.push_scope();
+            self.environment.declare(&f.variable, element, false);
+            let flow = self.execute_block(&f.body);
+            self.environment.pop_scope();
+
+            match flow? {
+                Flow::Next | Flow::Continue => {}
+                Flow::Break => break,
+                flow => return Ok(flow),
+            }
+        }
+
+        return Ok(Flow::Next);
+    }
+
+    fn evaluate_condition(&mut self, condition: &ast::Expression) -> Result<bool> {
+        return match self.evaluate(condition)? {
+            Value::Boolean(b) => Ok(b),
+            value => Err(Error::InvalidCondition {
+                typ: value.type_name(),
+                position: condition.position.clone(),
+            }),
+        };
+    }
+
+    fn evaluate(&mut self, expression: &ast::Expression) -> Result<Value> {
+        use ast::ExpressionKind::*;
+
+        return match &expression.kind {
+            Identifier(_) | Member(_) | Index(_) => {
+                Ok(self.evaluate_borrowed(expression)?.into_owned())
+            }
+            Boolean(b) => Ok(Value::Boolean(*b)),
+            Integer(i) => match lexer::integer_value(i) {
+                Some(i) => Ok(Value::Integer(i)),
+                None => Err(Error::InvalidLiteral {
+                    literal: i.clone(),
+                    position: expression.position.clone(),
+                }),
+            },
+            Float(f) => match lexer::float_value(f) {
+                Some(f) => Ok(Value::Float(f)),
+                None => Err(Error::InvalidLiteral {
+                    literal: f.clone(),
+                    position: expression.position.clone(),
+                }),
+            },
+            String(s) => Ok(Value::String(lexer::string_value(s))),
+            NoneLiteral => Ok(Value::None),
+            InterpolatedString(parts) => {
+                let mut string = std::string::String::new();
+                for part in parts {
+                    match part {
+                        ast::StringPart::Literal(literal) => {
+                            string.push_str(&lexer::unescape(literal))
+                        }
+                        ast::StringPart::Expression(expression) => {
+                            string.push_str(&self.evaluate_borrowed(expression)?.to_string())
+                        }
+                    }
+                }
+                Ok(Value::String(string))
+            }
+            Tuple(elements) if elements.is_empty() => Ok(Value::Unit),
+            Tuple(elements) => {
+                let mut values = vec![];
+                for element in elements {
+                    values.push(self.evaluate(element)?);
+                }
+                Ok(Value::Tuple(values))
+            }
+            List(elements) => {
+                let mut values = vec![];
+                for element in elements {
+                    values.push(self.evaluate(element)?);
+                }
+                Ok(Value::List(values))
+            }
+            Map(entries) => {
+                let mut values = IndexMap::new();
+                for entry in entries {
+                    let key = map_key(self.evaluate(&entry.key)?, &entry.key.position)?;
+                    let value = self.evaluate(&entry.value)?;
+                    // a repeated key keeps its first position and its last value
+                    values.insert(key, value);
+                }
+                Ok(Value::Map(Box::new(values)))
+            }
+
+            Prefix(prefix) => match (&prefix.operator, &prefix.right.kind) {
+                // the literal of `-9223372036854775808` alone does not fit into an `int`
+                (Token::Minus, Integer(i)) => match lexer::negated_integer_value(i) {
+                    Some(i) => Ok(Value::Integer(i)),
+                    None => Err(Error::InvalidLiteral {
+                        literal: i.clone(),
+                        position: prefix.right.position.clone(),
+                    }),
+                },
+                _ => {
+                    let right = self.evaluate(&prefix.right)?;
+                    evaluate_prefix(&prefix.operator, right, &expression.position)
+                }
+            },
+            Postfix(postfix) => self.evaluate_propagation(postfix, &expression.position),
+            Lambda(lambda) => {
+                let mut captures = HashMap::new();
+                for name in self
+                    .captures
+                    .get(&expression.position)
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Some(binding) = self.environment.get(name) {
+                        captures.insert(name.clone(), binding.clone());
+                    }
+                }
+                Ok(Value::Closure(Rc::new(Closure {
+                    lambda: lambda.clone(),
+                    captures,
+                })))
+            }
+            Infix(infix) if matches!(infix.operator, Token::And | Token::Or) => {
+                self.evaluate_logical(infix, &expression.position)
+            }
+            // the right operand of `??` is only evaluated if the left one is `none`
+            Infix(infix) if infix.operator == Token::Coalesce => {
+                match self.evaluate(&infix.left)? {
+                    Value::None => self.evaluate(&infix.right),
+                    value => Ok(value),
+                }
+            }
+            Infix(infix) => {
+                let left = self.evaluate(&infix.left)?;
+                let right = self.evaluate(&infix.right)?;
+                evaluate_infix(&infix.operator, left, right, &expression.position)
+            }
+            Call(call) => match &call.callee.kind {
+                Member(member) if is_method(&member.name) => {
+                    self.call_method(call, member, &expression.position)
+                }
+                _ => {
+                    let callee = self.evaluate(&call.callee)?;
+                    let mut arguments = vec![];
+                    for argument in &call.arguments {
+                        arguments.push(self.evaluate(argument)?);
+                    }
+
+                    self.call_value(callee, arguments, &call.callee.position)
+                }
+            },
+            Struct(s) => self.evaluate_struct(s, &expression.position),
+            Match(m) => self.evaluate_match(m, &expression.position),
+        };
+    }
+
+    /// Evaluates the expression without copying the value of the variable that it reads,
+    /// so reading a member or an element of a variable only copies the member or the element.
+    fn evaluate_borrowed(&mut self, expression: &ast::Expression) -> Result<Cow<'_, Value>> {
+        let mut steps = vec![];
+        let mut value = match self.evaluate_steps(expression, &mut steps)? {
+            Root::Variable(name, position) => match self.environment.get(name) {
+                Some(binding) => Cow::Borrowed(&binding.value),
+                None => {
+                    return Err(Error::UndefinedVariable {
+                        name: name.into(),
+                        position: position.clone(),
+                    })
+                }
+            },
+            Root::Value(value) => Cow::Owned(value),
+        };
+        for step in steps {
+            value = match value {
+                Cow::Borrowed(object) => select(object, step)?,
+                Cow::Owned(object) => Cow::Owned(select(&object, step)?.into_owned()),
+            };
+        }
+        return Ok(value);
+    }
+
+    /// Evaluates the indices of the expression in order and returns the value that the members
+    /// and elements are selected from.
+    fn evaluate_steps<'a>(
+        &mut self,
+        expression: &'a ast::Expression,
+        steps: &mut Vec<Step<'a>>,
+    ) -> Result<Root<'a>> {
+        return match &expression.kind {
+            ast::ExpressionKind::Identifier(name) => Ok(Root::Variable(name, &expression.position)),
+            ast::ExpressionKind::Member(member) => {
+                let root = self.evaluate_steps(&member.object, steps)?;
+                steps.push(Step::Member(member, &expression.position));
+                Ok(root)
+            }
+            ast::ExpressionKind::Index(index) => {
+                let root = self.evaluate_steps(&index.object, steps)?;
+                let i = self.evaluate(&index.index)?;
+                steps.push(Step::Index(i, &expression.position));
+                Ok(root)
+            }
+            _ => self.evaluate(expression).map(Root::Value),
+        };
+    }
+
+    /// Evaluates a struct literal and fills in the default values of omitted fields.
+    /// Default values are evaluated in the global scope.
+    fn evaluate_struct(&mut self, s: &ast::StructExpression, position: &Span) -> Result<Value> {
+        let mut typ = self.types.get(&s.name);
+        // the limit stops at aliases that refer to themselves
+        for _ in 0..self.types.len() {
+            match typ {
+                Some(ast::Type::Name(name)) => typ = self.types.get(name),
+                _ => break,
+            }
+        }
+        let declared = match typ {
+            Some(ast::Type::Struct(structure)) => structure.clone(),
+            _ => {
+                return Err(Error::UndefinedType {
+                    name: s.name.clone(),
+                    position: position.clone(),
+                })
+            }
+        };
+
+        let mut values = vec![];
+        for field in &s.fields {
+            if !declared.fields.iter().any(|f| f.name == field.name) {
+                return Err(Error::UndefinedMember {
+                    typ: s.name.clone(),
+                    name: field.name.clone(),
+                    position: field.value.position.clone(),
+                });
+            }
+            values.push((field.name.clone(), self.evaluate(&field.value)?));
+        }
+
+        let mut fields = vec![];
+        for field in declared.fields {
+            let value = match values.iter().position(|(name, _)| *name == field.name) {
+                Some(index) => values.swap_remove(index).1,
+                None => match &field.default {
+                    Some(default) => {
+                        let caller = self.environment.enter_function();
+                        let value = self.evaluate(default);
+                        self.environment.leave_function(caller);
+                        value?
+                    }
+                    None => {
+                        return Err(Error::MissingField {
+                            name: field.name,
+                            position: position.clone(),
+                        })
+                    }
+                },
+            };
+            fields.push((field.name, value));
+        }
+
+        return Ok(Value::Struct {
+            name: s.name.clone(),
+            fields,
+        });
+    }
+
+    fn call_value(
+        &mut self,
+        callee: Value,
+        arguments: Vec<Value>,
+        position: &Span,
+    ) -> Result<Value> {
+        return match callee {
+            Value::Function(function) => self.call_function(&function, arguments),
+            Value::Closure(closure) => self.call_closure(&closure, arguments),
+            Value::Builtin(name) => match arguments.as_slice() {
+                [value] => {
+                    println!("{}", value);
+                    Ok(Value::Unit)
+                }
+                _ => Err(Error::ArgumentCount {
+                    name: name.into(),
+                    want: 1,
+                    got: arguments.len(),
+                }),
+            },
+            Value::Constructor {
+                typ,
+                name,
+                elements,
+            } => {
+                if arguments.len() != elements {
+                    return Err(Error::ArgumentCount {
+                        name,
+                        want: elements,
+                        got: arguments.len(),
+                    });
+                }
+                Ok(Value::Variant {
+                    typ,
+                    name,
+                    elements: arguments,
+                })
+            }
+            value => Err(Error::NotCallable {
+                typ: value.type_name(),
+                position: position.clone(),
+            }),
+        };
+    }
+
+    /// Calls a method of a list or a map. A list or map stored in a variable is changed in place,
+    /// and only borrowed from the variable by methods that do not change it.
+    /// Members of other values with these names are called like any other callee.
+    fn call_method(
+        &mut self,
+        call: &ast::CallExpression,
+        member: &ast::MemberExpression,
+        position: &Span,
+    ) -> Result<Value> {
+        let mut arguments = vec![];
+        for argument in &call.arguments {
+            arguments.push(self.evaluate(argument)?);
+        }
+
+        if !matches!(member.name.as_str(), "push" | "pop" | "remove") {
+            let object = self.evaluate_borrowed(&member.object)?;
+            let want = match (&*object, member.name.as_str()) {
+                (Value::List(_) | Value::Map(_), "len") => Ok(0),
+                (Value::Map(_), "contains") => Ok(1),
+                (object, _) => Err(member_value(object, member, &call.callee.position)?.clone()),
+            };
+            let want = match want {
+                Ok(want) => want,
+                Err(callee) => {
+                    drop(object);
+                    return self.call_value(callee, arguments, &call.callee.position);
+                }
+            };
+            check_argument_count(member, want, &arguments)?;
+
+            return match (&*object, member.name.as_str(), arguments.pop()) {
+                (Value::List(elements), "len", _) => Ok(Value::Integer(elements.len() as i64)),
+                (Value::Map(entries), "len", _) => Ok(Value::Integer(entries.len() as i64)),
+                (Value::Map(entries), "contains", Some(key)) => {
+                    let key = map_key(key, position)?;
+                    Ok(Value::Boolean(entries.contains_key(&key)))
+                }
+                (object, _, _) => Err(Error::UndefinedMember {
+                    typ: object.type_name(),
+                    name: member.name.clone(),
+                    position: call.callee.position.clone(),
+                }),
+            };
+        }
+
+        let mut temporary;
+        let object = match member.object.assigned_variable() {
+            Some(_) => self.place(&member.object, position).map_err(|e| match e {
+                Error::ImmutableAssignment { name, position } => Error::ImmutableMutation {
+                    name,
+                    method: member.name.clone(),
+                    position,
+                },
+                e => e,
+            })?,
+            None => {
+                temporary = self.evaluate(&member.object)?;
+                &mut temporary
+            }
+        };
+
+        let want = match (&*object, member.name.as_str()) {
+            (Value::List(_), "pop") => 0,
+            (Value::List(_), "push") | (Value::Map(_), "remove") => 1,
+            (object, _) => {
+                let callee = member_value(object, member, &call.callee.position)?.clone();
+                return self.call_value(callee, arguments, &call.callee.position);
+            }
+        };
+        check_argument_count(member, want, &arguments)?;
+
+        return match (object, member.name.as_str(), arguments.pop()) {
+            (Value::List(elements), "push", Some(element)) => {
+                elements.push(element);
+                Ok(Value::Unit)
+            }
+            (Value::List(elements), "pop", _) => elements.pop().ok_or_else(|| Error::EmptyList {
+                position: position.clone(),
+            }),
+            (Value::Map(entries), "remove", Some(key)) => {
+                let key = map_key(key, position)?;
+                // the remaining entries keep their order
+                entries
+                    .shift_remove(&key)
+                    .ok_or_else(|| key_not_found(&key, position))
+            }
+            (object, _, _) => Err(Error::UndefinedMember {
+                typ: object.type_name(),
+                name: member.name.clone(),
+                position: call.callee.position.clone(),
+            }),
+        };
+    }
+
+    /// Evaluates `&&` and `||`, which only evaluate the right operand
+    /// if the left one does not decide the result.
+    fn evaluate_logical(&mut self, infix: &ast::InfixExpression, position: &Span) -> Result<Value> {
+        let left = self.evaluate(&infix.left)?;
+        if left == Value::Boolean(infix.operator == Token::Or) {
+            return Ok(left);
+        }
+
+        let right = self.evaluate(&infix.right)?;
+        return match (left, right) {
+            (Value::Boolean(_), Value::Boolean(right)) => Ok(Value::Boolean(right)),
+            (left, right) => Err(Error::InvalidOperands {
+                operator: infix.operator.to_string(),
+                operands: format!("{} and {}", left.type_name(), right.type_name()),
+                position: position.clone(),
+            }),
+        };
+    }
+}
+
+/// Returns the value that a function returns after executing its body with `result`.
+/// An error that `?` propagates out of the body is the returned value.
+fn returned_value(result: Result<Flow>) -> Result<Value> {
+    let flow = match result {
+        Err(Error::Propagated { error, .. }) => return Ok(error),
+        result => result?,
+    };
+    return match flow {
+        Flow::Return(value) => Ok(value),
+        // `break` and `continue` outside of a loop are rejected by the resolver
+        Flow::Next | Flow::Break | Flow::Continue => Ok(Value::Unit),
+    };
+}
+
+/// Returns the member or the element of the value that the step selects.
+fn select<'a>(object: &'a Value, step: Step) -> Result<Cow<'a, Value>> {
+    return match step {
+        Step::Member(member, position) => member_value(object, member, position).map(Cow::Borrowed),
+        Step::Index(index, position) => evaluate_index(object, index, position),
+    };
+}
+
+/// Returns the element of a tuple or the field of a struct.
+fn member_value<'a>(
+    object: &'a Value,
+    member: &ast::MemberExpression,
+    position: &Span,
+) -> Result<&'a Value> {
+    let value = match (object, member.name.parse::<usize>()) {
+        (Value::Tuple(elements), Ok(index)) => elements.get(index),
+        (Value::Struct { fields, .. }, _) => fields
+            .iter()
+            .find(|(name, _)| *name == member.name)
+            .map(|(_, value)| value),
+        _ => None,
+    };
+    return value.ok_or_else(|| Error::UndefinedMember {
+        typ: object.type_name(),
+        name: member.name.clone(),
+        position: position.clone(),
+    });
+}
+
+/// Returns the element at the index, which is a new string with one character for strings.
+fn evaluate_index<'a>(object: &'a Value, index: Value, position: &Span) -> Result<Cow<'a, Value>> {
+    return match (object, index) {
+        (Value::String(s), Value::Integer(i)) => {
+            let i = checked_index(i, s.chars().count(), position)?;
+            let c = s.chars().nth(i).unwrap_or_default();
+            Ok(Cow::Owned(Value::String(c.into())))
+        }
+        (Value::List(elements), Value::Integer(i)) => {
+            let i = checked_index(i, elements.len(), position)?;
+            Ok(Cow::Borrowed(&elements[i]))
+        }
+        (Value::Map(entries), key) => {
+            let key = map_key(key, position)?;
+            match entries.get(&key) {
+                Some(value) => Ok(Cow::Borrowed(value)),
+                None => Err(key_not_found(&key, position)),
+            }
+        }
+        (object @ (Value::String(_) | Value::List(_)), index) => Err(Error::InvalidOperands {
+            operator: "[]".into(),
+            operands: format!("{} and {}", object.type_name(), index.type_name()),
+            position: position.clone(),
+        }),
+        (object, _) => Err(Error::NotIndexable {
+            typ: object.type_name(),
+            position: position.clone(),
+        }),
+    };
+}
+
+/// Reports a call of the list or map method with the wrong number of arguments.
+fn check_argument_count(
+    member: &ast::MemberExpression,
+    want: usize,
+    arguments: &[Value],
+) -> Result<()> {
+    if arguments.len() != want {
+        return Err(Error::ArgumentCount {
+            name: member.name.clone(),
+            want,
+            got: arguments.len(),
+        });
+    }
+    return Ok(());
+}
+
+/// Returns the value as a map key or reports that its type cannot be hashed.
+fn map_key(key: Value, position: &Span) -> Result<Key> {
+    let typ = key.type_name();
+    return Key::new(key).ok_or_else(|| Error::InvalidKey {
+        typ,
+        position: position.clone(),
+    });
+}
+
+fn key_not_found(key: &Key, position: &Span) -> Error {
+    return Error::KeyNotFound {
+        key: key.value().to_string(),
+        position: position.clone(),
+    };
+}
+
+/// Returns whether calls of the member are calls of a method of lists or maps.
+fn is_method(name: &str) -> bool {
+    return matches!(name, "len" | "push" | "pop" | "contains" | "remove");
+}
+
+/// Returns the index if it is within the bounds of a sequence of the length.
+fn checked_index(index: i64, length: usize, position: &Span) -> Result<usize> {
+    return match usize::try_from(index) {
+        Ok(i) if i < length => Ok(i),
+        _ => Err(Error::IndexOutOfBounds {
+            index,
+            length,
+            position: position.clone(),
+        }),
+    };
+}
+
+fn evaluate_prefix(operator: &Token, right: Value, position: &Span) -> Result<Value> {
+    return match (operator, right) {
+        (Token::Minus, Value::Integer(i)) => Ok(Value::Integer(i.wrapping_neg())),
+        (Token::Minus, Value::Float(f)) => Ok(Value::Float(-f)),
+        (Token::ExclamationMark, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+        (Token::Tilde, Value::Integer(i)) => Ok(Value::Integer(!i)),
+        (operator, right) => Err(Error::InvalidOperands {
+            operator: operator.to_string(),
+            operands: right.type_name(),
+            position: position.clone(),
+        }),
+    };
+}
+
+fn evaluate_infix(operator: &Token, left: Value, right: Value, position: &Span) -> Result<Value> {
+    use Value::*;
+
+    return match (operator, left, right) {
+        (Token::Plus, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_add(r))),
+        (Token::Minus, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_sub(r))),
+        (Token::Asterisk, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_mul(r))),
+        (Token::Slash | Token::Percent, Integer(_), Integer(0)) => Err(Error::DivisionByZero {
+            position: position.clone(),
+        }),
+        (Token::Slash, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_div(r))),
+        (Token::Percent, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_rem(r))),
+        (Token::LessThan, Integer(l), Integer(r)) => Ok(Boolean(l < r)),
+        (Token::GreaterThan, Integer(l), Integer(r)) => Ok(Boolean(l > r)),
+        (Token::LessThanOrEqual, Integer(l), Integer(r)) => Ok(Boolean(l <= r)),
+        (Token::GreaterThanOrEqual, Integer(l), Integer(r)) => Ok(Boolean(l >= r)),
+        (Token::Ampersand, Integer(l), Integer(r)) => Ok(Integer(l & r)),
+        (Token::Pipe, Integer(l), Integer(r)) => Ok(Integer(l | r)),
+        (Token::Caret, Integer(l), Integer(r)) => Ok(Integer(l ^ r)),
+        // the shift amount is taken modulo 64 like in the compiled code
+        (Token::ShiftLeft, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_shl(r as u32))),
+        (Token::ShiftRight, Integer(l), Integer(r)) => Ok(Integer(l.wrapping_shr(r as u32))),
+
+        (Token::Plus, Float(l), Float(r)) => Ok(Float(l + r)),
+        (Token::Minus, Float(l), Float(r)) => Ok(Float(l - r)),
+        (Token::Asterisk, Float(l), Float(r)) => Ok(Float(l * r)),
+        (Token::Slash, Float(l), Float(r)) => Ok(Float(l / r)),
+        (Token::Percent, Float(l), Float(r)) => Ok(Float(l % r)),
+        (Token::LessThan, Float(l), Float(r)) => Ok(Boolean(l < r)),
+        (Token::GreaterThan, Float(l), Float(r)) => Ok(Boolean(l > r)),
+        (Token::LessThanOrEqual, Float(l), Float(r)) => Ok(Boolean(l <= r)),
+        (Token::GreaterThanOrEqual, Float(l), Float(r)) => Ok(Boolean(l >= r)),
+
+        (Token::Plus, String(l), String(r)) => Ok(String(l + &r)),
+        (Token::LessThan, String(l), String(r)) => Ok(Boolean(l < r)),
+        (Token::GreaterThan, String(l), String(r)) => Ok(Boolean(l > r)),
+        (Token::LessThanOrEqual, String(l), String(r)) => Ok(Boolean(l <= r)),
+        (Token::GreaterThanOrEqual, String(l), String(r)) => Ok(Boolean(l >= r)),
+
+        (Token::Equal, l, r) if l.type_name() == r.type_name() => Ok(Boolean(l == r)),
+        (Token::NotEqual, l, r) if l.type_name() == r.type_name() => Ok(Boolean(l != r)),
+        // an optional value is compared with `none`
+        (Token::Equal, l, r) if l == None || r == None => Ok(Boolean(false)),
+        (Token::NotEqual, l, r) if l == None || r == None => Ok(Boolean(true)),
+
+        (operator, l, r) => Err(Error::InvalidOperands {
+            operator: operator.to_string(),
+            operands: format!("{} and {}", l.type_name(), r.type_name()),
+            position: position.clone(),
+        }),
+    };
+}
+
+/// Loads the file and calls its `main` function.
+pub fn run(file: &ast::File) -> Result<Value> {
+    let mut interpreter = Interpreter::new();
+    interpreter.load(file)?;
+    return interpreter.call("main", vec![]);
+}
+
+#[cfg(test)]
+#[path = "tests/interp.rs"]
+mod tests;
@@ -0,0 +1,727 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+use crate::parser::Parser;
+
+#[test]
+fn interp_expressions() {
+    let cases = &[
+        ("1 + 2 * 3", Value::Integer(7)),
+        ("7 / 2 - 10 % 4", Value::Integer(1)),
+        ("-(1.5 * 2.0)", Value::Float(-3.0)),
+        ("7.5 % 2.0", Value::Float(1.5)),
+        ("0xFF + 0o17 + 0b1010 + 1_000", Value::Integer(1280)),
+        ("-9223372036854775808", Value::Integer(i64::MIN)),
+        ("2.5e3 * 1e-3", Value::Float(2.5)),
+        (
+            r#""Hello" + " " + "World""#,
+            Value::String("Hello World".into()),
+        ),
+        (r#""a" < "b""#, Value::Boolean(true)),
+        ("!(1 == 2)", Value::Boolean(true)),
+        (
+            r#""a\tb\n\"c\" \\ \{\} \u{1F600}""#,
+            Value::String("a\tb\n\"c\" \\ {} \u{1F600}".into()),
+        ),
+        (
+            r##"r#"C:\path\{"name"}"#"##,
+            Value::String(r#"C:\path\{"name"}"#.into()),
+        ),
+        ("\"one\n  two\"", Value::String("one\n  two".into())),
+        (
+            r#""{1 + 1}\n{"\"{true}\""}""#,
+            Value::String("2\n\"true\"".into()),
+        ),
+        ("true != false", Value::Boolean(true)),
+        ("0b1100 & 0b1010 | 0b0001 ^ 0b0011", Value::Integer(0b1010)),
+        ("~0 << 4 >> 2", Value::Integer(-4)),
+        ("1 << 65", Value::Integer(2)),
+        (
+            "1 <= 1 && 2.5 >= 3.0 || \"b\" >= \"a\"",
+            Value::Boolean(true),
+        ),
+        ("false && 1 / 0 == 0", Value::Boolean(false)),
+        ("true || 1 / 0 == 0", Value::Boolean(true)),
+    ];
+
+    for case in cases {
+        let source = format!("fn main() {{\n  return {}\n}}", case.0);
+        assert_value_equal(&[(&source, case.1.clone())]);
+    }
+}
+
+#[test]
+fn interp_statements() {
+    let cases = &[
+        (
+            r#"
+fn main() {
+  mut a := 1
+  {
+    a = a + 1
+    mut a := 10
+    a = 20
+  }
+  return a
+}
+"#,
+            Value::Integer(2),
+        ),
+        (
+            r#"
+fn main() {
+  {
+    {
+      return "inner"
+    }
+  }
+  return "outer"
+}
+"#,
+            Value::String("inner".into()),
+        ),
+        (
+            r#"
+fn main() {
+  a := 1
+}
+"#,
+            Value::Unit,
+        ),
+        (
+            r#"
+fn main() {
+  a := 42
+  mut b := ""
+  if a > 9000 {
+    b = "It's over 9000."
+  } else if a < 0 {
+    b = "It's negative."
+  } else {
+    b = "It's only 42."
+  }
+  return b
+}
+"#,
+            Value::String("It's only 42.".into()),
+        ),
+        (
+            r#"
+fn main() {
+  if 1 < 2 {
+    return "then"
+  }
+  return "after"
+}
+"#,
+            Value::String("then".into()),
+        ),
+        (
+            r#"
+fn main() {
+  mut i := 0
+  mut sum := 0
+  while true {
+    i = i + 1
+    if i > 10 {
+      break
+    }
+    if i % 2 == 0 {
+      continue
+    }
+    sum = sum + i
+  }
+  return sum
+}
+"#,
+            Value::Integer(25),
+        ),
+        (
+            r#"
+fn main() {
+  mut reversed := ""
+  for c in "stressed" {
+    reversed = c + reversed
+  }
+  return reversed
+}
+"#,
+            Value::String("desserts".into()),
+        ),
+        (
+            r#"
+fn main() {
+  mut a := 10
+  a += 5
+  a -= 1
+  a *= 3
+  a /= 4
+  a %= 6
+  mut s := "a"
+  s += "b"
+  return "{a}{s}"
+}
+"#,
+            Value::String("4ab".into()),
+        ),
+        (
+            r#"
+fn divmod(a: int, b: int): (int, int) {
+  return (a / b, a % b)
+}
+
+fn main() {
+  (q, r) := divmod(17, 5)
+  mut (t, _) := ((q, r), ())
+  t = (t.1, t.0)
+  return "{t} {t.0 * 10 + t.1} {(1,)}"
+}
+"#,
+            Value::String("(2, 3) 23 (1,)".into()),
+        ),
+        (
+            r#"
+type Point = { x: int, y: int = 10 }
+
+fn main() {
+  mut p := Point{ x: 1 }
+  q := p
+  p.x += 5
+  mut t := (p, 0)
+  t.0.y = 3
+  return "{p.x} {p.y} {q.x} {t.0.y} {t.0}"
+}
+"#,
+            Value::String("6 10 1 3 Point{ x: 6, y: 3 }".into()),
+        ),
+        (
+            r#"
+fn main() {
+  s := "héllo"
+  t := ((s, 1),)
+  return s[1] + t.0.0[t.0.1 + 3]
+}
+"#,
+            Value::String("éo".into()),
+        ),
+        (
+            r#"
+fn main() {
+  mut xs := [3, 1]
+  ys := xs
+  xs.push(4)
+  xs[1] += xs.pop()
+  mut grid := [[0], [1]]
+  grid[1].push(xs.len())
+  mut sum := 0
+  for x in xs {
+    sum += x
+  }
+  return "{sum} {ys} {grid} {[(1, "a")]}"
+}
+"#,
+            Value::String("8 [3, 1] [[0], [1, 2]] [(1, a)]".into()),
+        ),
+        (
+            r#"
+fn main() {
+  mut ages := {"bob": 25, "alice": 30, "bob": 26}
+  ages["carol"] = 41
+  ages["alice"] += 1
+  copy := ages
+  removed := ages.remove("bob")
+  ages["bob"] = removed
+  mut names := ""
+  for entry in ages {
+    names += "{entry.0}={entry.1} "
+  }
+  return "{names}{ages.len()} {ages.contains("dave")} {copy}"
+}
+"#,
+            Value::String(
+                "alice=31 carol=41 bob=26 3 false {bob: 26, alice: 31, carol: 41}".into(),
+            ),
+        ),
+        (
+            r#"
+fn grid(): [[int]] {
+  return [[1, 2], [3, 4]]
+}
+
+fn main() {
+  m := {"a": [1, 2], "b": [3]}
+  s := "xyz"
+  return "{m["a"][1]} {m["b"][0]} {grid()[1][0]} {s[2]} {[[5]][0][0]} {m}"
+}
+"#,
+            Value::String("2 3 3 z 5 {a: [1, 2], b: [3]}".into()),
+        ),
+        (
+            r#"
+type Bag = { items: [int], counts: {string: int} }
+
+fn main() {
+  bag := Bag{ items: [1, 2, 3], counts: {"a": 1} }
+  pair := (bag, [4])
+  return "{bag.items[2]} {bag.items.len()} {bag.counts.contains("a")} {pair.0.counts["a"]} {pair.1.len()} {pair.1}"
+}
+"#,
+            Value::String("3 3 true 1 1 [4]".into()),
+        ),
+        (
+            r#"
+fn fact(n: int): int {
+  return 0
+}
+
+fn main() {
+  fn fact(n: int): int {
+    if n < 2 {
+      return 1
+    }
+    return n * fact(n - 1)
+  }
+  return fact(5)
+}
+"#,
+            Value::Integer(120),
+        ),
+        (
+            r#"
+x := 7
+type Line = { from: Point, to: Point = Point{ x: 0 } }
+type Point = { x: int, y: int = x }
+
+fn main() {
+  return Line{ from: Point{ y: 1, x: 2 } }
+}
+"#,
+            Value::Struct {
+                name: "Line".into(),
+                fields: vec![
+                    (
+                        "from".into(),
+                        Value::Struct {
+                            name: "Point".into(),
+                            fields: vec![
+                                ("x".into(), Value::Integer(2)),
+                                ("y".into(), Value::Integer(1)),
+                            ],
+                        },
+                    ),
+                    (
+                        "to".into(),
+                        Value::Struct {
+                            name: "Point".into(),
+                            fields: vec![
+                                ("x".into(), Value::Integer(0)),
+                                ("y".into(), Value::Integer(7)),
+                            ],
+                        },
+                    ),
+                ],
+            },
+        ),
+        (
+            r#"
+fn fib(n: int): int {
+  if n < 2 {
+    return n
+  }
+  return fib(n - 1) + fib(n - 2)
+}
+
+fn main() {
+  return fib(
+    20
+  )
+}
+"#,
+            Value::Integer(6765),
+        ),
+        (
+            r#"
+fn describe(a: int): string {
+  if a > 9000 {
+    return "It's over 9000."
+  }
+  return "It's only {a}, {a < 0} {"[{a}]"}."
+}
+
+fn main() {
+  return describe(42)
+}
+"#,
+            Value::String("It's only 42, false [42].".into()),
+        ),
+        (
+            r#"
+enum Shape { Circle(float), Rect(float, float), Empty }
+
+fn area(s: Shape): float {
+  return match s {
+    Circle(r) => 3.0 * r * r
+    Rect(w, h) => w * h
+    Empty => 0.0
+  }
+}
+
+enum List { Cons(int, List), Nil }
+
+fn sum(l: List): int {
+  return match l {
+    Cons(x, rest) => x + sum(rest)
+    Nil => 0
+  }
+}
+
+fn main(): string {
+  shapes := [Circle(1.0), Rect(2.0, 3.0), Empty]
+  mut total := 0.0
+  for s in shapes {
+    total += area(s)
+  }
+  t := match (Empty, 1) {
+    (Circle(_), _) => "c"
+    (Empty, n) => "empty {n}"
+    _ => "other"
+  }
+  return "{total} {sum(Cons(1, Cons(2, Nil)))} {t}"
+}
+"#,
+            Value::String("9 3 empty 1".into()),
+        ),
+        (
+            r#"
+fn find(xs: [int], x: int): int? {
+  mut i := 0
+  for y in xs {
+    if y == x {
+      return i
+    }
+    i += 1
+  }
+  return none
+}
+
+fn main(): string {
+  xs := [3, 5, 7]
+  a := find(xs, 5) ?? -1
+  b := find(xs, 4) ?? find(xs, 7) ?? -1
+  mut s := ""
+  if i := find(xs, 7) {
+    s = "at {i}"
+  } else {
+    s = "missing"
+  }
+  mut m := none
+  m = 2
+  c := m == none
+  return "{a} {b} {s} {c}"
+}
+"#,
+            Value::String("1 2 at 2 false".into()),
+        ),
+        (
+            r#"
+fn parse(s: string): int!string {
+  if s == "one" {
+    return Ok(1)
+  }
+  return Err("bad {s}")
+}
+
+fn sum(a: string, b: string): int!string {
+  x := parse(a)?
+  y := parse(b)?
+  return Ok(x + y)
+}
+
+fn show(r: int!string): string {
+  return match r {
+    Ok(v) => "ok {v}"
+    Err(e) => e
+  }
+}
+
+fn main(): string {
+  return "{show(sum("one", "one"))}, {show(sum("one", "two"))}, {show(sum("six", "one"))}"
+}
+"#,
+            Value::String("ok 2, bad two, bad six".into()),
+        ),
+        (
+            r#"
+fn map(xs: [int], f: fn (x: int): int): [int] {
+  mut ys := []
+  for x in xs {
+    ys.push(f(x))
+  }
+  return ys
+}
+
+fn adder(n: int): fn (x: int): int {
+  return fn (x: int): int { return x + n }
+}
+
+fn main(): string {
+  mut factor := 10
+  scale := fn (x: int): int {
+    return x * factor
+  }
+  factor = 100
+  add := adder(1)
+  twice := fn (f: fn (x: int): int, x: int): int { return f(f(x)) }
+  ys := map([1, 2], scale)
+  square := twice(fn (x: int): int { return x * x }, 3)
+  return "{ys[0]} {ys[1]} {twice(add, 5)} {square}"
+}
+"#,
+            Value::String("10 20 7 81".into()),
+        ),
+    ];
+
+    assert_value_equal(cases);
+}
+
+#[test]
+fn interp_call() {
+    let source = r#"
+scale := 10
+
+fn add(a: int, b: int): int {
+  sum := a + b
+  return sum * scale
+}
+"#;
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .load(&Parser::new(source).parse().unwrap())
+        .unwrap();
+
+    assert_eq!(
+        Ok(Value::Integer(30)),
+        interpreter.call("add", vec![Value::Integer(1), Value::Integer(2)])
+    );
+    assert_eq!(
+        Err(Error::ArgumentCount {
+            name: "add".into(),
+            want: 2,
+            got: 1
+        }),
+        interpreter.call("add", vec![Value::Integer(1)])
+    );
+    assert_eq!(
+        Err(Error::UndefinedFunction { name: "sub".into() }),
+        interpreter.call("sub", vec![])
+    );
+}
+
+#[test]
+fn interp_captures() {
+    let source = r#"
+fn counter(start: int): fn (): int {
+  unused := [1, 2, 3]
+  step := 2
+  return fn (): int {
+    inner := fn (): int { return start }
+    return inner() + step
+  }
+}
+"#;
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .load(&Parser::new(source).parse().unwrap())
+        .unwrap();
+
+    let Ok(Value::Closure(closure)) = interpreter.call("counter", vec![Value::Integer(5)]) else {
+        panic!("counter does not return a closure");
+    };
+    let mut names: Vec<&String> = closure.captures.keys().collect();
+    names.sort();
+    assert_eq!(vec!["start", "step"], names);
+    assert_eq!(
+        Ok(Value::Integer(7)),
+        interpreter.call_closure(&closure, vec![])
+    );
+}
+
+#[test]
+fn interp_errors() {
+    let cases = &[
+        (
+            r#"
+fn main() {
+  mut n := 1
+  n.push(2)
+}
+"#,
+            Error::UndefinedMember {
+                typ: "int".into(),
+                name: "push".into(),
+                position: Span::from((28, 4, 26), (33, 4, 26)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  a := 1
+  a = 2
+}
+"#,
+            Error::ImmutableAssignment {
+                name: "a".into(),
+                position: Span::from((24, 4, 22), (28, 4, 22)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  p := (1, 2)
+  p.0 = 3
+}
+"#,
+            Error::ImmutableAssignment {
+                name: "p".into(),
+                position: Span::from((29, 4, 27), (35, 4, 27)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  xs := [1]
+  xs.push(2)
+}
+"#,
+            Error::ImmutableMutation {
+                name: "xs".into(),
+                method: "push".into(),
+                position: Span::from((27, 4, 25), (36, 4, 25)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  {
+    a := 1
+  }
+  return a
+}
+"#,
+            Error::UndefinedVariable {
+                name: "a".into(),
+                position: Span::from((41, 6, 32), (41, 6, 32)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  return 1 / 0
+}
+"#,
+            Error::DivisionByZero {
+                position: Span::from((22, 3, 13), (26, 3, 13)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  return "a" - 1
+}
+"#,
+            Error::InvalidOperands {
+                operator: "-".into(),
+                operands: "string and int".into(),
+                position: Span::from((22, 3, 13), (28, 3, 13)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  return "abc"[3]
+}
+"#,
+            Error::IndexOutOfBounds {
+                index: 3,
+                length: 3,
+                position: Span::from((22, 3, 13), (29, 3, 13)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  mut xs := [1]
+  xs[-1] = 2
+}
+"#,
+            Error::IndexOutOfBounds {
+                index: -1,
+                length: 1,
+                position: Span::from((31, 4, 29), (36, 4, 29)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  mut xs := []
+  xs.pop()
+}
+"#,
+            Error::EmptyList {
+                position: Span::from((30, 4, 28), (37, 4, 28)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  mut m := {1: 2}
+  m[3] += 1
+}
+"#,
+            Error::KeyNotFound {
+                key: "3".into(),
+                position: Span::from((33, 4, 31), (36, 4, 31)),
+            },
+        ),
+        (
+            r#"
+fn main() {
+  m := {1: 2}
+  return m.contains(1.5)
+}
+"#,
+            Error::InvalidKey {
+                typ: "float".into(),
+                position: Span::from((36, 4, 27), (50, 4, 27)),
+            },
+        ),
+        (
+            r#"
+enum E { A(int), B }
+
+fn main() {
+  return match A(1) {
+    B => 0
+  }
+}
+"#,
+            Error::NoMatch {
+                value: "A(1)".into(),
+                position: Span::from((44, 5, 35), (70, 7, 68)),
+            },
+        ),
+    ];
+
+    for case in cases {
+        let file = Parser::new(case.0).parse().unwrap();
+        assert_eq!(Err(case.1.clone()), run(&file));
+    }
+}
+
+fn assert_value_equal(cases: &[(&str, Value)]) {
+    for case in cases {
+        let file = Parser::new(case.0).parse().unwrap();
+        assert_eq!(Ok(case.1.clone()), run(&file), "{}", case.0);
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use indexmap::IndexMap;
+
+use super::Binding;
+use crate::ast;
+
+/// Runtime value of an X expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Unit,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Tuple(Vec<Value>),
+    List(Vec<Value>),
+    /// Entries of a map in the order their keys were first inserted, boxed to keep values small.
+    Map(Box<IndexMap<Key, Value>>),
+    /// Instance of a declared struct type with its fields in declaration order.
+    Struct {
+        name: String,
+        fields: Vec<(String, Value)>,
+    },
+    /// Value of an enum type: `Circle(1.5)`, `Empty`
+    Variant {
+        typ: String,
+        name: String,
+        elements: Vec<Value>,
+    },
+    /// Variant with elements, which is called with them to create a `Value::Variant`.
+    Constructor {
+        typ: String,
+        name: String,
+        elements: usize,
+    },
+    Function(Rc<ast::FunctionDeclaration>),
+    Closure(Rc<Closure>),
+    /// Function that the language provides, like `print`.
+    Builtin(&'static str),
+    /// `none`. Optional values that are not `none` are their value itself.
+    None,
+}
+
+/// Value of a lambda with copies of the bindings it captures from the function that evaluated it.
+/// The resolver rejects assignments to them in the lambda, so they are never changed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Closure {
+    pub lambda: ast::LambdaExpression,
+    pub captures: HashMap<String, Binding>,
+}
+
+/// Key of a map. Only values of types that can be hashed can be keys.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Key {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl Key {
+    /// Returns the key for the value, or `None` if the value cannot be a key.
+    pub fn new(value: Value) -> Option<Self> {
+        return match value {
+            Value::Boolean(b) => Some(Key::Boolean(b)),
+            Value::Integer(i) => Some(Key::Integer(i)),
+            Value::String(s) => Some(Key::String(s)),
+            _ => None,
+        };
+    }
+
+    /// Returns the value of the key.
+    pub fn value(&self) -> Value {
+        return match self {
+            Key::Boolean(b) => Value::Boolean(*b),
+            Key::Integer(i) => Value::Integer(*i),
+            Key::String(s) => Value::String(s.clone()),
+        };
+    }
+}
+
+impl Value {
+    /// Returns the name of the X type of the value.
+    pub fn type_name(&self) -> String {
+        return match self {
+            Value::Unit => "()".into(),
+            Value::Boolean(_) => "bool".into(),
+            Value::Integer(_) => "int".into(),
+            Value::Float(_) => "float".into(),
+            Value::String(_) => "string".into(),
+            Value::Tuple(_) => "tuple".into(),
+            Value::List(_) => "list".into(),
+            Value::Map(_) => "map".into(),
+            Value::Struct { name, .. } => name.clone(),
+            Value::Variant { typ, .. } => typ.clone(),
+            Value::Constructor { .. }
+            | Value::Function(_)
+            | Value::Closure(_)
+            | Value::Builtin(_) => "fn".into(),
+            Value::None => "none".into(),
+        };
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(Value::to_string).collect();
+                match elements.as_slice() {
+                    [element] => write!(f, "({},)", element),
+                    _ => write!(f, "({})", elements.join(", ")),
+                }
+            }
+            Value::List(elements) => {
+                let elements: Vec<String> = elements.iter().map(Value::to_string).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Value::Map(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.value(), value))
+                    .collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Value::Struct { name, fields } if fields.is_empty() => write!(f, "{}{{}}", name),
+            Value::Struct { name, fields } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value))
+                    .collect();
+                write!(f, "{}{{ {} }}", name, fields.join(", "))
+            }
+            Value::Variant { name, elements, .. } if elements.is_empty() => write!(f, "{}", name),
+            Value::Variant { name, elements, .. } => {
+                let elements: Vec<String> = elements.iter().map(Value::to_string).collect();
+                write!(f, "{}({})", name, elements.join(", "))
+            }
+            Value::Constructor { name, .. } => write!(f, "fn {}", name),
+            Value::Function(function) => write!(f, "fn {}", function.name),
+            Value::Closure(_) => write!(f, "fn"),
+            Value::Builtin(name) => write!(f, "fn {}", name),
+            Value::None => write!(f, "none"),
+        }
+    }
+}
@@ -15,10 +15,15 @@ pub struct Cursor<'a> {
 
 impl<'a> Cursor<'a> {
     pub fn new(source: &'a str) -> Self {
+        return Self::with_position(source, Position::new());
+    }
+
+    /// Creates a cursor over a source that starts at `position` within an enclosing source.
+    pub fn with_position(source: &'a str, position: Position) -> Self {
         let mut cursor = Self {
             chars: source.chars(),
             current: EOF,
-            position: Position::new(),
+            position,
         };
         cursor.next();
         return cursor;
@@ -7,6 +7,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 #[derive(Clone, Debug, PartialEq)]
 pub enum Error {
     UnexpectedEOF { position: Position },
+    InvalidEscape { escape: String, position: Position },
+    InvalidNumber { number: String, position: Position },
 }
 
 impl fmt::Display for Error {
@@ -14,6 +16,12 @@ impl fmt::Display for Error {
         use Error::*;
         match self {
             UnexpectedEOF { position } => write!(f, "Unexpected EOF @ {}", position),
+            InvalidEscape { escape, position } => {
+                write!(f, "Invalid escape sequence {} @ {}", escape, position)
+            }
+            InvalidNumber { number, position } => {
+                write!(f, "Invalid number literal {} @ {}", number, position)
+            }
         }
     }
 }
@@ -1,9 +1,13 @@
 mod cursor;
 mod error;
+mod number;
+mod string;
 use cursor::*;
 pub use error::*;
+pub use number::*;
+pub use string::*;
 
-use crate::token::{Span, Token, TokenInfo};
+use crate::token::{Position, Span, Token, TokenInfo};
 
 #[derive(Clone, Debug)]
 pub struct Lexer<'a> {
@@ -23,6 +27,17 @@ impl<'a> Lexer<'a> {
         };
     }
 
+    /// Creates a lexer over a source that starts at `position` within an enclosing source,
+    /// such as the embedded expression of an interpolated string.
+    pub fn with_position(source: &'a str, position: Position) -> Self {
+        return Lexer {
+            cursor: Cursor::with_position(source, position),
+
+            token: Token::EOF,
+            position: Span::new(),
+        };
+    }
+
     pub fn current(&self) -> TokenInfo {
         return TokenInfo {
             token: self.token.clone(),
@@ -44,15 +59,43 @@ impl<'a> Lexer<'a> {
                 _ => Token::Colon,
             },
             '.' => Token::Dot,
-            '+' => Token::Plus,
-            '-' => Token::Minus,
-            '*' => Token::Asterisk,
+            '+' => match self.cursor.peek() {
+                '=' => {
+                    self.cursor.next();
+                    Token::PlusAssign
+                }
+                _ => Token::Plus,
+            },
+            '-' => match self.cursor.peek() {
+                '=' => {
+                    self.cursor.next();
+                    Token::MinusAssign
+                }
+                _ => Token::Minus,
+            },
+            '*' => match self.cursor.peek() {
+                '=' => {
+                    self.cursor.next();
+                    Token::AsteriskAssign
+                }
+                _ => Token::Asterisk,
+            },
             '/' => match self.cursor.peek() {
                 '/' => return self.read_line_comment(),
                 '*' => return self.read_block_comment(),
+                '=' => {
+                    self.cursor.next();
+                    Token::SlashAssign
+                }
                 _ => Token::Slash,
             },
-            '%' => Token::Percent,
+            '%' => match self.cursor.peek() {
+                '=' => {
+                    self.cursor.next();
+                    Token::PercentAssign
+                }
+                _ => Token::Percent,
+            },
             '!' => match self.cursor.peek() {
                 '=' => {
                     self.cursor.next();
@@ -65,10 +108,57 @@ impl<'a> Lexer<'a> {
                     self.cursor.next();
                     Token::Equal
                 }
+                '>' => {
+                    self.cursor.next();
+                    Token::Arrow
+                }
                 _ => Token::Assign,
             },
-            '<' => Token::LessThan,
-            '>' => Token::GreaterThan,
+            '<' => match self.cursor.peek() {
+                '=' => {
+                    self.cursor.next();
+                    Token::LessThanOrEqual
+                }
+                '<' => {
+                    self.cursor.next();
+                    Token::ShiftLeft
+                }
+                _ => Token::LessThan,
+            },
+            '>' => match self.cursor.peek() {
+                '=' => {
+                    self.cursor.next();
+                    Token::GreaterThanOrEqual
+                }
+                '>' => {
+                    self.cursor.next();
+                    Token::ShiftRight
+                }
+                _ => Token::GreaterThan,
+            },
+            '&' => match self.cursor.peek() {
+                '&' => {
+                    self.cursor.next();
+                    Token::And
+                }
+                _ => Token::Ampersand,
+            },
+            '|' => match self.cursor.peek() {
+                '|' => {
+                    self.cursor.next();
+                    Token::Or
+                }
+                _ => Token::Pipe,
+            },
+            '?' => match self.cursor.peek() {
+                '?' => {
+                    self.cursor.next();
+                    Token::Coalesce
+                }
+                _ => Token::QuestionMark,
+            },
+            '^' => Token::Caret,
+            '~' => Token::Tilde,
 
             ',' => Token::Comma,
             ';' => Token::Semicolon,
@@ -80,6 +170,7 @@ impl<'a> Lexer<'a> {
             '}' => Token::RightBrace,
 
             '"' => return self.read_string(),
+            'r' if matches!(self.cursor.peek(), '"' | '#') => return self.read_raw_string(),
             _ => {
                 if is_letter(self.cursor.current()) {
                     return self.read_identifier();
@@ -121,58 +212,6 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn read_number(&mut self) -> Result<TokenInfo> {
-        let mut number = String::new();
-
-        while is_digit(self.cursor.current()) {
-            number.push(self.cursor.current());
-            self.cursor.next();
-        }
-
-        if self.cursor.current() == '.' {
-            number.push(self.cursor.current());
-            self.cursor.next();
-
-            while is_digit(self.cursor.current()) {
-                number.push(self.cursor.current());
-                self.cursor.next();
-            }
-
-            self.token = Token::Float(number);
-        } else {
-            self.token = Token::Integer(number);
-        }
-
-        self.position.end = self.cursor.position().back(1);
-        return Ok(self.current());
-    }
-
-    fn read_string(&mut self) -> Result<TokenInfo> {
-        self.cursor.next(); // skip "
-
-        let mut string = String::new();
-
-        loop {
-            match self.cursor.current() {
-                '"' => break,
-                '\0' => {
-                    return Err(Error::UnexpectedEOF {
-                        position: self.cursor.position(),
-                    });
-                }
-                _ => {
-                    string.push(self.cursor.current());
-                    self.cursor.next();
-                }
-            }
-        }
-        self.position.end = self.cursor.position();
-        self.token = Token::String(string);
-
-        self.cursor.next(); // "
-        return Ok(self.current());
-    }
-
     fn read_line_comment(&mut self) -> Result<TokenInfo> {
         self.cursor.skip(2);
 
@@ -0,0 +1,128 @@
+use super::*;
+
+impl<'a> Lexer<'a> {
+    /// Reads an integer or float literal. Integers can be written in hexadecimal (`0xFF`),
+    /// octal (`0o17`) or binary (`0b1010`), floats can have an exponent (`6.02e23`)
+    /// and digits of both can be separated by single underscores (`1_000_000`).
+    /// The token keeps the literal as it is spelled in the source.
+    pub(super) fn read_number(&mut self) -> Result<TokenInfo> {
+        let position = self.cursor.position();
+        let mut number = String::new();
+        let mut float = false;
+
+        // the index of a tuple element is always an integer: `t.0.1`
+        let index = self.token == Token::Dot;
+
+        let radix = match (self.cursor.current(), self.cursor.peek()) {
+            ('0', 'x') => 16,
+            ('0', 'o') => 8,
+            ('0', 'b') => 2,
+            _ => 10,
+        };
+
+        if radix != 10 {
+            number.push(self.cursor.current());
+            number.push(self.cursor.peek());
+            self.cursor.skip(2);
+
+            if !matches!(self.read_digits(&mut number, radix), Some(1..)) {
+                return self.invalid_number(number, position);
+            }
+        } else {
+            if self.read_digits(&mut number, 10).is_none() {
+                return self.invalid_number(number, position);
+            }
+
+            if self.cursor.current() == '.' && !index {
+                float = true;
+                number.push('.');
+                self.cursor.next();
+
+                if self.read_digits(&mut number, 10).is_none() {
+                    return self.invalid_number(number, position);
+                }
+            }
+
+            if matches!(self.cursor.current(), 'e' | 'E') && !index {
+                float = true;
+                number.push(self.cursor.current());
+                self.cursor.next();
+
+                if matches!(self.cursor.current(), '+' | '-') {
+                    number.push(self.cursor.current());
+                    self.cursor.next();
+                }
+                if !matches!(self.read_digits(&mut number, 10), Some(1..)) {
+                    return self.invalid_number(number, position);
+                }
+            }
+        }
+
+        // a number must not run into a name or a digit that is invalid for its radix
+        if is_letter(self.cursor.current()) || is_digit(self.cursor.current()) {
+            return self.invalid_number(number, position);
+        }
+
+        self.position.end = self.cursor.position().back(1);
+        if float {
+            self.token = Token::Float(number);
+        } else {
+            self.token = Token::Integer(number);
+        }
+        return Ok(self.current());
+    }
+
+    /// Reads digits of the radix and underscores into `number` and returns the number of digits,
+    /// or `None` if an underscore is not followed by a digit, like in `1_` or `1__2`.
+    fn read_digits(&mut self, number: &mut String, radix: u32) -> Option<usize> {
+        let mut digits = 0;
+        while self.cursor.current().is_digit(radix) || self.cursor.current() == '_' {
+            if self.cursor.current() != '_' {
+                digits += 1;
+            } else if !self.cursor.peek().is_digit(radix) {
+                return None;
+            }
+            number.push(self.cursor.current());
+            self.cursor.next();
+        }
+        return Some(digits);
+    }
+
+    /// Reports the malformed literal including the rest of its letters and digits.
+    fn invalid_number(&mut self, mut number: String, position: Position) -> Result<TokenInfo> {
+        while is_letter(self.cursor.current()) || is_digit(self.cursor.current()) {
+            number.push(self.cursor.current());
+            self.cursor.next();
+        }
+        return Err(Error::InvalidNumber { number, position });
+    }
+}
+
+/// Returns the value of an integer literal spelled as in the source,
+/// or `None` if it does not fit into an `int`.
+pub fn integer_value(literal: &str) -> Option<i64> {
+    return i64::try_from(magnitude(literal)?).ok();
+}
+
+/// Returns the value of an integer literal with a minus in front of it, which fits into an `int`
+/// for `9223372036854775808` although the literal alone does not.
+pub fn negated_integer_value(literal: &str) -> Option<i64> {
+    return 0i64.checked_sub_unsigned(magnitude(literal)?);
+}
+
+/// Returns the value of an integer literal without its sign, which may not fit into an `int`.
+fn magnitude(literal: &str) -> Option<u64> {
+    let literal = literal.replace('_', "");
+    let (radix, digits) = match literal.get(..2) {
+        Some("0x") => (16, &literal[2..]),
+        Some("0o") => (8, &literal[2..]),
+        Some("0b") => (2, &literal[2..]),
+        _ => (10, &literal[..]),
+    };
+    return u64::from_str_radix(digits, radix).ok();
+}
+
+/// Returns the value of a float literal spelled as in the source.
+pub fn float_value(literal: &str) -> Option<f64> {
+    return literal.replace('_', "").parse().ok();
+}
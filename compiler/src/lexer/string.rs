@@ -0,0 +1,226 @@
+use super::*;
+
+use crate::token::StringPart;
+
+impl<'a> Lexer<'a> {
+    /// Reads a string literal, which may span multiple lines and contain escape sequences
+    /// and embedded `{expression}`s. The token keeps the literal as it is spelled in the source.
+    pub(super) fn read_string(&mut self) -> Result<TokenInfo> {
+        self.cursor.next(); // skip "
+
+        let mut parts = vec![];
+        let mut string = String::new();
+
+        loop {
+            match self.cursor.current() {
+                '"' => break,
+                '\\' => string.push_str(&self.read_escape()?),
+                '{' => {
+                    if !string.is_empty() {
+                        parts.push(StringPart::Literal(std::mem::take(&mut string)));
+                    }
+                    parts.push(self.read_string_expression()?);
+                }
+                '\0' => {
+                    return Err(Error::UnexpectedEOF {
+                        position: self.cursor.position(),
+                    });
+                }
+                _ => {
+                    string.push(self.cursor.current());
+                    self.cursor.next();
+                }
+            }
+        }
+        self.position.end = self.cursor.position();
+        if parts.is_empty() {
+            self.token = Token::String(format!("\"{}\"", string));
+        } else {
+            if !string.is_empty() {
+                parts.push(StringPart::Literal(string));
+            }
+            self.token = Token::InterpolatedString(parts);
+        }
+
+        self.cursor.next(); // "
+        return Ok(self.current());
+    }
+
+    /// Reads a raw string literal `r"..."` or `r#"..."#` without escape sequences or interpolation.
+    /// Any number of `#` can be used, so that the content can contain `"#`.
+    pub(super) fn read_raw_string(&mut self) -> Result<TokenInfo> {
+        let mut string = String::from('r');
+        self.cursor.next(); // skip r
+
+        let mut hashes = 0;
+        while self.cursor.current() == '#' {
+            hashes += 1;
+            string.push('#');
+            self.cursor.next();
+        }
+
+        if self.cursor.current() != '"' {
+            self.position.end = self.cursor.position().back(1);
+            self.token = Token::Illegal;
+            return Ok(self.current());
+        }
+        string.push('"');
+        self.cursor.next();
+
+        loop {
+            match self.cursor.current() {
+                '"' if (0..hashes).all(|i| self.cursor.peek_nth(i) == '#') => break,
+                '\0' => {
+                    return Err(Error::UnexpectedEOF {
+                        position: self.cursor.position(),
+                    });
+                }
+                c => {
+                    string.push(c);
+                    self.cursor.next();
+                }
+            }
+        }
+        string.push('"');
+        string.push_str(&"#".repeat(hashes));
+
+        self.position.end = self.cursor.position().forward(hashes);
+        self.token = Token::String(string);
+
+        self.cursor.skip(1 + hashes);
+        return Ok(self.current());
+    }
+
+    /// Validates the escape sequence at the cursor and returns its spelling.
+    fn read_escape(&mut self) -> Result<String> {
+        let position = self.cursor.position();
+        let mut escape = String::from('\\');
+        self.cursor.next(); // skip \
+
+        let c = self.cursor.current();
+        match c {
+            'n' | 'r' | 't' | '0' | '\\' | '"' | '{' | '}' => {
+                escape.push(c);
+                self.cursor.next();
+            }
+            'u' => {
+                escape.push(c);
+                self.cursor.next();
+
+                if self.cursor.current() != '{' {
+                    return Err(Error::InvalidEscape { escape, position });
+                }
+                escape.push('{');
+                self.cursor.next();
+
+                let mut hex = String::new();
+                while self.cursor.current().is_ascii_hexdigit() {
+                    hex.push(self.cursor.current());
+                    self.cursor.next();
+                }
+                escape.push_str(&hex);
+
+                if self.cursor.current() != '}' {
+                    return Err(Error::InvalidEscape { escape, position });
+                }
+                escape.push('}');
+                self.cursor.next();
+
+                if hex.len() > 6 || unicode_char(&hex).is_none() {
+                    return Err(Error::InvalidEscape { escape, position });
+                }
+            }
+            '\0' => {
+                return Err(Error::UnexpectedEOF {
+                    position: self.cursor.position(),
+                });
+            }
+            _ => {
+                escape.push(c);
+                return Err(Error::InvalidEscape { escape, position });
+            }
+        }
+
+        return Ok(escape);
+    }
+
+    /// Reads the source of an embedded `{expression}` up to its matching `}`.
+    fn read_string_expression(&mut self) -> Result<StringPart> {
+        self.cursor.next(); // skip {
+
+        let position = self.cursor.position();
+        let mut source = String::new();
+        let mut depth = 0;
+        let mut in_string = false;
+
+        loop {
+            match self.cursor.current() {
+                '}' if depth == 0 && !in_string => break,
+                '\0' => {
+                    return Err(Error::UnexpectedEOF {
+                        position: self.cursor.position(),
+                    });
+                }
+                c => {
+                    match c {
+                        '"' => in_string = !in_string,
+                        '{' if !in_string => depth += 1,
+                        '}' if !in_string => depth -= 1,
+                        '\\' if in_string => {
+                            // keep an escaped quote from ending the nested string
+                            source.push(c);
+                            self.cursor.next();
+                        }
+                        _ => {}
+                    }
+                    source.push(self.cursor.current());
+                    self.cursor.next();
+                }
+            }
+        }
+
+        self.cursor.next(); // skip }
+        return Ok(StringPart::Expression { source, position });
+    }
+}
+
+/// Returns the value of a string literal spelled as in the source, including its delimiters.
+pub fn string_value(literal: &str) -> String {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return raw[hashes + 1..raw.len() - hashes - 1].into();
+    }
+    return unescape(&literal[1..literal.len() - 1]);
+}
+
+/// Replaces the escape sequences of a string, which have already been validated by the lexer.
+pub fn unescape(string: &str) -> String {
+    let mut result = String::new();
+
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('0') => result.push('\0'),
+            Some('u') => {
+                let hex: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+                result.extend(unicode_char(&hex));
+            }
+            Some(c) => result.push(c),
+            None => result.push('\\'),
+        }
+    }
+
+    return result;
+}
+
+fn unicode_char(hex: &str) -> Option<char> {
+    return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+}
@@ -2,6 +2,8 @@ use super::*;
 
 use pretty_assertions::assert_eq;
 
+use crate::token::StringPart;
+
 use Token::*;
 
 #[test]
@@ -92,6 +94,194 @@ true
     assert_tokens_equal(source, want);
 }
 
+#[test]
+fn lex_logical_expressions() {
+    let source = "a >= 0 && a <= 10 || !b";
+
+    let want = &[
+        Identifier("a".into()),
+        GreaterThanOrEqual,
+        Integer("0".into()),
+        And,
+        Identifier("a".into()),
+        LessThanOrEqual,
+        Integer("10".into()),
+        Or,
+        ExclamationMark,
+        Identifier("b".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_bitwise_expressions() {
+    let source = "~a & 0xFF | b ^ c << 2 >> 1";
+
+    let want = &[
+        Tilde,
+        Identifier("a".into()),
+        Ampersand,
+        Integer("0xFF".into()),
+        Pipe,
+        Identifier("b".into()),
+        Caret,
+        Identifier("c".into()),
+        ShiftLeft,
+        Integer("2".into()),
+        ShiftRight,
+        Integer("1".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_compound_assignments() {
+    let source = "a += 1; a -= 2; a *= 3; a /= 4; a %= 5";
+
+    let want = &[
+        Identifier("a".into()),
+        PlusAssign,
+        Integer("1".into()),
+        Semicolon,
+        Identifier("a".into()),
+        MinusAssign,
+        Integer("2".into()),
+        Semicolon,
+        Identifier("a".into()),
+        AsteriskAssign,
+        Integer("3".into()),
+        Semicolon,
+        Identifier("a".into()),
+        SlashAssign,
+        Integer("4".into()),
+        Semicolon,
+        Identifier("a".into()),
+        PercentAssign,
+        Integer("5".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_tuple_expressions() {
+    let source = "(a, b) := t.0.1";
+
+    let want = &[
+        LeftParenthesis,
+        Identifier("a".into()),
+        Comma,
+        Identifier("b".into()),
+        RightParenthesis,
+        Declare,
+        Identifier("t".into()),
+        Dot,
+        Integer("0".into()),
+        Dot,
+        Integer("1".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_struct_declarations() {
+    let source = "type P = { x: int }
+p.x = P{ x: 1 }.x";
+
+    let want = &[
+        Type,
+        Identifier("P".into()),
+        Assign,
+        LeftBrace,
+        Identifier("x".into()),
+        Colon,
+        Identifier("int".into()),
+        RightBrace,
+        Identifier("p".into()),
+        Dot,
+        Identifier("x".into()),
+        Assign,
+        Identifier("P".into()),
+        LeftBrace,
+        Identifier("x".into()),
+        Colon,
+        Integer("1".into()),
+        RightBrace,
+        Dot,
+        Identifier("x".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_enum_declarations() {
+    let source = "enum E { A(int), B }
+match e { A(x) => x, B => 0 }";
+
+    let want = &[
+        Enum,
+        Identifier("E".into()),
+        LeftBrace,
+        Identifier("A".into()),
+        LeftParenthesis,
+        Identifier("int".into()),
+        RightParenthesis,
+        Comma,
+        Identifier("B".into()),
+        RightBrace,
+        Match,
+        Identifier("e".into()),
+        LeftBrace,
+        Identifier("A".into()),
+        LeftParenthesis,
+        Identifier("x".into()),
+        RightParenthesis,
+        Arrow,
+        Identifier("x".into()),
+        Comma,
+        Identifier("B".into()),
+        Arrow,
+        Integer("0".into()),
+        RightBrace,
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_optionals() {
+    let source = "fn f(x: int?): int { return x ?? 0 }
+y := none";
+
+    let want = &[
+        Function,
+        Identifier("f".into()),
+        LeftParenthesis,
+        Identifier("x".into()),
+        Colon,
+        Identifier("int".into()),
+        QuestionMark,
+        RightParenthesis,
+        Colon,
+        Identifier("int".into()),
+        LeftBrace,
+        Return,
+        Identifier("x".into()),
+        Coalesce,
+        Integer("0".into()),
+        RightBrace,
+        Identifier("y".into()),
+        Declare,
+        None,
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
 #[test]
 fn lex_statements() {
     let source = r#"
@@ -134,7 +324,77 @@ fn test(): string {
         LeftBrace,
         // return 42
         Return,
-        String("Hello World".into()),
+        String(r#""Hello World""#.into()),
+        // }
+        RightBrace,
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_if_statements() {
+    let source = r#"
+if a > 9000 {
+} else if a < 0 {
+} else {
+}
+"#;
+
+    let want = &[
+        // if a > 9000 {
+        If,
+        Identifier("a".into()),
+        GreaterThan,
+        Integer("9000".into()),
+        LeftBrace,
+        // } else if a < 0 {
+        RightBrace,
+        Else,
+        If,
+        Identifier("a".into()),
+        LessThan,
+        Integer("0".into()),
+        LeftBrace,
+        // } else {
+        RightBrace,
+        Else,
+        LeftBrace,
+        // }
+        RightBrace,
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_loop_statements() {
+    let source = r#"
+while true {
+  break
+}
+for c in "abc" {
+  continue
+}
+"#;
+
+    let want = &[
+        // while true {
+        While,
+        True,
+        LeftBrace,
+        // break
+        Break,
+        // }
+        RightBrace,
+        // for c in "abc" {
+        For,
+        Identifier("c".into()),
+        In,
+        String(r#""abc""#.into()),
+        LeftBrace,
+        // continue
+        Continue,
         // }
         RightBrace,
     ];
@@ -142,6 +402,208 @@ fn test(): string {
     assert_tokens_equal(source, want);
 }
 
+#[test]
+fn lex_interpolated_strings() {
+    let source = r#"
+"It's only {a}."
+"{f("}")}{ {b} }"
+"#;
+
+    let want = &[
+        InterpolatedString(vec![
+            StringPart::Literal("It's only ".into()),
+            StringPart::Expression {
+                source: "a".into(),
+                position: Position::from((13, 2, 1)),
+            },
+            StringPart::Literal(".".into()),
+        ]),
+        InterpolatedString(vec![
+            StringPart::Expression {
+                source: "f(\"}\")".into(),
+                position: Position::from((20, 3, 18)),
+            },
+            StringPart::Expression {
+                source: " {b} ".into(),
+                position: Position::from((28, 3, 18)),
+            },
+        ]),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_strings() {
+    let source = r####"
+"Tab\t \"quoted\" \\ \{ \u{1F600}"
+r"C:\path\{name}"
+r##"a "# b"##
+"multi
+line"
+"####;
+
+    let want = &[
+        String(r#""Tab\t \"quoted\" \\ \{ \u{1F600}""#.into()),
+        String(r#"r"C:\path\{name}""#.into()),
+        String(r###"r##"a "# b"##"###.into()),
+        String("\"multi\nline\"".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn lex_numbers() {
+    let source = "0xFF 0o17 0b1010 1_000_000 6.02e23 1e-9 2.5E+3 1_0.0_1 0x_dead_BEEF";
+
+    let want = &[
+        Integer("0xFF".into()),
+        Integer("0o17".into()),
+        Integer("0b1010".into()),
+        Integer("1_000_000".into()),
+        Float("6.02e23".into()),
+        Float("1e-9".into()),
+        Float("2.5E+3".into()),
+        Float("1_0.0_1".into()),
+        Integer("0x_dead_BEEF".into()),
+    ];
+
+    assert_tokens_equal(source, want);
+}
+
+#[test]
+fn number_values() {
+    let integers = &[
+        ("0xFF", Some(255)),
+        ("0o17", Some(15)),
+        ("0b1010", Some(10)),
+        ("1_000_000", Some(1_000_000)),
+        ("0x7fff_ffff_ffff_ffff", Some(i64::MAX)),
+        ("9223372036854775808", Option::None),
+    ];
+    for case in integers {
+        assert_eq!(case.1, integer_value(case.0), "{}", case.0);
+    }
+
+    let negated = &[
+        ("1", Some(-1)),
+        ("9223372036854775808", Some(i64::MIN)),
+        ("0x8000_0000_0000_0000", Some(i64::MIN)),
+        ("9223372036854775809", Option::None),
+        ("18446744073709551616", Option::None),
+    ];
+    for case in negated {
+        assert_eq!(case.1, negated_integer_value(case.0), "{}", case.0);
+    }
+
+    let floats = &[("6.02e23", 6.02e23), ("1e-9", 1e-9), ("1_0.0_1", 10.01)];
+    for case in floats {
+        assert_eq!(Some(case.1), float_value(case.0), "{}", case.0);
+    }
+}
+
+#[test]
+fn lex_errors() {
+    let cases = &[
+        (
+            r#""a\qb""#,
+            Error::InvalidEscape {
+                escape: r"\q".into(),
+                position: Position::from((2, 1, 0)),
+            },
+        ),
+        (
+            r#""\u{110000}""#,
+            Error::InvalidEscape {
+                escape: r"\u{110000}".into(),
+                position: Position::from((1, 1, 0)),
+            },
+        ),
+        (
+            r#""\u1F600""#,
+            Error::InvalidEscape {
+                escape: r"\u".into(),
+                position: Position::from((1, 1, 0)),
+            },
+        ),
+        (
+            "0b102",
+            Error::InvalidNumber {
+                number: "0b102".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "0x",
+            Error::InvalidNumber {
+                number: "0x".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "1e+",
+            Error::InvalidNumber {
+                number: "1e+".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "1_",
+            Error::InvalidNumber {
+                number: "1_".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "1__2",
+            Error::InvalidNumber {
+                number: "1__2".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "0x_",
+            Error::InvalidNumber {
+                number: "0x_".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "1_.5",
+            Error::InvalidNumber {
+                number: "1_".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "1.5_e3",
+            Error::InvalidNumber {
+                number: "1.5_e3".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            "42abc",
+            Error::InvalidNumber {
+                number: "42abc".into(),
+                position: Position::from((0, 1, 0)),
+            },
+        ),
+        (
+            r##"r#"open""##,
+            Error::UnexpectedEOF {
+                position: Position::from((8, 1, 0)),
+            },
+        ),
+    ];
+
+    for case in cases {
+        let mut lexer = Lexer::new(case.0);
+        assert_eq!(Err(case.1.clone()), lexer.next(), "{}", case.0);
+    }
+}
+
 fn assert_tokens_equal(source: &str, want: &[Token]) {
     let mut lexer = Lexer::new(source);
     let mut got = vec![];
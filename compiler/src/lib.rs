@@ -1,11 +1,19 @@
 #[path = "ast/ast.rs"]
 pub mod ast;
+#[path = "checker/checker.rs"]
+pub mod checker;
+#[path = "codegen/codegen.rs"]
+pub mod codegen;
 #[path = "fmt/fmt.rs"]
 pub mod fmt;
+#[path = "interp/interp.rs"]
+pub mod interp;
 #[path = "lexer/lexer.rs"]
 pub mod lexer;
 #[path = "parser/parser.rs"]
 pub mod parser;
+#[path = "resolver/resolver.rs"]
+pub mod resolver;
 #[path = "token/token.rs"]
 pub mod token;
 
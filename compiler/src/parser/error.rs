@@ -12,6 +12,9 @@ pub enum Error {
         position: Span,
     },
     UnexpectedEOF,
+    InvalidAssignmentTarget {
+        position: Span,
+    },
 }
 
 impl fmt::Display for Error {
@@ -28,6 +31,9 @@ impl fmt::Display for Error {
                 got, position, want
             ),
             Error::UnexpectedEOF => write!(f, "unexpected EOF"),
+            Error::InvalidAssignmentTarget { position } => {
+                write!(f, "invalid assignment target @ {}", position)
+            }
         }
     }
 }
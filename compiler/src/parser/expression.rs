@@ -1,6 +1,7 @@
 use super::*;
 
 use crate::ast::{ExpressionKind::*, Type};
+use crate::token::StringPart;
 
 impl<'a> Parser<'a> {
     /// Parses an expression using the Pratt parsing algorithm
@@ -15,8 +16,18 @@ impl<'a> Parser<'a> {
         return Ok(expression);
     }
 
+    /// Parses the condition of `if` or `while` or the iterable of `for`, which are followed
+    /// by a block that must not be taken for the fields of a struct literal.
+    pub(super) fn parse_condition(&mut self) -> Result<ast::Expression> {
+        return self
+            .with_struct_literals(false, |parser| parser.parse_expression(Precedence::Lowest));
+    }
+
     fn parse_prefix(&mut self) -> Result<ast::Expression> {
         let expression = match &self.current.token {
+            Token::Identifier(_) if self.is_struct_literal() => {
+                return self.parse_struct_expression()
+            }
             Token::Identifier(id) => ast::Expression {
                 kind: Identifier(id.into()),
                 typ: Type::Unknown,
@@ -42,8 +53,21 @@ impl<'a> Parser<'a> {
                 typ: Type::Boolean,
                 position: self.current.position(),
             },
+            Token::None => ast::Expression {
+                kind: NoneLiteral,
+                typ: Type::Unknown,
+                position: self.current.position(),
+            },
+            Token::InterpolatedString(_) => return self.parse_interpolated_string(),
             Token::LeftParenthesis => return self.parse_grouped_expression(),
-            Token::Minus | Token::ExclamationMark => return self.parse_prefix_expression(),
+            Token::LeftBracket => return self.parse_list_expression(),
+            // blocks are statements, so a brace in an expression always starts a map
+            Token::LeftBrace => return self.parse_map_expression(),
+            Token::Match => return self.parse_match_expression(),
+            Token::Function => return self.parse_lambda_expression(),
+            Token::Minus | Token::ExclamationMark | Token::Tilde => {
+                return self.parse_prefix_expression()
+            }
 
             _ => {
                 return Err(Error::UnexpectedToken {
@@ -63,32 +87,220 @@ impl<'a> Parser<'a> {
             Token::Plus
             | Token::Minus
             | Token::Asterisk
+            | Token::Slash
             | Token::Percent
             | Token::Equal
             | Token::NotEqual
             | Token::LessThan
-            | Token::GreaterThan => self.parse_infix_expression(left),
+            | Token::GreaterThan
+            | Token::LessThanOrEqual
+            | Token::GreaterThanOrEqual
+            | Token::And
+            | Token::Or
+            | Token::Ampersand
+            | Token::Pipe
+            | Token::Caret
+            | Token::ShiftLeft
+            | Token::ShiftRight
+            | Token::Coalesce => self.parse_infix_expression(left),
+            Token::QuestionMark => self.parse_postfix_expression(left),
+            Token::LeftParenthesis => self.parse_call_expression(left),
+            Token::Dot => self.parse_member_expression(left),
+            Token::LeftBracket => self.parse_index_expression(left),
 
             _ => Ok(left),
         };
     }
 
+    fn parse_interpolated_string(&mut self) -> Result<ast::Expression> {
+        let parts = match self.current.token() {
+            Token::InterpolatedString(parts) => parts,
+            _ => panic!("Expression is not an interpolated string."),
+        };
+
+        let mut result = vec![];
+        for part in parts {
+            match part {
+                StringPart::Literal(literal) => result.push(ast::StringPart::Literal(literal)),
+                StringPart::Expression { source, position } => {
+                    let mut parser = Parser::with_position(&source, position);
+                    let expression = parser.parse_expression(Precedence::Lowest)?;
+                    parser.expect(Token::EOF)?;
+
+                    result.push(ast::StringPart::Expression(expression));
+                }
+            }
+        }
+
+        let expression = ast::Expression {
+            kind: InterpolatedString(result),
+            typ: Type::String,
+            position: self.current.position(),
+        };
+
+        self.next();
+        return Ok(expression);
+    }
+
     fn parse_grouped_expression(&mut self) -> Result<ast::Expression> {
         let mut position = self.current.position();
 
         self.consume(Token::LeftParenthesis)?;
 
-        let mut expression = self.parse_expression(Precedence::Lowest)?;
+        let mut elements = vec![];
+        while self.current.token != Token::RightParenthesis {
+            elements.push(self.with_struct_literals(true, |parser| {
+                parser.parse_expression(Precedence::Lowest)
+            })?);
 
-        // TODO: parse tuple literal if there is a comma
+            if self.current.token == Token::RightParenthesis {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+        // a trailing comma makes a single expression a tuple: `(a,)`
+        let tuple = elements.len() != 1 || self.previous.token == Token::Comma;
 
         self.consume(Token::RightParenthesis)?;
 
         position.end = self.previous.position.end();
+        if tuple {
+            return Ok(ast::Expression {
+                kind: Tuple(elements),
+                typ: Type::Unknown,
+                position,
+            });
+        }
+
+        let mut expression = elements.remove(0);
         expression.position = position;
         return Ok(expression);
     }
 
+    fn parse_list_expression(&mut self) -> Result<ast::Expression> {
+        let mut position = self.current.position();
+
+        self.consume(Token::LeftBracket)?;
+
+        let mut elements = vec![];
+        while self.current.token != Token::RightBracket {
+            elements.push(self.with_struct_literals(true, |parser| {
+                parser.parse_expression(Precedence::Lowest)
+            })?);
+
+            if self.current.token == Token::RightBracket {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+
+        self.consume(Token::RightBracket)?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: List(elements),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_map_expression(&mut self) -> Result<ast::Expression> {
+        let mut position = self.current.position();
+
+        self.consume(Token::LeftBrace)?;
+
+        let mut entries = vec![];
+        while self.current.token != Token::RightBrace {
+            let (key, value) = self.with_struct_literals(true, |parser| {
+                let key = parser.parse_expression(Precedence::Lowest)?;
+                parser.consume(Token::Colon)?;
+                let value = parser.parse_expression(Precedence::Lowest)?;
+                return Ok((key, value));
+            })?;
+            entries.push(ast::MapEntry { key, value });
+
+            if self.current.token == Token::RightBrace {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+
+        self.consume(Token::RightBrace)?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: Map(entries),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_match_expression(&mut self) -> Result<ast::Expression> {
+        let mut position = self.current.position();
+
+        self.consume(Token::Match)?;
+
+        let subject = Box::new(self.parse_condition()?);
+
+        self.consume(Token::LeftBrace)?;
+
+        let mut arms = vec![];
+        while self.current.token != Token::RightBrace {
+            arms.push(self.parse_match_arm()?);
+
+            if self.current.token == Token::RightBrace {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+
+        self.consume(Token::RightBrace)?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: Match(ast::MatchExpression { subject, arms }),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_lambda_expression(&mut self) -> Result<ast::Expression> {
+        let mut position = self.current.position();
+
+        let typ = self.parse_function_type()?;
+        // struct literals are allowed in the body even if the lambda is part of a condition
+        let body = self.with_struct_literals(true, |parser| parser.parse_block_statement())?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: Lambda(ast::LambdaExpression { typ, body }),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_match_arm(&mut self) -> Result<ast::MatchArm> {
+        let mut position = self.current.position();
+
+        let pattern = self.parse_pattern()?;
+
+        self.consume(Token::Arrow)?;
+
+        let body =
+            self.with_struct_literals(true, |parser| parser.parse_expression(Precedence::Lowest))?;
+
+        position.end = body.position.end();
+        return Ok(ast::MatchArm {
+            pattern,
+            body,
+            position,
+        });
+    }
+
     fn parse_prefix_expression(&mut self) -> Result<ast::Expression> {
         let mut position = self.current.position();
 
@@ -112,7 +324,12 @@ impl<'a> Parser<'a> {
         let operator = self.current.token.clone();
         self.next(); // advance past operator
 
-        let right = self.parse_expression(Precedence::of(&operator))?;
+        let precedence = match operator {
+            // `??` is right-associative, so `a ?? b ?? c` is `a ?? (b ?? c)`
+            Token::Coalesce => Precedence::LessGreater,
+            _ => Precedence::of(&operator),
+        };
+        let right = self.parse_expression(precedence)?;
 
         position.end = right.position.end();
         return Ok(ast::Expression {
@@ -125,4 +342,146 @@ impl<'a> Parser<'a> {
             position,
         });
     }
+
+    fn parse_postfix_expression(&mut self, left: ast::Expression) -> Result<ast::Expression> {
+        let mut position = left.position.clone();
+
+        let operator = self.current.token.clone();
+        position.end = self.current.position.end();
+        self.next(); // advance past operator
+
+        return Ok(ast::Expression {
+            kind: Postfix(ast::PostfixExpression {
+                left: Box::new(left),
+                operator,
+            }),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_call_expression(&mut self, callee: ast::Expression) -> Result<ast::Expression> {
+        let mut position = callee.position.clone();
+
+        self.consume(Token::LeftParenthesis)?;
+
+        let mut arguments = vec![];
+        while self.current.token != Token::RightParenthesis {
+            arguments.push(self.with_struct_literals(true, |parser| {
+                parser.parse_expression(Precedence::Lowest)
+            })?);
+
+            if self.current.token == Token::RightParenthesis {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+
+        self.consume(Token::RightParenthesis)?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: Call(ast::CallExpression {
+                callee: Box::new(callee),
+                arguments,
+            }),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_member_expression(&mut self, object: ast::Expression) -> Result<ast::Expression> {
+        let mut position = object.position.clone();
+
+        self.consume(Token::Dot)?;
+
+        let name = match &self.current.token {
+            Token::Identifier(name) => name.clone(),
+            // tuple elements are accessed by their index
+            Token::Integer(index) if index.chars().all(|c| c.is_ascii_digit()) => index.clone(),
+            _ => {
+                return Err(Error::UnexpectedToken {
+                    want: "member name".into(),
+                    got: self.current.token.to_string(),
+                    position: self.current.position(),
+                })
+            }
+        };
+        self.next();
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: Member(ast::MemberExpression {
+                object: Box::new(object),
+                name,
+            }),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    fn parse_index_expression(&mut self, object: ast::Expression) -> Result<ast::Expression> {
+        let mut position = object.position.clone();
+
+        self.consume(Token::LeftBracket)?;
+
+        let index =
+            self.with_struct_literals(true, |parser| parser.parse_expression(Precedence::Lowest))?;
+
+        self.consume(Token::RightBracket)?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            kind: Index(ast::IndexExpression {
+                object: Box::new(object),
+                index: Box::new(index),
+            }),
+            typ: Type::Unknown,
+            position,
+        });
+    }
+
+    /// `Name{` on one line starts a struct literal, unless a block follows the expression.
+    fn is_struct_literal(&self) -> bool {
+        return self.struct_literals
+            && self.next.token == Token::LeftBrace
+            && self.next.position.start.line == self.current.position.end.line;
+    }
+
+    fn parse_struct_expression(&mut self) -> Result<ast::Expression> {
+        let mut position = self.current.position();
+
+        let name = self.get_identifier_name()?;
+        self.next();
+
+        self.consume(Token::LeftBrace)?;
+
+        let mut fields = vec![];
+        while self.current.token != Token::RightBrace {
+            let name = self.get_identifier_name()?;
+            self.next();
+
+            self.consume(Token::Colon)?;
+
+            let value = self
+                .with_struct_literals(true, |parser| parser.parse_expression(Precedence::Lowest))?;
+            fields.push(ast::FieldValue { name, value });
+
+            if self.current.token == Token::RightBrace {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+
+        self.consume(Token::RightBrace)?;
+
+        position.end = self.previous.position.end();
+        return Ok(ast::Expression {
+            typ: Type::Name(name.clone()),
+            kind: Struct(ast::StructExpression { name, fields }),
+            position,
+        });
+    }
 }
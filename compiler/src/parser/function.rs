@@ -11,7 +11,13 @@ impl<'a> Parser<'a> {
 
         let mut parameters = vec![];
         while self.current.token != Token::RightParenthesis {
-            parameters.push(self.parse_parameter()?)
+            parameters.push(self.parse_parameter()?);
+
+            if self.current.token == Token::RightParenthesis {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
         }
 
         self.consume(Token::RightParenthesis)?;
@@ -41,4 +47,4 @@ impl<'a> Parser<'a> {
 
         return Ok(ast::Return { value });
     }
-}
\ No newline at end of file
+}
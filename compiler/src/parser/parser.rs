@@ -2,7 +2,7 @@ mod error;
 mod precedence;
 
 use error::*;
-use precedence::*;
+pub use precedence::*;
 
 mod expression;
 mod function;
@@ -11,11 +11,15 @@ mod types;
 
 use crate::ast;
 use crate::lexer::Lexer;
-use crate::token::{Token, TokenInfo};
+use crate::token::{Position, Token, TokenInfo};
 
 #[derive(Clone, Debug)]
 pub struct Parser<'a> {
     lexer: Lexer<'a>,
+    /// First error of the lexer, after which only `Token::EOF` is produced.
+    lexer_error: Option<crate::lexer::Error>,
+    /// Whether `name {` starts a struct literal, which it does not in front of a block.
+    struct_literals: bool,
 
     pub(super) previous: TokenInfo,
     pub(super) current: TokenInfo,
@@ -24,8 +28,19 @@ pub struct Parser<'a> {
 
 impl<'a> Parser<'a> {
     pub fn new(source: &'a str) -> Self {
+        return Self::from_lexer(Lexer::new(source));
+    }
+
+    /// Creates a parser over a source that starts at `position` within an enclosing source.
+    pub(super) fn with_position(source: &'a str, position: Position) -> Self {
+        return Self::from_lexer(Lexer::with_position(source, position));
+    }
+
+    fn from_lexer(lexer: Lexer<'a>) -> Self {
         let mut parser = Self {
-            lexer: Lexer::new(source),
+            lexer,
+            lexer_error: None,
+            struct_literals: true,
 
             previous: TokenInfo::new(),
             current: TokenInfo::new(),
@@ -41,7 +56,15 @@ impl<'a> Parser<'a> {
     pub fn parse(&mut self) -> Result<ast::File> {
         let mut file = ast::File::new();
         while self.current.token != Token::EOF {
-            file.statements.push(Box::new(self.parse_statement()?))
+            match self.parse_statement() {
+                Ok(statement) => file.statements.push(Box::new(statement)),
+                // an invalid token is the cause of any following error
+                Err(e) => return Err(self.lexer_error.take().map_or(e, Error::LexerError)),
+            }
+        }
+
+        if let Some(e) = self.lexer_error.take() {
+            return Err(Error::LexerError(e));
         }
         return Ok(file);
     }
@@ -49,7 +72,18 @@ impl<'a> Parser<'a> {
     pub(super) fn next(&mut self) {
         std::mem::swap(&mut self.current, &mut self.previous);
         std::mem::swap(&mut self.next, &mut self.current);
-        self.next = self.lexer.next().unwrap_or(TokenInfo::new())
+        if self.lexer_error.is_some() {
+            self.next = TokenInfo::new();
+            return;
+        }
+
+        self.next = match self.lexer.next() {
+            Ok(token) => token,
+            Err(e) => {
+                self.lexer_error = Some(e);
+                TokenInfo::new()
+            }
+        };
     }
 
     pub(super) fn expect(&mut self, token: Token) -> Result<()> {
@@ -75,6 +109,15 @@ impl<'a> Parser<'a> {
             return true;
         } else if self.on_new_line() || self.current.token == Token::EOF {
             return true;
+        } else if self.current.token == Token::RightBrace {
+            // the last statement of a block can be on the line of its closing brace
+            return true;
+        } else if matches!(
+            self.current.token,
+            Token::LineComment(_) | Token::BlockComment(_)
+        ) {
+            // a trailing comment is parsed as its own statement
+            return true;
         } else {
             return false;
         }
@@ -123,6 +166,18 @@ impl<'a> Parser<'a> {
         return self.current.position.start.line > self.previous.position.end.line;
     }
 
+    /// Runs `parse` with struct literals allowed or not and restores the previous setting.
+    pub(super) fn with_struct_literals<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        let previous = std::mem::replace(&mut self.struct_literals, allowed);
+        let result = parse(self);
+        self.struct_literals = previous;
+        return result;
+    }
+
     pub(super) fn get_identifier_name(&self) -> Result<String> {
         if let Token::Identifier(identifier) = &self.current.token {
             return Ok(identifier.into());
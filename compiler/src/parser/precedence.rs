@@ -1,31 +1,72 @@
 use crate::token::Token;
 
+/// Binding strength of the operators, from the loosest to the tightest.
+///
+/// Like in C, `||` binds looser than `&&`, which binds looser than the comparisons.
+/// Unlike in C, the bitwise operators bind tighter than the comparisons,
+/// so that `x & 1 == 0` compares the result of `x & 1`. The same goes for `??`,
+/// so that `x ?? 0 > 1` compares the unwrapped value.
 #[derive(PartialEq, PartialOrd)]
 pub enum Precedence {
     Lowest,
+    /// ||
+    Or,
+    /// &&
+    And,
+    /// == !=
     Equal,
+    /// < > <= >=
     LessGreater,
+    /// ??
+    Coalesce,
+    /// |
+    BitOr,
+    /// ^
+    BitXor,
+    /// &
+    BitAnd,
+    /// << >>
+    Shift,
+    /// + -
     Sum,
+    /// * / %
     Product,
+    /// -x !x ~x
     Prefix,
+    /// x?
+    Postfix,
+    /// f(x)
     Call,
+    /// a.b a[b]
     Index,
 }
 
 impl Precedence {
     pub fn of(token: &Token) -> Precedence {
         match token {
+            Token::Or => Precedence::Or,
+            Token::And => Precedence::And,
             Token::Equal => Precedence::Equal,
             Token::NotEqual => Precedence::Equal,
             Token::LessThan => Precedence::LessGreater,
             Token::GreaterThan => Precedence::LessGreater,
+            Token::LessThanOrEqual => Precedence::LessGreater,
+            Token::GreaterThanOrEqual => Precedence::LessGreater,
+            Token::Coalesce => Precedence::Coalesce,
+            Token::Pipe => Precedence::BitOr,
+            Token::Caret => Precedence::BitXor,
+            Token::Ampersand => Precedence::BitAnd,
+            Token::ShiftLeft => Precedence::Shift,
+            Token::ShiftRight => Precedence::Shift,
             Token::Plus => Precedence::Sum,
             Token::Minus => Precedence::Sum,
             Token::Asterisk => Precedence::Product,
             Token::Slash => Precedence::Product,
             Token::Percent => Precedence::Product,
+            Token::QuestionMark => Precedence::Postfix,
             Token::LeftParenthesis => Precedence::Call,
             Token::LeftBracket => Precedence::Index,
+            Token::Dot => Precedence::Index,
             _ => Precedence::Lowest,
         }
     }
@@ -11,12 +11,42 @@ impl<'a> Parser<'a> {
             Token::BlockComment(_) => Comment(self.parse_comment_statement()?),
             Token::Mutable => Declaration(self.parse_declaration_statement(true)?),
             Token::LeftBrace => Block(self.parse_block_statement()?),
-            Token::Function => FunctionDeclaration(self.parse_function_declaration()?),
+            Token::Function if matches!(self.next.token, Token::Identifier(_)) => {
+                FunctionDeclaration(self.parse_function_declaration()?)
+            }
+            Token::Type => TypeDeclaration(self.parse_type_declaration()?),
+            Token::Enum => TypeDeclaration(self.parse_enum_declaration()?),
             Token::Return => Return(self.parse_return_statement()?),
+            Token::If => If(self.parse_if_statement()?),
+            Token::While => While(self.parse_while_statement()?),
+            Token::For => For(self.parse_for_statement()?),
+            Token::Break => {
+                self.next();
+                Break
+            }
+            Token::Continue => {
+                self.next();
+                Continue
+            }
+            Token::LeftParenthesis if self.is_destructuring() => {
+                Declaration(self.parse_declaration_statement(false)?)
+            }
             _ => match &self.next.token {
                 Token::Declare => Declaration(self.parse_declaration_statement(false)?),
-                Token::Assign => Assignment(self.parse_assignment_statement()?),
-                _ => Expression(self.parse_expression(Precedence::Lowest)?),
+                _ => {
+                    let expression = self.parse_expression(Precedence::Lowest)?;
+                    match self.current.token {
+                        Token::Assign
+                        | Token::PlusAssign
+                        | Token::MinusAssign
+                        | Token::AsteriskAssign
+                        | Token::SlashAssign
+                        | Token::PercentAssign => {
+                            Assignment(self.parse_assignment_statement(expression)?)
+                        }
+                        _ => Expression(expression),
+                    }
+                }
             },
         };
 
@@ -47,8 +77,7 @@ impl<'a> Parser<'a> {
             self.consume(Token::Mutable)?;
         }
 
-        let name = self.get_identifier_name()?;
-        self.next();
+        let pattern = self.parse_pattern()?;
 
         self.consume(Token::Declare)?;
 
@@ -56,20 +85,120 @@ impl<'a> Parser<'a> {
 
         return Ok(ast::Declaration {
             mutable,
-            name,
+            pattern,
             value,
         });
     }
 
-    fn parse_assignment_statement(&mut self) -> Result<ast::Assignment> {
+    pub(super) fn parse_pattern(&mut self) -> Result<ast::Pattern> {
+        if self.current.token != Token::LeftParenthesis {
+            let name = self.get_identifier_name()?;
+            self.next();
+
+            if name == "_" {
+                return Ok(ast::Pattern::Wildcard);
+            } else if self.current.token == Token::LeftParenthesis {
+                let (elements, _) = self.parse_pattern_elements()?;
+                return Ok(ast::Pattern::Variant(ast::VariantPattern {
+                    name,
+                    elements,
+                }));
+            }
+            return Ok(ast::Pattern::Identifier(name));
+        }
+
+        let (mut elements, trailing_comma) = self.parse_pattern_elements()?;
+        if elements.len() != 1 || trailing_comma {
+            return Ok(ast::Pattern::Tuple(elements));
+        }
+        return Ok(elements.remove(0));
+    }
+
+    /// Parses the parenthesized patterns of a tuple or a variant
+    /// and returns whether a comma follows the last one.
+    fn parse_pattern_elements(&mut self) -> Result<(Vec<ast::Pattern>, bool)> {
+        self.consume(Token::LeftParenthesis)?;
+
+        let mut elements = vec![];
+        while self.current.token != Token::RightParenthesis {
+            elements.push(self.parse_pattern()?);
+
+            if self.current.token == Token::RightParenthesis {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+        let trailing_comma = self.previous.token == Token::Comma;
+
+        self.consume(Token::RightParenthesis)?;
+
+        return Ok((elements, trailing_comma));
+    }
+
+    /// Returns whether a pattern followed by `:=` starts at the current token, like in
+    /// `(a, b) := pair` or `if x := maybe`, which cannot be told apart from
+    /// an expression by the next token alone.
+    fn is_destructuring(&self) -> bool {
+        let mut parser = self.clone();
+        return parser.parse_pattern().is_ok() && parser.current.token == Token::Declare;
+    }
+
+    fn parse_assignment_statement(&mut self, target: ast::Expression) -> Result<ast::Assignment> {
+        if target.assigned_variable().is_none() {
+            return Err(Error::InvalidAssignmentTarget {
+                position: target.position,
+            });
+        }
+
+        let operator = match self.current.token {
+            Token::Assign => None,
+            Token::PlusAssign => Some(Token::Plus),
+            Token::MinusAssign => Some(Token::Minus),
+            Token::AsteriskAssign => Some(Token::Asterisk),
+            Token::SlashAssign => Some(Token::Slash),
+            Token::PercentAssign => Some(Token::Percent),
+            _ => {
+                return Err(Error::UnexpectedToken {
+                    want: "assignment operator".into(),
+                    got: self.current.token.to_string(),
+                    position: self.current.position(),
+                })
+            }
+        };
+        self.next();
+
+        let value = Box::new(self.parse_expression(Precedence::Lowest)?);
+
+        return Ok(ast::Assignment {
+            target,
+            operator,
+            value,
+        });
+    }
+
+    fn parse_type_declaration(&mut self) -> Result<ast::TypeDeclaration> {
+        self.consume(Token::Type)?;
+
         let name = self.get_identifier_name()?;
         self.next();
 
         self.consume(Token::Assign)?;
 
-        let value = Box::new(self.parse_expression(Precedence::Lowest)?);
+        let typ = self.parse_type()?;
+
+        return Ok(ast::TypeDeclaration { name, typ });
+    }
+
+    fn parse_enum_declaration(&mut self) -> Result<ast::TypeDeclaration> {
+        self.consume(Token::Enum)?;
+
+        let name = self.get_identifier_name()?;
+        self.next();
+
+        let typ = ast::Type::Enum(self.parse_enum_type()?);
 
-        return Ok(ast::Assignment { name, value });
+        return Ok(ast::TypeDeclaration { name, typ });
     }
 
     pub(super) fn parse_block_statement(&mut self) -> Result<ast::Block> {
@@ -85,4 +214,61 @@ impl<'a> Parser<'a> {
         return Ok(ast::Block { statements });
     }
 
+    fn parse_if_statement(&mut self) -> Result<ast::If> {
+        self.consume(Token::If)?;
+
+        let mut binding = None;
+        if self.is_destructuring() {
+            binding = Some(self.parse_pattern()?);
+            self.consume(Token::Declare)?;
+        }
+
+        let condition = Box::new(self.parse_condition()?);
+        let consequence = self.parse_block_statement()?;
+
+        let mut alternative = None;
+        if self.current.token == Token::Else {
+            self.next();
+
+            if self.current.token == Token::If {
+                alternative = Some(ast::Else::If(Box::new(self.parse_if_statement()?)));
+            } else {
+                alternative = Some(ast::Else::Block(self.parse_block_statement()?));
+            }
+        }
+
+        return Ok(ast::If {
+            binding,
+            condition,
+            consequence,
+            alternative,
+        });
+    }
+
+    fn parse_while_statement(&mut self) -> Result<ast::While> {
+        self.consume(Token::While)?;
+
+        let condition = Box::new(self.parse_condition()?);
+        let body = self.parse_block_statement()?;
+
+        return Ok(ast::While { condition, body });
+    }
+
+    fn parse_for_statement(&mut self) -> Result<ast::For> {
+        self.consume(Token::For)?;
+
+        let variable = self.get_identifier_name()?;
+        self.next();
+
+        self.consume(Token::In)?;
+
+        let iterable = Box::new(self.parse_condition()?);
+        let body = self.parse_block_statement()?;
+
+        return Ok(ast::For {
+            variable,
+            iterable,
+            body,
+        });
+    }
 }
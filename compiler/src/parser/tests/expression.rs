@@ -31,7 +31,7 @@ fn parse_literals() {
             r#""Hello World""#,
             ast::Expression {
                 typ: ast::Type::String,
-                kind: String("Hello World".into()),
+                kind: String(r#""Hello World""#.into()),
                 position: Span::from((0, 1, 0), (12, 1, 0)),
             },
         ),
@@ -94,6 +94,41 @@ fn parse_prefix_expression() {
     assert_expression_equal(cases);
 }
 
+#[test]
+fn parse_postfix_expression() {
+    let cases = &[(
+        r#"-a?.b"#,
+        ast::Expression {
+            typ: ast::Type::Unknown,
+            kind: Prefix(ast::PrefixExpression {
+                operator: Token::Minus,
+                right: Box::new(ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Member(ast::MemberExpression {
+                        object: Box::new(ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Postfix(ast::PostfixExpression {
+                                left: Box::new(ast::Expression {
+                                    typ: ast::Type::Unknown,
+                                    kind: Identifier("a".into()),
+                                    position: Span::from((1, 1, 0), (1, 1, 0)),
+                                }),
+                                operator: Token::QuestionMark,
+                            }),
+                            position: Span::from((1, 1, 0), (2, 1, 0)),
+                        }),
+                        name: "b".into(),
+                    }),
+                    position: Span::from((1, 1, 0), (4, 1, 0)),
+                }),
+            }),
+            position: Span::from((0, 1, 0), (4, 1, 0)),
+        },
+    )];
+
+    assert_expression_equal(cases);
+}
+
 #[test]
 fn parse_infix_expression() {
     let cases = &[
@@ -124,13 +159,13 @@ fn parse_infix_expression() {
                 kind: Infix(ast::InfixExpression {
                     left: Box::new(ast::Expression {
                         typ: ast::Type::String,
-                        kind: String("Hello".into()),
+                        kind: String(r#""Hello""#.into()),
                         position: Span::from((0, 1, 0), (6, 1, 0)),
                     }),
                     operator: Token::NotEqual,
                     right: Box::new(ast::Expression {
                         typ: ast::Type::String,
-                        kind: String("World".into()),
+                        kind: String(r#""World""#.into()),
                         position: Span::from((11, 1, 0), (17, 1, 0)),
                     }),
                 }),
@@ -169,7 +204,655 @@ fn parse_infix_expression() {
                 position: Span::from((0, 1, 0), (9, 1, 0)),
             },
         ),
+        (
+            r#"a || b && c >= 1"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Infix(ast::InfixExpression {
+                    left: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("a".into()),
+                        position: Span::from((0, 1, 0), (0, 1, 0)),
+                    }),
+                    operator: Token::Or,
+                    right: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Infix(ast::InfixExpression {
+                            left: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Identifier("b".into()),
+                                position: Span::from((5, 1, 0), (5, 1, 0)),
+                            }),
+                            operator: Token::And,
+                            right: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Infix(ast::InfixExpression {
+                                    left: Box::new(ast::Expression {
+                                        typ: ast::Type::Unknown,
+                                        kind: Identifier("c".into()),
+                                        position: Span::from((10, 1, 0), (10, 1, 0)),
+                                    }),
+                                    operator: Token::GreaterThanOrEqual,
+                                    right: Box::new(ast::Expression {
+                                        typ: ast::Type::Integer,
+                                        kind: Integer("1".into()),
+                                        position: Span::from((15, 1, 0), (15, 1, 0)),
+                                    }),
+                                }),
+                                position: Span::from((10, 1, 0), (15, 1, 0)),
+                            }),
+                        }),
+                        position: Span::from((5, 1, 0), (15, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (15, 1, 0)),
+            },
+        ),
+        (
+            r#"~a & 1 == b"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Infix(ast::InfixExpression {
+                    left: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Infix(ast::InfixExpression {
+                            left: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Prefix(ast::PrefixExpression {
+                                    operator: Token::Tilde,
+                                    right: Box::new(ast::Expression {
+                                        typ: ast::Type::Unknown,
+                                        kind: Identifier("a".into()),
+                                        position: Span::from((1, 1, 0), (1, 1, 0)),
+                                    }),
+                                }),
+                                position: Span::from((0, 1, 0), (1, 1, 0)),
+                            }),
+                            operator: Token::Ampersand,
+                            right: Box::new(ast::Expression {
+                                typ: ast::Type::Integer,
+                                kind: Integer("1".into()),
+                                position: Span::from((5, 1, 0), (5, 1, 0)),
+                            }),
+                        }),
+                        position: Span::from((0, 1, 0), (5, 1, 0)),
+                    }),
+                    operator: Token::Equal,
+                    right: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("b".into()),
+                        position: Span::from((10, 1, 0), (10, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (10, 1, 0)),
+            },
+        ),
+        (
+            "a ?? b ?? none",
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Infix(ast::InfixExpression {
+                    left: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("a".into()),
+                        position: Span::from((0, 1, 0), (0, 1, 0)),
+                    }),
+                    operator: Token::Coalesce,
+                    right: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Infix(ast::InfixExpression {
+                            left: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Identifier("b".into()),
+                                position: Span::from((5, 1, 0), (5, 1, 0)),
+                            }),
+                            operator: Token::Coalesce,
+                            right: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: NoneLiteral,
+                                position: Span::from((10, 1, 0), (13, 1, 0)),
+                            }),
+                        }),
+                        position: Span::from((5, 1, 0), (13, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (13, 1, 0)),
+            },
+        ),
+    ];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_tuple_expression() {
+    let cases = &[
+        (
+            r#"(1, a)"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Tuple(vec![
+                    ast::Expression {
+                        typ: ast::Type::Integer,
+                        kind: Integer("1".into()),
+                        position: Span::from((1, 1, 0), (1, 1, 0)),
+                    },
+                    ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("a".into()),
+                        position: Span::from((4, 1, 0), (4, 1, 0)),
+                    },
+                ]),
+                position: Span::from((0, 1, 0), (5, 1, 0)),
+            },
+        ),
+        (
+            r#"(a,)"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Tuple(vec![ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Identifier("a".into()),
+                    position: Span::from((1, 1, 0), (1, 1, 0)),
+                }]),
+                position: Span::from((0, 1, 0), (3, 1, 0)),
+            },
+        ),
+        (
+            r#"()"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Tuple(vec![]),
+                position: Span::from((0, 1, 0), (1, 1, 0)),
+            },
+        ),
+        (
+            r#"(a)"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Identifier("a".into()),
+                position: Span::from((0, 1, 0), (2, 1, 0)),
+            },
+        ),
+    ];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_member_expression() {
+    let cases = &[(
+        r#"-t.0.1"#,
+        ast::Expression {
+            typ: ast::Type::Unknown,
+            kind: Prefix(ast::PrefixExpression {
+                operator: Token::Minus,
+                right: Box::new(ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Member(ast::MemberExpression {
+                        object: Box::new(ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Member(ast::MemberExpression {
+                                object: Box::new(ast::Expression {
+                                    typ: ast::Type::Unknown,
+                                    kind: Identifier("t".into()),
+                                    position: Span::from((1, 1, 0), (1, 1, 0)),
+                                }),
+                                name: "0".into(),
+                            }),
+                            position: Span::from((1, 1, 0), (3, 1, 0)),
+                        }),
+                        name: "1".into(),
+                    }),
+                    position: Span::from((1, 1, 0), (5, 1, 0)),
+                }),
+            }),
+            position: Span::from((0, 1, 0), (5, 1, 0)),
+        },
+    )];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_list_expression() {
+    let cases = &[
+        (
+            "[1, [a]]",
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: List(vec![
+                    ast::Expression {
+                        typ: ast::Type::Integer,
+                        kind: Integer("1".into()),
+                        position: Span::from((1, 1, 0), (1, 1, 0)),
+                    },
+                    ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: List(vec![ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("a".into()),
+                            position: Span::from((5, 1, 0), (5, 1, 0)),
+                        }]),
+                        position: Span::from((4, 1, 0), (6, 1, 0)),
+                    },
+                ]),
+                position: Span::from((0, 1, 0), (7, 1, 0)),
+            },
+        ),
+        (
+            "[\n  1,\n  2,\n]",
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: List(vec![
+                    ast::Expression {
+                        typ: ast::Type::Integer,
+                        kind: Integer("1".into()),
+                        position: Span::from((4, 2, 2), (4, 2, 2)),
+                    },
+                    ast::Expression {
+                        typ: ast::Type::Integer,
+                        kind: Integer("2".into()),
+                        position: Span::from((9, 3, 7), (9, 3, 7)),
+                    },
+                ]),
+                position: Span::from((0, 1, 0), (12, 4, 12)),
+            },
+        ),
+    ];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_map_expression() {
+    let cases = &[
+        (
+            "{\"a\": [1], b: []}",
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Map(vec![
+                    ast::MapEntry {
+                        key: ast::Expression {
+                            typ: ast::Type::String,
+                            kind: String("\"a\"".into()),
+                            position: Span::from((1, 1, 0), (3, 1, 0)),
+                        },
+                        value: ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: List(vec![ast::Expression {
+                                typ: ast::Type::Integer,
+                                kind: Integer("1".into()),
+                                position: Span::from((7, 1, 0), (7, 1, 0)),
+                            }]),
+                            position: Span::from((6, 1, 0), (8, 1, 0)),
+                        },
+                    },
+                    ast::MapEntry {
+                        key: ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("b".into()),
+                            position: Span::from((11, 1, 0), (11, 1, 0)),
+                        },
+                        value: ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: List(vec![]),
+                            position: Span::from((14, 1, 0), (15, 1, 0)),
+                        },
+                    },
+                ]),
+                position: Span::from((0, 1, 0), (16, 1, 0)),
+            },
+        ),
+        (
+            "{}",
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Map(vec![]),
+                position: Span::from((0, 1, 0), (1, 1, 0)),
+            },
+        ),
+    ];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_match_expression() {
+    let cases = &[(
+        "match s {\n  Rect(w, _) => w, (a,) => a\n  Empty => 0\n}",
+        ast::Expression {
+            typ: ast::Type::Unknown,
+            kind: Match(ast::MatchExpression {
+                subject: Box::new(ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Identifier("s".into()),
+                    position: Span::from((6, 1, 0), (6, 1, 0)),
+                }),
+                arms: vec![
+                    ast::MatchArm {
+                        pattern: ast::Pattern::Variant(ast::VariantPattern {
+                            name: "Rect".into(),
+                            elements: vec![
+                                ast::Pattern::Identifier("w".into()),
+                                ast::Pattern::Wildcard,
+                            ],
+                        }),
+                        body: ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("w".into()),
+                            position: Span::from((26, 2, 10), (26, 2, 10)),
+                        },
+                        position: Span::from((12, 2, 10), (26, 2, 10)),
+                    },
+                    ast::MatchArm {
+                        pattern: ast::Pattern::Tuple(vec![ast::Pattern::Identifier("a".into())]),
+                        body: ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("a".into()),
+                            position: Span::from((37, 2, 10), (37, 2, 10)),
+                        },
+                        position: Span::from((29, 2, 10), (37, 2, 10)),
+                    },
+                    ast::MatchArm {
+                        pattern: ast::Pattern::Identifier("Empty".into()),
+                        body: ast::Expression {
+                            typ: ast::Type::Integer,
+                            kind: Integer("0".into()),
+                            position: Span::from((50, 3, 39), (50, 3, 39)),
+                        },
+                        position: Span::from((41, 3, 39), (50, 3, 39)),
+                    },
+                ],
+            }),
+            position: Span::from((0, 1, 0), (52, 4, 52)),
+        },
+    )];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_lambda_expression() {
+    let cases = &[(
+        r#"fn (x: int): _ { return x }"#,
+        ast::Expression {
+            typ: ast::Type::Unknown,
+            kind: Lambda(ast::LambdaExpression {
+                typ: ast::FunctionType {
+                    parameters: vec![ast::Parameter {
+                        name: "x".into(),
+                        typ: ast::Type::Integer,
+                        position: Span::from((4, 1, 0), (9, 1, 0)),
+                    }],
+                    return_type: Some(Box::new(ast::Type::Unknown)),
+                },
+                body: ast::Block {
+                    statements: vec![Box::new(ast::Statement {
+                        kind: ast::StatementKind::Return(ast::Return {
+                            value: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Identifier("x".into()),
+                                position: Span::from((24, 1, 0), (24, 1, 0)),
+                            }),
+                        }),
+                        position: Span::from((17, 1, 0), (24, 1, 0)),
+                    })],
+                },
+            }),
+            position: Span::from((0, 1, 0), (26, 1, 0)),
+        },
+    )];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_index_expression() {
+    let cases = &[(
+        r#"a.b[0].c"#,
+        ast::Expression {
+            typ: ast::Type::Unknown,
+            kind: Member(ast::MemberExpression {
+                object: Box::new(ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Index(ast::IndexExpression {
+                        object: Box::new(ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Member(ast::MemberExpression {
+                                object: Box::new(ast::Expression {
+                                    typ: ast::Type::Unknown,
+                                    kind: Identifier("a".into()),
+                                    position: Span::from((0, 1, 0), (0, 1, 0)),
+                                }),
+                                name: "b".into(),
+                            }),
+                            position: Span::from((0, 1, 0), (2, 1, 0)),
+                        }),
+                        index: Box::new(ast::Expression {
+                            typ: ast::Type::Integer,
+                            kind: Integer("0".into()),
+                            position: Span::from((4, 1, 0), (4, 1, 0)),
+                        }),
+                    }),
+                    position: Span::from((0, 1, 0), (5, 1, 0)),
+                }),
+                name: "c".into(),
+            }),
+            position: Span::from((0, 1, 0), (7, 1, 0)),
+        },
+    )];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_struct_expression() {
+    let cases = &[
+        (
+            r#"Point{ x: 1, y: (P{}) }"#,
+            ast::Expression {
+                typ: ast::Type::Name("Point".into()),
+                kind: Struct(ast::StructExpression {
+                    name: "Point".into(),
+                    fields: vec![
+                        ast::FieldValue {
+                            name: "x".into(),
+                            value: ast::Expression {
+                                typ: ast::Type::Integer,
+                                kind: Integer("1".into()),
+                                position: Span::from((10, 1, 0), (10, 1, 0)),
+                            },
+                        },
+                        ast::FieldValue {
+                            name: "y".into(),
+                            value: ast::Expression {
+                                typ: ast::Type::Name("P".into()),
+                                kind: Struct(ast::StructExpression {
+                                    name: "P".into(),
+                                    fields: vec![],
+                                }),
+                                position: Span::from((16, 1, 0), (20, 1, 0)),
+                            },
+                        },
+                    ],
+                }),
+                position: Span::from((0, 1, 0), (22, 1, 0)),
+            },
+        ),
+        (
+            r#"p.x"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Member(ast::MemberExpression {
+                    object: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("p".into()),
+                        position: Span::from((0, 1, 0), (0, 1, 0)),
+                    }),
+                    name: "x".into(),
+                }),
+                position: Span::from((0, 1, 0), (2, 1, 0)),
+            },
+        ),
+    ];
+
+    assert_expression_equal(cases);
+}
+
+#[test]
+fn parse_call_expression() {
+    let cases = &[
+        (
+            r#"f()"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Call(ast::CallExpression {
+                    callee: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("f".into()),
+                        position: Span::from((0, 1, 0), (0, 1, 0)),
+                    }),
+                    arguments: vec![],
+                }),
+                position: Span::from((0, 1, 0), (2, 1, 0)),
+            },
+        ),
+        (
+            r#"max(a, 1 + 2)"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Call(ast::CallExpression {
+                    callee: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("max".into()),
+                        position: Span::from((0, 1, 0), (2, 1, 0)),
+                    }),
+                    arguments: vec![
+                        ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("a".into()),
+                            position: Span::from((4, 1, 0), (4, 1, 0)),
+                        },
+                        ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Infix(ast::InfixExpression {
+                                left: Box::new(ast::Expression {
+                                    typ: ast::Type::Integer,
+                                    kind: Integer("1".into()),
+                                    position: Span::from((7, 1, 0), (7, 1, 0)),
+                                }),
+                                operator: Token::Plus,
+                                right: Box::new(ast::Expression {
+                                    typ: ast::Type::Integer,
+                                    kind: Integer("2".into()),
+                                    position: Span::from((11, 1, 0), (11, 1, 0)),
+                                }),
+                            }),
+                            position: Span::from((7, 1, 0), (11, 1, 0)),
+                        },
+                    ],
+                }),
+                position: Span::from((0, 1, 0), (12, 1, 0)),
+            },
+        ),
+        (
+            "print(\n  \"a\"\n  \"b\"\n)",
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Call(ast::CallExpression {
+                    callee: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("print".into()),
+                        position: Span::from((0, 1, 0), (4, 1, 0)),
+                    }),
+                    arguments: vec![
+                        ast::Expression {
+                            typ: ast::Type::String,
+                            kind: String(r#""a""#.into()),
+                            position: Span::from((9, 2, 7), (11, 2, 7)),
+                        },
+                        ast::Expression {
+                            typ: ast::Type::String,
+                            kind: String(r#""b""#.into()),
+                            position: Span::from((15, 3, 13), (17, 3, 13)),
+                        },
+                    ],
+                }),
+                position: Span::from((0, 1, 0), (19, 4, 19)),
+            },
+        ),
+        (
+            r#"-f(1)"#,
+            ast::Expression {
+                typ: ast::Type::Unknown,
+                kind: Prefix(ast::PrefixExpression {
+                    operator: Token::Minus,
+                    right: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Call(ast::CallExpression {
+                            callee: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Identifier("f".into()),
+                                position: Span::from((1, 1, 0), (1, 1, 0)),
+                            }),
+                            arguments: vec![ast::Expression {
+                                typ: ast::Type::Integer,
+                                kind: Integer("1".into()),
+                                position: Span::from((3, 1, 0), (3, 1, 0)),
+                            }],
+                        }),
+                        position: Span::from((1, 1, 0), (4, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (4, 1, 0)),
+            },
+        ),
     ];
 
     assert_expression_equal(cases);
 }
+
+#[test]
+fn parse_interpolated_string() {
+    let cases = &[(
+        r#""a + 1 = {a + 1}!""#,
+        ast::Expression {
+            typ: ast::Type::String,
+            kind: InterpolatedString(vec![
+                ast::StringPart::Literal("a + 1 = ".into()),
+                ast::StringPart::Expression(ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Infix(ast::InfixExpression {
+                        left: Box::new(ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("a".into()),
+                            position: Span::from((10, 1, 0), (10, 1, 0)),
+                        }),
+                        operator: Token::Plus,
+                        right: Box::new(ast::Expression {
+                            typ: ast::Type::Integer,
+                            kind: Integer("1".into()),
+                            position: Span::from((14, 1, 0), (14, 1, 0)),
+                        }),
+                    }),
+                    position: Span::from((10, 1, 0), (14, 1, 0)),
+                }),
+                ast::StringPart::Literal("!".into()),
+            ]),
+            position: Span::from((0, 1, 0), (17, 1, 0)),
+        },
+    )];
+
+    assert_expression_equal(cases);
+
+    let mut parser = Parser::new(r#""{a b}""#);
+    pretty_assertions::assert_eq!(
+        Err(Error::UnexpectedToken {
+            want: "ILLEGAL".into(),
+            got: "b".into(),
+            position: Span::from((4, 1, 0), (4, 1, 0)),
+        }),
+        parser.parse_expression(Precedence::Lowest)
+    );
+}
@@ -37,3 +37,26 @@ fn assert_type_equal(cases: &[(&str, ast::Type)]) {
         assert_eq!(case.1, got);
     }
 }
+
+#[test]
+fn parse_lexer_error() {
+    let mut parser = Parser::new("a := 1\nb := \"\\x\"\nc := 3");
+    assert_eq!(
+        Err(Error::LexerError(crate::lexer::Error::InvalidEscape {
+            escape: "\\x".into(),
+            position: crate::token::Position::from((13, 2, 7)),
+        })),
+        parser.parse()
+    );
+}
+
+#[test]
+fn parse_invalid_assignment_target() {
+    let mut parser = Parser::new("f() = 1");
+    assert_eq!(
+        Err(Error::InvalidAssignmentTarget {
+            position: Span::from((0, 1, 0), (2, 1, 0)),
+        }),
+        parser.parse()
+    );
+}
@@ -35,7 +35,7 @@ fn parse_declaration_statement() {
             r#"a := 1"#,
             ast::Statement {
                 kind: Declaration(ast::Declaration {
-                    name: "a".into(),
+                    pattern: ast::Pattern::Identifier("a".into()),
                     mutable: false,
                     value: Box::new(ast::Expression {
                         typ: ast::Type::Integer,
@@ -50,11 +50,11 @@ fn parse_declaration_statement() {
             r#"mut b := "Hello""#,
             ast::Statement {
                 kind: Declaration(ast::Declaration {
-                    name: "b".into(),
+                    pattern: ast::Pattern::Identifier("b".into()),
                     mutable: true,
                     value: Box::new(ast::Expression {
                         typ: ast::Type::String,
-                        kind: String("Hello".into()),
+                        kind: String(r#""Hello""#.into()),
                         position: Span::from((9, 1, 0), (15, 1, 0)),
                     }),
                 }),
@@ -66,66 +66,247 @@ fn parse_declaration_statement() {
     assert_statement_equal(cases);
 }
 
+#[test]
+fn parse_destructuring_declaration() {
+    let cases = &[
+        (
+            r#"(a, _) := b"#,
+            ast::Statement {
+                kind: Declaration(ast::Declaration {
+                    pattern: ast::Pattern::Tuple(vec![
+                        ast::Pattern::Identifier("a".into()),
+                        ast::Pattern::Wildcard,
+                    ]),
+                    mutable: false,
+                    value: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("b".into()),
+                        position: Span::from((10, 1, 0), (10, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (10, 1, 0)),
+            },
+        ),
+        (
+            r#"mut ((a), (b,)) := c"#,
+            ast::Statement {
+                kind: Declaration(ast::Declaration {
+                    pattern: ast::Pattern::Tuple(vec![
+                        ast::Pattern::Identifier("a".into()),
+                        ast::Pattern::Tuple(vec![ast::Pattern::Identifier("b".into())]),
+                    ]),
+                    mutable: true,
+                    value: Box::new(ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("c".into()),
+                        position: Span::from((19, 1, 0), (19, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (19, 1, 0)),
+            },
+        ),
+        (
+            r#"(a, b)"#,
+            ast::Statement {
+                kind: Expression(ast::Expression {
+                    typ: ast::Type::Unknown,
+                    kind: Tuple(vec![
+                        ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("a".into()),
+                            position: Span::from((1, 1, 0), (1, 1, 0)),
+                        },
+                        ast::Expression {
+                            typ: ast::Type::Unknown,
+                            kind: Identifier("b".into()),
+                            position: Span::from((4, 1, 0), (4, 1, 0)),
+                        },
+                    ]),
+                    position: Span::from((0, 1, 0), (5, 1, 0)),
+                }),
+                position: Span::from((0, 1, 0), (5, 1, 0)),
+            },
+        ),
+    ];
+
+    assert_statement_equal(cases);
+}
+
 #[test]
 fn parse_assignment_statement() {
-    let cases = &[(
-        r#"a = 3.141"#,
-        ast::Statement {
-            kind: Assignment(ast::Assignment {
-                name: "a".into(),
-                value: Box::new(ast::Expression {
-                    typ: ast::Type::Float,
-                    kind: Float("3.141".into()),
-                    position: Span::from((4, 1, 0), (8, 1, 0)),
-                }),
-            }),
-            position: Span::from((0, 1, 0), (8, 1, 0)),
-        },
-    )];
+    let cases = &[
+        (
+            r#"a = 3.141"#,
+            ast::Statement {
+                kind: Assignment(ast::Assignment {
+                    target: ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("a".into()),
+                        position: Span::from((0, 1, 0), (0, 1, 0)),
+                    },
+                    operator: None,
+                    value: Box::new(ast::Expression {
+                        typ: ast::Type::Float,
+                        kind: Float("3.141".into()),
+                        position: Span::from((4, 1, 0), (8, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (8, 1, 0)),
+            },
+        ),
+        (
+            r#"a %= 2"#,
+            ast::Statement {
+                kind: Assignment(ast::Assignment {
+                    target: ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Identifier("a".into()),
+                        position: Span::from((0, 1, 0), (0, 1, 0)),
+                    },
+                    operator: Some(Token::Percent),
+                    value: Box::new(ast::Expression {
+                        typ: ast::Type::Integer,
+                        kind: Integer("2".into()),
+                        position: Span::from((5, 1, 0), (5, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (5, 1, 0)),
+            },
+        ),
+        (
+            r#"p.x = 3"#,
+            ast::Statement {
+                kind: Assignment(ast::Assignment {
+                    target: ast::Expression {
+                        typ: ast::Type::Unknown,
+                        kind: Member(ast::MemberExpression {
+                            object: Box::new(ast::Expression {
+                                typ: ast::Type::Unknown,
+                                kind: Identifier("p".into()),
+                                position: Span::from((0, 1, 0), (0, 1, 0)),
+                            }),
+                            name: "x".into(),
+                        }),
+                        position: Span::from((0, 1, 0), (2, 1, 0)),
+                    },
+                    operator: None,
+                    value: Box::new(ast::Expression {
+                        typ: ast::Type::Integer,
+                        kind: Integer("3".into()),
+                        position: Span::from((6, 1, 0), (6, 1, 0)),
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (6, 1, 0)),
+            },
+        ),
+    ];
+
+    assert_statement_equal(cases);
+}
+
+#[test]
+fn parse_type_declaration() {
+    let cases = &[
+        (
+            r#"type P = { x: int = 0 }"#,
+            ast::Statement {
+                kind: TypeDeclaration(ast::TypeDeclaration {
+                    name: "P".into(),
+                    typ: ast::Type::Struct(ast::StructType {
+                        fields: vec![ast::Field {
+                            name: "x".into(),
+                            typ: ast::Type::Integer,
+                            default: Some(Box::new(ast::Expression {
+                                typ: ast::Type::Integer,
+                                kind: Integer("0".into()),
+                                position: Span::from((20, 1, 0), (20, 1, 0)),
+                            })),
+                        }],
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (22, 1, 0)),
+            },
+        ),
+        (
+            "enum Shape {\n  Circle(float)\n  Rect(float, float), Empty\n}",
+            ast::Statement {
+                kind: TypeDeclaration(ast::TypeDeclaration {
+                    name: "Shape".into(),
+                    typ: ast::Type::Enum(ast::EnumType {
+                        variants: vec![
+                            ast::Variant {
+                                name: "Circle".into(),
+                                elements: vec![ast::Type::Float],
+                            },
+                            ast::Variant {
+                                name: "Rect".into(),
+                                elements: vec![ast::Type::Float, ast::Type::Float],
+                            },
+                            ast::Variant {
+                                name: "Empty".into(),
+                                elements: vec![],
+                            },
+                        ],
+                    }),
+                }),
+                position: Span::from((0, 1, 0), (57, 4, 57)),
+            },
+        ),
+    ];
 
     assert_statement_equal(cases);
 }
 
 #[test]
 fn parse_block_statement() {
-    let cases = &[(
-        r#"
+    let cases = &[
+        (
+            r#"
 { 
     1234
     "Hello"; "World"
 }"#,
-        ast::Statement {
-            kind: Block(ast::Block {
-                statements: vec![
-                    Box::new(ast::Statement {
-                        kind: Expression(ast::Expression {
-                            typ: ast::Type::Integer,
-                            kind: Integer("1234".into()),
+            ast::Statement {
+                kind: Block(ast::Block {
+                    statements: vec![
+                        Box::new(ast::Statement {
+                            kind: Expression(ast::Expression {
+                                typ: ast::Type::Integer,
+                                kind: Integer("1234".into()),
+                                position: Span::from((8, 3, 4), (11, 3, 4)),
+                            }),
                             position: Span::from((8, 3, 4), (11, 3, 4)),
                         }),
-                        position: Span::from((8, 3, 4), (11, 3, 4)),
-                    }),
-                    Box::new(ast::Statement {
-                        kind: Expression(ast::Expression {
-                            typ: ast::Type::String,
-                            kind: String("Hello".into()),
-                            position: Span::from((17, 4, 13), (23, 4, 13)),
+                        Box::new(ast::Statement {
+                            kind: Expression(ast::Expression {
+                                typ: ast::Type::String,
+                                kind: String(r#""Hello""#.into()),
+                                position: Span::from((17, 4, 13), (23, 4, 13)),
+                            }),
+                            position: Span::from((17, 4, 13), (24, 4, 13)),
                         }),
-                        position: Span::from((17, 4, 13), (24, 4, 13)),
-                    }),
-                    Box::new(ast::Statement {
-                        kind: Expression(ast::Expression {
-                            typ: ast::Type::String,
-                            kind: String("World".into()),
+                        Box::new(ast::Statement {
+                            kind: Expression(ast::Expression {
+                                typ: ast::Type::String,
+                                kind: String(r#""World""#.into()),
+                                position: Span::from((26, 4, 13), (32, 4, 13)),
+                            }),
                             position: Span::from((26, 4, 13), (32, 4, 13)),
                         }),
-                        position: Span::from((26, 4, 13), (32, 4, 13)),
-                    }),
-                ],
-            }),
-            position: Span::from((1, 2, 1), (34, 5, 34)),
-        },
-    )];
+                    ],
+                }),
+                position: Span::from((1, 2, 1), (34, 5, 34)),
+            },
+        ),
+        // a brace at the start of a statement is a block and not a map
+        (
+            "{}",
+            ast::Statement {
+                kind: Block(ast::Block { statements: vec![] }),
+                position: Span::from((0, 1, 0), (1, 1, 0)),
+            },
+        ),
+    ];
 
     assert_statement_equal(cases);
 }
@@ -175,6 +356,7 @@ fn foo(bar: string): int {
                         parameters: vec![ast::Parameter {
                             name: "bar".into(),
                             typ: Type::String,
+                            position: Span::from((8, 2, 1), (18, 2, 1)),
                         }],
                         return_type: Some(Box::new(Type::Integer)),
                     },
@@ -198,3 +380,125 @@ fn foo(bar: string): int {
 
     assert_statement_equal(cases);
 }
+
+#[test]
+fn parse_if_statement() {
+    let cases = &[
+        (
+            r#"
+if a {
+} else if b {
+  1
+} else {
+}
+"#,
+            ast::Statement {
+                kind: If(ast::If {
+                    binding: None,
+                    condition: Box::new(ast::Expression {
+                        kind: Identifier("a".into()),
+                        typ: Type::Unknown,
+                        position: Span::from((4, 2, 1), (4, 2, 1)),
+                    }),
+                    consequence: ast::Block { statements: vec![] },
+                    alternative: Some(ast::Else::If(Box::new(ast::If {
+                        binding: None,
+                        condition: Box::new(ast::Expression {
+                            kind: Identifier("b".into()),
+                            typ: Type::Unknown,
+                            position: Span::from((18, 3, 8), (18, 3, 8)),
+                        }),
+                        consequence: ast::Block {
+                            statements: vec![Box::new(ast::Statement {
+                                kind: Expression(ast::Expression {
+                                    kind: Integer("1".into()),
+                                    typ: Type::Integer,
+                                    position: Span::from((24, 4, 22), (24, 4, 22)),
+                                }),
+                                position: Span::from((24, 4, 22), (24, 4, 22)),
+                            })],
+                        },
+                        alternative: Some(ast::Else::Block(ast::Block { statements: vec![] })),
+                    }))),
+                }),
+                position: Span::from((1, 2, 1), (35, 6, 35)),
+            },
+        ),
+        (
+            "if (a, _) := b {\n}",
+            ast::Statement {
+                kind: If(ast::If {
+                    binding: Some(ast::Pattern::Tuple(vec![
+                        ast::Pattern::Identifier("a".into()),
+                        ast::Pattern::Wildcard,
+                    ])),
+                    condition: Box::new(ast::Expression {
+                        kind: Identifier("b".into()),
+                        typ: Type::Unknown,
+                        position: Span::from((13, 1, 0), (13, 1, 0)),
+                    }),
+                    consequence: ast::Block { statements: vec![] },
+                    alternative: None,
+                }),
+                position: Span::from((0, 1, 0), (17, 2, 17)),
+            },
+        ),
+    ];
+
+    assert_statement_equal(cases);
+}
+
+#[test]
+fn parse_loop_statements() {
+    let cases = &[
+        (
+            r#"
+while a {
+  break
+}
+"#,
+            ast::Statement {
+                kind: While(ast::While {
+                    condition: Box::new(ast::Expression {
+                        kind: Identifier("a".into()),
+                        typ: Type::Unknown,
+                        position: Span::from((7, 2, 1), (7, 2, 1)),
+                    }),
+                    body: ast::Block {
+                        statements: vec![Box::new(ast::Statement {
+                            kind: Break,
+                            position: Span::from((13, 3, 11), (17, 3, 11)),
+                        })],
+                    },
+                }),
+                position: Span::from((1, 2, 1), (19, 4, 19)),
+            },
+        ),
+        (
+            r#"
+for c in "abc" {
+  continue
+}
+"#,
+            ast::Statement {
+                kind: For(ast::For {
+                    variable: "c".into(),
+                    iterable: Box::new(ast::Expression {
+                        kind: String(r#""abc""#.into()),
+                        typ: Type::String,
+                        position: Span::from((10, 2, 1), (14, 2, 1)),
+                    }),
+                    body: ast::Block {
+                        statements: vec![Box::new(ast::Statement {
+                            kind: Continue,
+                            position: Span::from((20, 3, 18), (27, 3, 18)),
+                        })],
+                    },
+                }),
+                position: Span::from((1, 2, 1), (29, 4, 29)),
+            },
+        ),
+    ];
+
+    assert_statement_equal(cases);
+}
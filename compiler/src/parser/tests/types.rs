@@ -38,6 +38,7 @@ fn parse_tuple_type() {
                 parameters: vec![ast::Parameter {
                     name: "foo".into(),
                     typ: Type::String,
+                    position: Span::from((4, 1, 0), (14, 1, 0)),
                 }],
                 return_type: None,
             }),
@@ -48,12 +49,87 @@ fn parse_tuple_type() {
                 parameters: vec![ast::Parameter {
                     name: "bar".into(),
                     typ: Type::Integer,
+                    position: Span::from((4, 1, 0), (11, 1, 0)),
                 }],
                 return_type: Some(Box::new(Type::Tuple(ast::TupleType {
                     elements: vec![Type::String, Type::Boolean],
                 }))),
             }),
         ),
+        (
+            "fn (baz: _): _",
+            Type::Function(ast::FunctionType {
+                parameters: vec![ast::Parameter {
+                    name: "baz".into(),
+                    typ: Type::Unknown,
+                    position: Span::from((4, 1, 0), (9, 1, 0)),
+                }],
+                return_type: Some(Box::new(Type::Unknown)),
+            }),
+        ),
+        (
+            "[[(int, _)]]",
+            Type::List(Box::new(Type::List(Box::new(Type::Tuple(
+                ast::TupleType {
+                    elements: vec![Type::Integer, Type::Unknown],
+                },
+            ))))),
+        ),
+        (
+            "{string: {(int, int): [bool]}}",
+            Type::Map(ast::MapType {
+                key: Box::new(Type::String),
+                value: Box::new(Type::Map(ast::MapType {
+                    key: Box::new(Type::Tuple(ast::TupleType {
+                        elements: vec![Type::Integer, Type::Integer],
+                    })),
+                    value: Box::new(Type::List(Box::new(Type::Boolean))),
+                })),
+            }),
+        ),
+        (
+            "{ key: int }",
+            Type::Struct(ast::StructType {
+                fields: vec![ast::Field {
+                    name: "key".into(),
+                    typ: Type::Integer,
+                    default: None,
+                }],
+            }),
+        ),
+        ("{}", Type::Struct(ast::StructType { fields: vec![] })),
+        (
+            "[int?]?",
+            Type::Optional(Box::new(Type::List(Box::new(Type::Optional(Box::new(
+                Type::Integer,
+            )))))),
+        ),
+        (
+            "[int]!E?",
+            Type::Optional(Box::new(Type::Result(ast::ResultType {
+                value: Box::new(Type::List(Box::new(Type::Integer))),
+                error: Box::new(Type::Name("E".into())),
+            }))),
+        ),
+        (
+            "fn (): int?!string",
+            Type::Function(ast::FunctionType {
+                parameters: vec![],
+                return_type: Some(Box::new(Type::Result(ast::ResultType {
+                    value: Box::new(Type::Optional(Box::new(Type::Integer))),
+                    error: Box::new(Type::String),
+                }))),
+            }),
+        ),
+        (
+            "fn (): P??",
+            Type::Function(ast::FunctionType {
+                parameters: vec![],
+                return_type: Some(Box::new(Type::Optional(Box::new(Type::Optional(
+                    Box::new(Type::Name("P".into())),
+                ))))),
+            }),
+        ),
     ];
 
     assert_type_equal(cases);
@@ -4,9 +4,42 @@ use ast::Type;
 
 impl<'a> Parser<'a> {
     pub(super) fn parse_type(&mut self) -> Result<ast::Type> {
+        let mut typ = self.parse_primary_type()?;
+
+        // suffixes apply from left to right, so `int?!string` is a result of an optional
+        loop {
+            match self.current.token {
+                Token::QuestionMark => {
+                    self.next();
+                    typ = Type::Optional(Box::new(typ));
+                }
+                // `int??` is lexed as `int` followed by `??`
+                Token::Coalesce => {
+                    self.next();
+                    typ = Type::Optional(Box::new(Type::Optional(Box::new(typ))));
+                }
+                Token::ExclamationMark => {
+                    self.next();
+                    let error = self.parse_primary_type()?;
+                    typ = Type::Result(ast::ResultType {
+                        value: Box::new(typ),
+                        error: Box::new(error),
+                    });
+                }
+                _ => break,
+            }
+        }
+
+        return Ok(typ);
+    }
+
+    /// Parses a type without the `?` and `!E` suffixes.
+    fn parse_primary_type(&mut self) -> Result<ast::Type> {
         let typ = match &self.current.token {
             Token::Identifier(_) => self.parse_identifier_type()?,
             Token::LeftParenthesis => Type::Tuple(self.parse_tuple_type()?),
+            Token::LeftBracket => Type::List(Box::new(self.parse_list_type()?)),
+            Token::LeftBrace if self.is_map_type() => Type::Map(self.parse_map_type()?),
             Token::LeftBrace => Type::Struct(self.parse_struct_type()?),
             Token::Function => Type::Function(self.parse_function_type()?),
 
@@ -26,6 +59,7 @@ impl<'a> Parser<'a> {
         let identifier = self.get_identifier_name()?;
 
         let typ = match identifier.as_str() {
+            "_" => Type::Unknown,
             "bool" => Type::Boolean,
             "int" => Type::Integer,
             "float" => Type::Float,
@@ -57,6 +91,42 @@ impl<'a> Parser<'a> {
         return Ok(ast::TupleType { elements });
     }
 
+    fn parse_list_type(&mut self) -> Result<ast::Type> {
+        self.consume(Token::LeftBracket)?;
+
+        let element = self.parse_type()?;
+
+        self.consume(Token::RightBracket)?;
+
+        return Ok(element);
+    }
+
+    /// `{` starts a map type if a type follows it instead of the name of a field.
+    /// The names of the built-in types are types there, so `{string: int}` is a map.
+    fn is_map_type(&self) -> bool {
+        return match &self.next.token {
+            Token::Identifier(name) => {
+                matches!(name.as_str(), "_" | "bool" | "int" | "float" | "string")
+            }
+            Token::RightBrace => false,
+            _ => true,
+        };
+    }
+
+    fn parse_map_type(&mut self) -> Result<ast::MapType> {
+        self.consume(Token::LeftBrace)?;
+
+        let key = Box::new(self.parse_type()?);
+
+        self.consume(Token::Colon)?;
+
+        let value = Box::new(self.parse_type()?);
+
+        self.consume(Token::RightBrace)?;
+
+        return Ok(ast::MapType { key, value });
+    }
+
     fn parse_struct_type(&mut self) -> Result<ast::StructType> {
         self.consume(Token::LeftBrace)?;
 
@@ -94,7 +164,38 @@ impl<'a> Parser<'a> {
         return Ok(ast::Field { name, typ, default });
     }
 
-    fn parse_function_type(&mut self) -> Result<ast::FunctionType> {
+    pub(super) fn parse_enum_type(&mut self) -> Result<ast::EnumType> {
+        self.consume(Token::LeftBrace)?;
+
+        let mut variants = vec![];
+        while self.current.token != Token::RightBrace {
+            variants.push(self.parse_variant()?);
+
+            if self.current.token == Token::RightBrace {
+                break;
+            } else {
+                self.consume_expression_separator()?;
+            }
+        }
+
+        self.consume(Token::RightBrace)?;
+
+        return Ok(ast::EnumType { variants });
+    }
+
+    fn parse_variant(&mut self) -> Result<ast::Variant> {
+        let name = self.get_identifier_name()?;
+        self.next();
+
+        let mut elements = vec![];
+        if self.current.token == Token::LeftParenthesis {
+            elements = self.parse_tuple_type()?.elements;
+        }
+
+        return Ok(ast::Variant { name, elements });
+    }
+
+    pub(super) fn parse_function_type(&mut self) -> Result<ast::FunctionType> {
         self.consume(Token::Function)?;
 
         self.consume(Token::LeftParenthesis)?;
@@ -126,13 +227,19 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_parameter(&mut self) -> Result<ast::Parameter> {
+        let mut position = self.current.position();
         let name = self.get_identifier_name()?;
         self.next();
 
         self.consume(Token::Colon)?;
 
         let typ = self.parse_type()?;
+        position.end = self.previous.position.end();
 
-        return Ok(ast::Parameter { name, typ });
+        return Ok(ast::Parameter {
+            name,
+            typ,
+            position,
+        });
     }
 }
@@ -0,0 +1,156 @@
+use std::fmt;
+
+use super::BindingKind;
+use crate::token::Span;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    UndefinedName {
+        name: String,
+        position: Span,
+    },
+    DuplicateParameter {
+        name: String,
+        position: Span,
+    },
+    /// `break` or `continue` that is not inside of a loop.
+    OutsideLoop {
+        statement: String,
+        position: Span,
+    },
+    /// Assignment to a binding that was not declared with `mut`, like a parameter or a loop variable.
+    ImmutableAssignment {
+        name: String,
+        kind: BindingKind,
+        position: Span,
+        declaration: Span,
+    },
+    /// Assignment in a lambda to a binding of an enclosing function, which the lambda copies.
+    CapturedAssignment {
+        name: String,
+        position: Span,
+        declaration: Span,
+    },
+    /// Call of a method like `push` that changes a binding not declared with `mut` in place.
+    ImmutableMutation {
+        name: String,
+        kind: BindingKind,
+        method: String,
+        position: Span,
+        declaration: Span,
+    },
+    /// Call of a method like `push` in a lambda that changes a captured binding in place.
+    CapturedMutation {
+        name: String,
+        method: String,
+        position: Span,
+        declaration: Span,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UndefinedName { name, position } => {
+                write!(f, "undefined name: {} @ {}", name, position)
+            }
+            Error::DuplicateParameter { name, position } => {
+                write!(f, "duplicate parameter: {} @ {}", name, position)
+            }
+            Error::OutsideLoop {
+                statement,
+                position,
+            } => write!(f, "{} outside of a loop @ {}", statement, position),
+            Error::ImmutableAssignment {
+                name,
+                kind,
+                position,
+                declaration,
+            } => write!(
+                f,
+                "assignment to immutable binding: {} @ {}, declared @ {}{}",
+                name,
+                position,
+                declaration,
+                mutability_hint(name, kind)
+            ),
+            Error::CapturedAssignment {
+                name,
+                position,
+                declaration,
+            } => write!(
+                f,
+                "assignment to captured binding: {} @ {}, declared @ {} (a lambda only has a copy of the bindings it captures)",
+                name, position, declaration
+            ),
+            Error::ImmutableMutation {
+                name,
+                kind,
+                method,
+                position,
+                declaration,
+            } => write!(
+                f,
+                "{} changes immutable binding: {} @ {}, declared @ {}{}",
+                method,
+                name,
+                position,
+                declaration,
+                mutability_hint(name, kind)
+            ),
+            Error::CapturedMutation {
+                name,
+                method,
+                position,
+                declaration,
+            } => write!(
+                f,
+                "{} changes captured binding: {} @ {}, declared @ {} (a lambda only has a copy of the bindings it captures)",
+                method, name, position, declaration
+            ),
+        }
+    }
+}
+
+/// Diagnostics for legal code that is likely a mistake.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Warning {
+    Shadowing {
+        name: String,
+        position: Span,
+        shadowed: Span,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::Shadowing {
+                name,
+                position,
+                shadowed,
+            } => write!(
+                f,
+                "declaration of {} @ {} shadows previous declaration @ {}",
+                name, position, shadowed
+            ),
+        }
+    }
+}
+
+/// Returns how to change a binding of the kind, which only variables can be declared to allow.
+fn mutability_hint(name: &str, kind: &BindingKind) -> String {
+    return match kind {
+        BindingKind::Variable { .. } => {
+            format!(
+                " (declare it with `mut {} := ...` to make it mutable)",
+                name
+            )
+        }
+        BindingKind::Parameter => " (parameters cannot be changed)".into(),
+        BindingKind::LoopVariable => {
+            " (the variable of a for loop is bound to each element and cannot be changed)".into()
+        }
+        BindingKind::Function | BindingKind::Variant => String::new(),
+    };
+}
@@ -0,0 +1,588 @@
+mod error;
+mod scope;
+
+pub use error::*;
+pub use scope::*;
+
+use crate::ast;
+use crate::token::Span;
+
+/// Variants of the built-in result type, which are declared unless a file declares its own.
+pub const RESULT_VARIANTS: [&str; 2] = ["Ok", "Err"];
+
+/// Built-in function that writes a string and a newline to stdout, unless a file declares its own.
+pub const PRINT: &str = "print";
+
+/// Links every use of a name to its declaration and reports names that are not declared.
+///
+/// Function bodies only see their own parameters and locals and the scope of the file,
+/// not the locals of an enclosing function.
+#[derive(Clone, Debug)]
+pub struct Resolver {
+    scopes: Vec<Scope>,
+    current: usize,
+    stack: Vec<usize>,
+    /// Number of loops enclosing the current statement within the current function.
+    loops: usize,
+    /// Scopes of the parameters of the lambdas enclosing the current statement within
+    /// the current function, innermost last, with the span of each lambda.
+    closures: Vec<(usize, Span)>,
+
+    references: Vec<Reference>,
+    captures: Vec<Capture>,
+    errors: Vec<Error>,
+    warnings: Vec<Warning>,
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        return Resolver {
+            scopes: vec![Scope {
+                parent: None,
+                bindings: vec![],
+            }],
+            current: 0,
+            stack: vec![],
+            loops: 0,
+            closures: vec![],
+
+            references: vec![],
+            captures: vec![],
+            errors: vec![],
+            warnings: vec![],
+        };
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn resolve(mut self, file: &ast::File) -> Result<Resolution, Vec<Error>> {
+        self.resolve_file(file);
+
+        if !self.errors.is_empty() {
+            return Err(self.errors);
+        }
+
+        return Ok(Resolution {
+            scopes: self.scopes,
+            references: self.references,
+            captures: self.captures,
+            warnings: self.warnings,
+        });
+    }
+
+    fn resolve_file(&mut self, file: &ast::File) {
+        // functions and variants can be used before their declaration at the top level
+        for statement in &file.statements {
+            match &statement.kind {
+                ast::StatementKind::FunctionDeclaration(function) => {
+                    self.declare(&function.name, BindingKind::Function, &statement.position)
+                }
+                ast::StatementKind::TypeDeclaration(declaration) => {
+                    self.declare_variants(&declaration.typ, &statement.position)
+                }
+                _ => {}
+            }
+        }
+
+        for statement in &file.statements {
+            match &statement.kind {
+                ast::StatementKind::FunctionDeclaration(function) => {
+                    self.resolve_function(function, &statement.position)
+                }
+                ast::StatementKind::TypeDeclaration(declaration) => {
+                    self.resolve_type_declaration(declaration)
+                }
+                _ => self.resolve_statement(statement),
+            }
+        }
+    }
+
+    fn resolve_statements(&mut self, statements: &[Box<ast::Statement>]) {
+        for statement in statements {
+            self.resolve_statement(statement);
+        }
+    }
+
+    fn resolve_statement(&mut self, statement: &ast::Statement) {
+        use ast::StatementKind::*;
+
+        match &statement.kind {
+            Comment(_) => {}
+            Declaration(declaration) => {
+                self.resolve_expression(&declaration.value);
+                let kind = BindingKind::Variable {
+                    mutable: declaration.mutable,
+                };
+                for name in declaration.pattern.names() {
+                    self.declare(name, kind.clone(), &statement.position);
+                }
+            }
+            Assignment(assignment) => {
+                self.resolve_expression(&assignment.value);
+                match &assignment.target.kind {
+                    ast::ExpressionKind::Identifier(name) => {
+                        self.reference(name, &statement.position)
+                    }
+                    _ => self.resolve_expression(&assignment.target),
+                }
+                // members are changed in place, so the variable itself must be mutable
+                if let Some(name) = assignment.target.assigned_variable() {
+                    self.check_mutable(name, None, &statement.position);
+                }
+            }
+            Block(block) => self.resolve_block(block),
+            FunctionDeclaration(function) => {
+                self.declare(&function.name, BindingKind::Function, &statement.position);
+                self.resolve_function(function, &statement.position);
+            }
+            TypeDeclaration(declaration) => {
+                self.declare_variants(&declaration.typ, &statement.position);
+                self.resolve_type_declaration(declaration);
+            }
+            Return(ret) => self.resolve_expression(&ret.value),
+            If(i) => self.resolve_if(i),
+            While(w) => {
+                self.resolve_expression(&w.condition);
+                self.resolve_loop(&w.body);
+            }
+            For(f) => {
+                self.resolve_expression(&f.iterable);
+
+                self.enter_scope(self.current);
+                self.declare(&f.variable, BindingKind::LoopVariable, &statement.position);
+                self.resolve_loop(&f.body);
+                self.leave_scope();
+            }
+            Break => self.check_loop("break", &statement.position),
+            Continue => self.check_loop("continue", &statement.position),
+            Expression(expression) => self.resolve_expression(expression),
+        }
+    }
+
+    fn resolve_type_declaration(&mut self, declaration: &ast::TypeDeclaration) {
+        // default values are evaluated in the scope of the file
+        self.enter_scope(0);
+        self.resolve_type(&declaration.typ);
+        self.leave_scope();
+    }
+
+    /// Declares the variants of an enum type, which are used without the name of the enum.
+    fn declare_variants(&mut self, typ: &ast::Type, position: &Span) {
+        if let ast::Type::Enum(e) = typ {
+            for variant in &e.variants {
+                self.declare(&variant.name, BindingKind::Variant, position);
+            }
+        }
+    }
+
+    fn resolve_block(&mut self, block: &ast::Block) {
+        self.enter_scope(self.current);
+        self.resolve_statements(&block.statements);
+        self.leave_scope();
+    }
+
+    fn resolve_if(&mut self, i: &ast::If) {
+        self.resolve_expression(&i.condition);
+        match &i.binding {
+            Some(binding) => {
+                // the names of `if x := maybe` are only bound in the consequence
+                self.enter_scope(self.current);
+                let kind = BindingKind::Variable { mutable: false };
+                for name in binding.names() {
+                    self.declare(name, kind.clone(), &i.condition.position);
+                }
+                self.resolve_block(&i.consequence);
+                self.leave_scope();
+            }
+            None => self.resolve_block(&i.consequence),
+        }
+        match &i.alternative {
+            Some(ast::Else::If(alternative)) => self.resolve_if(alternative),
+            Some(ast::Else::Block(alternative)) => self.resolve_block(alternative),
+            None => {}
+        }
+    }
+
+    fn resolve_loop(&mut self, body: &ast::Block) {
+        self.loops += 1;
+        self.resolve_block(body);
+        self.loops -= 1;
+    }
+
+    /// Reports a `break` or `continue` that is not inside of a loop.
+    fn check_loop(&mut self, statement: &str, position: &Span) {
+        if self.loops == 0 {
+            self.errors.push(Error::OutsideLoop {
+                statement: statement.into(),
+                position: position.clone(),
+            });
+        }
+    }
+
+    fn resolve_function(&mut self, function: &ast::FunctionDeclaration, position: &Span) {
+        let nested = self.current != 0;
+        self.enter_scope(0);
+        // a nested function can call itself, although it cannot use the other locals
+        // of the enclosing function
+        if nested {
+            self.scopes[self.current].bindings.push(Binding {
+                name: function.name.clone(),
+                kind: BindingKind::Function,
+                position: position.clone(),
+            });
+        }
+        // loops of the enclosing function cannot be left from within this one
+        let loops = std::mem::take(&mut self.loops);
+        // and its lambdas cannot capture the locals of this one
+        let closures = std::mem::take(&mut self.closures);
+
+        self.declare_parameters(&function.typ);
+        self.resolve_statements(&function.body.statements);
+
+        self.closures = closures;
+        self.loops = loops;
+        self.leave_scope();
+    }
+
+    /// Resolves a lambda like a function whose scope is nested in the current one,
+    /// so that its body can use the names of the enclosing functions.
+    fn resolve_lambda(&mut self, lambda: &ast::LambdaExpression, position: &Span) {
+        self.enter_scope(self.current);
+        let loops = std::mem::take(&mut self.loops);
+        self.closures.push((self.current, position.clone()));
+
+        self.declare_parameters(&lambda.typ);
+        self.resolve_statements(&lambda.body.statements);
+
+        self.closures.pop();
+        self.loops = loops;
+        self.leave_scope();
+    }
+
+    fn declare_parameters(&mut self, typ: &ast::FunctionType) {
+        for (i, parameter) in typ.parameters.iter().enumerate() {
+            if typ.parameters[..i].iter().any(|p| p.name == parameter.name) {
+                self.errors.push(Error::DuplicateParameter {
+                    name: parameter.name.clone(),
+                    position: parameter.position.clone(),
+                });
+            } else {
+                self.declare(&parameter.name, BindingKind::Parameter, &parameter.position);
+            }
+        }
+    }
+
+    fn resolve_expression(&mut self, expression: &ast::Expression) {
+        use ast::ExpressionKind::*;
+
+        match &expression.kind {
+            Identifier(name) => self.reference(name, &expression.position),
+            Boolean(_) | Integer(_) | Float(_) | String(_) | NoneLiteral => {}
+            InterpolatedString(parts) => {
+                for part in parts {
+                    if let ast::StringPart::Expression(expression) = part {
+                        self.resolve_expression(expression);
+                    }
+                }
+            }
+            Tuple(elements) | List(elements) => {
+                for element in elements {
+                    self.resolve_expression(element);
+                }
+            }
+            Map(entries) => {
+                for entry in entries {
+                    self.resolve_expression(&entry.key);
+                    self.resolve_expression(&entry.value);
+                }
+            }
+
+            Prefix(prefix) => self.resolve_expression(&prefix.right),
+            Postfix(postfix) => self.resolve_expression(&postfix.left),
+            Lambda(lambda) => self.resolve_lambda(lambda, &expression.position),
+            Infix(infix) => {
+                self.resolve_expression(&infix.left);
+                self.resolve_expression(&infix.right);
+            }
+            Call(call) => {
+                self.resolve_expression(&call.callee);
+                for argument in &call.arguments {
+                    self.resolve_expression(argument);
+                }
+                // `push` and `pop` change a list and `remove` changes a map in place
+                if let Member(member) = &call.callee.kind {
+                    if matches!(member.name.as_str(), "push" | "pop" | "remove") {
+                        if let Some(name) = member.object.assigned_variable() {
+                            self.check_mutable(name, Some(&member.name), &expression.position);
+                        }
+                    }
+                }
+            }
+            Member(member) => self.resolve_expression(&member.object),
+            Index(index) => {
+                self.resolve_expression(&index.object);
+                self.resolve_expression(&index.index);
+            }
+            Struct(s) => {
+                for field in &s.fields {
+                    self.resolve_expression(&field.value);
+                }
+            }
+            Match(m) => {
+                self.resolve_expression(&m.subject);
+                for arm in &m.arms {
+                    self.enter_scope(self.current);
+                    self.resolve_pattern(&arm.pattern, &arm.position);
+                    self.resolve_expression(&arm.body);
+                    self.leave_scope();
+                }
+            }
+        }
+    }
+
+    /// Declares the names bound by the pattern of a match arm. Names of variants
+    /// are references to the variants instead.
+    fn resolve_pattern(&mut self, pattern: &ast::Pattern, position: &Span) {
+        match pattern {
+            ast::Pattern::Identifier(name) => match self.lookup(name) {
+                Some(binding) if binding.kind == BindingKind::Variant => {
+                    self.reference(name, position)
+                }
+                None if RESULT_VARIANTS.contains(&name.as_str()) => {}
+                _ => {
+                    let kind = BindingKind::Variable { mutable: false };
+                    self.declare(name, kind, position);
+                }
+            },
+            ast::Pattern::Wildcard => {}
+            ast::Pattern::Tuple(elements) => {
+                for element in elements {
+                    self.resolve_pattern(element, position);
+                }
+            }
+            ast::Pattern::Variant(variant) => {
+                self.reference(&variant.name, position);
+                for element in &variant.elements {
+                    self.resolve_pattern(element, position);
+                }
+            }
+        }
+    }
+
+    /// Resolves the default values of the fields of struct types.
+    fn resolve_type(&mut self, typ: &ast::Type) {
+        match typ {
+            ast::Type::Struct(s) => {
+                for field in &s.fields {
+                    self.resolve_type(&field.typ);
+                    if let Some(default) = &field.default {
+                        self.resolve_expression(default);
+                    }
+                }
+            }
+            ast::Type::Tuple(t) => {
+                for element in &t.elements {
+                    self.resolve_type(element);
+                }
+            }
+            ast::Type::List(element) | ast::Type::Optional(element) => self.resolve_type(element),
+            ast::Type::Result(result) => {
+                self.resolve_type(&result.value);
+                self.resolve_type(&result.error);
+            }
+            ast::Type::Enum(e) => {
+                for variant in &e.variants {
+                    for element in &variant.elements {
+                        self.resolve_type(element);
+                    }
+                }
+            }
+            ast::Type::Map(map) => {
+                self.resolve_type(&map.key);
+                self.resolve_type(&map.value);
+            }
+            _ => {}
+        }
+    }
+
+    /// Enters a new scope whose parent is `parent`, which is not necessarily the current scope.
+    fn enter_scope(&mut self, parent: usize) {
+        self.scopes.push(Scope {
+            parent: Some(parent),
+            bindings: vec![],
+        });
+        self.stack.push(self.current);
+        self.current = self.scopes.len() - 1;
+    }
+
+    fn leave_scope(&mut self) {
+        self.current = self.stack.pop().expect("left the scope of the file");
+    }
+
+    fn declare(&mut self, name: &str, kind: BindingKind, position: &Span) {
+        if let Some(shadowed) = self.lookup(name) {
+            self.warnings.push(Warning::Shadowing {
+                name: name.into(),
+                position: position.clone(),
+                shadowed: shadowed.position.clone(),
+            });
+        }
+
+        self.scopes[self.current].bindings.push(Binding {
+            name: name.into(),
+            kind,
+            position: position.clone(),
+        });
+    }
+
+    fn reference(&mut self, name: &str, position: &Span) {
+        match self.lookup_with_scope(name) {
+            Some((scope, binding)) => {
+                let declaration = binding.position.clone();
+                self.capture(name, scope, &declaration);
+                self.references.push(Reference {
+                    name: name.into(),
+                    position: position.clone(),
+                    declaration,
+                });
+            }
+            None if RESULT_VARIANTS.contains(&name) || name == PRINT => {}
+            None => self.errors.push(Error::UndefinedName {
+                name: name.into(),
+                position: position.clone(),
+            }),
+        }
+    }
+
+    /// Records a binding declared in `scope` as captured by the enclosing lambdas
+    /// that it is declared outside of. Bindings of the file are never captured.
+    fn capture(&mut self, name: &str, scope: usize, declaration: &Span) {
+        if scope == 0 {
+            return;
+        }
+        let closures: Vec<Span> = self
+            .closures
+            .iter()
+            .filter(|(closure, _)| !self.is_within(scope, *closure))
+            .map(|(_, position)| position.clone())
+            .collect();
+        for closure in closures {
+            let captured = self
+                .captures
+                .iter()
+                .any(|c| c.closure == closure && c.declaration == *declaration);
+            if !captured {
+                self.captures.push(Capture {
+                    name: name.into(),
+                    closure,
+                    declaration: declaration.clone(),
+                });
+            }
+        }
+    }
+
+    /// Reports an assignment to `name`, or a call of the `method` that changes it in place,
+    /// if its binding is not declared with `mut` or if it is captured by the current lambda,
+    /// which only has a copy of it. Parameters and functions can never be assigned to.
+    fn check_mutable(&mut self, name: &str, method: Option<&str>, position: &Span) {
+        let (scope, binding) = match self.lookup_with_scope(name) {
+            Some(found) => found,
+            None => return,
+        };
+
+        let captured = match self.closures.last() {
+            Some((closure, _)) => scope != 0 && !self.is_within(scope, *closure),
+            None => false,
+        };
+        let name = name.to_string();
+        let position = position.clone();
+        let declaration = binding.position.clone();
+        let kind = binding.kind.clone();
+        let mutable = kind == BindingKind::Variable { mutable: true };
+
+        let error = match (captured, method) {
+            (true, None) => Error::CapturedAssignment {
+                name,
+                position,
+                declaration,
+            },
+            (true, Some(method)) => Error::CapturedMutation {
+                name,
+                method: method.into(),
+                position,
+                declaration,
+            },
+            (false, _) if mutable => return,
+            (false, None) => Error::ImmutableAssignment {
+                name,
+                kind,
+                position,
+                declaration,
+            },
+            (false, Some(method)) => Error::ImmutableMutation {
+                name,
+                kind,
+                method: method.into(),
+                position,
+                declaration,
+            },
+        };
+        self.errors.push(error);
+    }
+
+    /// Returns the most recent binding of `name` visible from the current scope.
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        return self.lookup_with_scope(name).map(|(_, binding)| binding);
+    }
+
+    /// Returns the most recent binding of `name` visible from the current scope
+    /// together with the index of the scope that declares it.
+    fn lookup_with_scope(&self, name: &str) -> Option<(usize, &Binding)> {
+        let mut scope = Some(self.current);
+        while let Some(index) = scope {
+            let binding = self.scopes[index]
+                .bindings
+                .iter()
+                .rev()
+                .find(|binding| binding.name == name);
+            if let Some(binding) = binding {
+                return Some((index, binding));
+            }
+            scope = self.scopes[index].parent;
+        }
+        return None;
+    }
+
+    /// Returns whether `scope` is `ancestor` or nested in it.
+    fn is_within(&self, scope: usize, ancestor: usize) -> bool {
+        let mut scope = Some(scope);
+        while let Some(index) = scope {
+            if index == ancestor {
+                return true;
+            }
+            scope = self.scopes[index].parent;
+        }
+        return false;
+    }
+}
+
+/// Resolves the names of the file.
+pub fn resolve(file: &ast::File) -> Result<Resolution, Vec<Error>> {
+    return Resolver::new().resolve(file);
+}
+
+/// Returns the bindings that the lambdas of the file capture, even if the file has errors.
+/// The interpreter copies them into closures without requiring a resolved file.
+pub fn captures(file: &ast::File) -> Vec<Capture> {
+    let mut resolver = Resolver::new();
+    resolver.resolve_file(file);
+    return resolver.captures;
+}
+
+#[cfg(test)]
+#[path = "tests/resolver.rs"]
+mod tests;
@@ -0,0 +1,69 @@
+use crate::token::Span;
+
+use super::Warning;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BindingKind {
+    Variable {
+        mutable: bool,
+    },
+    Parameter,
+    /// Variable of a `for` loop, which is bound to each element in turn.
+    LoopVariable,
+    Function,
+    /// Variant of an enum, which is declared with the enum.
+    Variant,
+}
+
+/// A name introduced by a declaration, parameter, function declaration or enum declaration.
+/// `position` is the span of the statement that declares it, or of the parameter itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub position: Span,
+}
+
+/// A node of the scope tree. The root scope at index 0 is the scope of the file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scope {
+    pub parent: Option<usize>,
+    pub bindings: Vec<Binding>,
+}
+
+/// A use of a name, linked to the binding it refers to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reference {
+    pub name: String,
+    pub position: Span,
+    pub declaration: Span,
+}
+
+/// A binding of an enclosing function that a lambda uses, linked to the lambda at `closure`.
+/// Lambdas nested in the lambda that use the binding capture it as well.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capture {
+    pub name: String,
+    pub closure: Span,
+    pub declaration: Span,
+}
+
+/// Result of resolving the names of a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resolution {
+    pub scopes: Vec<Scope>,
+    pub references: Vec<Reference>,
+    pub captures: Vec<Capture>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Resolution {
+    /// Returns the span of the declaration that the name used at `position` refers to.
+    pub fn declaration(&self, position: &Span) -> Option<&Span> {
+        return self
+            .references
+            .iter()
+            .find(|reference| reference.position == *position)
+            .map(|reference| &reference.declaration);
+    }
+}
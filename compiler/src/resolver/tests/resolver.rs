@@ -0,0 +1,397 @@
+use super::*;
+
+use pretty_assertions::assert_eq;
+
+use crate::parser::Parser;
+use crate::token::Span;
+
+#[test]
+fn resolve_references() {
+    let source = r#"
+limit := 10
+
+fn main(): int {
+  mut a := limit
+  {
+    b := a + limit
+    a = b
+  }
+  return a
+}
+
+fn helper(limit: int): int {
+  return limit
+}
+"#;
+    let resolution = resolve(&Parser::new(source).parse().unwrap()).unwrap();
+
+    // (name, line of the use, line of the declaration)
+    let got: Vec<(&str, usize, usize)> = resolution
+        .references
+        .iter()
+        .map(|r| {
+            (
+                r.name.as_str(),
+                r.position.start.line,
+                r.declaration.start.line,
+            )
+        })
+        .collect();
+    let want = vec![
+        ("limit", 5, 2),
+        ("a", 7, 5),
+        ("limit", 7, 2),
+        ("b", 8, 7),
+        ("a", 8, 5),
+        ("a", 10, 5),
+        ("limit", 14, 13),
+    ];
+    assert_eq!(want, got);
+
+    // file -> main -> block, file -> helper
+    let parents: Vec<Option<usize>> = resolution.scopes.iter().map(|s| s.parent).collect();
+    assert_eq!(vec![None, Some(0), Some(1), Some(0)], parents);
+}
+
+#[test]
+fn resolve_function_scopes() {
+    let source = r#"
+fn outer() {
+  local := 1
+  fn inner(): int {
+    return outer
+  }
+  inner
+}
+"#;
+    let resolution = resolve(&Parser::new(source).parse().unwrap()).unwrap();
+    let got: Vec<(&str, usize, usize)> = resolution
+        .references
+        .iter()
+        .map(|r| {
+            (
+                r.name.as_str(),
+                r.position.start.line,
+                r.declaration.start.line,
+            )
+        })
+        .collect();
+    assert_eq!(vec![("outer", 5, 2), ("inner", 7, 4)], got);
+
+    let source = source.replace("return outer", "return local");
+    let got = resolve(&Parser::new(&source).parse().unwrap());
+    assert_eq!(
+        Err(vec![Error::UndefinedName {
+            name: "local".into(),
+            position: Span::from((58, 5, 47), (62, 5, 47)),
+        }]),
+        got
+    );
+
+    let source = r#"
+fn main(): int {
+  fn fact(n: int): int {
+    if n < 2 {
+      return 1
+    }
+    return n * fact(n - 1)
+  }
+  return fact(5)
+}
+"#;
+    let resolution = resolve(&Parser::new(source).parse().unwrap()).unwrap();
+    let got: Vec<(&str, usize, usize)> = resolution
+        .references
+        .iter()
+        .map(|r| {
+            (
+                r.name.as_str(),
+                r.position.start.line,
+                r.declaration.start.line,
+            )
+        })
+        .collect();
+    let want = vec![
+        ("n", 4, 3),
+        ("n", 7, 3),
+        ("fact", 7, 3),
+        ("n", 7, 3),
+        ("fact", 9, 3),
+    ];
+    assert_eq!(want, got);
+}
+
+#[test]
+fn resolve_captures() {
+    let source = r#"
+limit := 10
+
+fn outer(n: int) {
+  total := 0
+  add := fn (x: int): int {
+    y := x + n
+    get := fn (): int {
+      return total + y + limit
+    }
+    return get()
+  }
+  fn inner(): int {
+    return limit
+  }
+}
+"#;
+    let resolution = resolve(&Parser::new(source).parse().unwrap()).unwrap();
+
+    // (name, line of the lambda, line of the declaration)
+    let got: Vec<(&str, usize, usize)> = resolution
+        .captures
+        .iter()
+        .map(|c| {
+            (
+                c.name.as_str(),
+                c.closure.start.line,
+                c.declaration.start.line,
+            )
+        })
+        .collect();
+    let want = vec![("n", 6, 4), ("total", 6, 5), ("total", 8, 5), ("y", 8, 7)];
+    assert_eq!(want, got);
+}
+
+#[test]
+fn resolve_errors() {
+    let cases = &[
+        (
+            "a = 1\nb := c + b",
+            vec![
+                Error::UndefinedName {
+                    name: "a".into(),
+                    position: Span::from((0, 1, 0), (4, 1, 0)),
+                },
+                Error::UndefinedName {
+                    name: "c".into(),
+                    position: Span::from((11, 2, 6), (11, 2, 6)),
+                },
+                Error::UndefinedName {
+                    name: "b".into(),
+                    position: Span::from((15, 2, 6), (15, 2, 6)),
+                },
+            ],
+        ),
+        (
+            "fn f(a: int, b: int, a: float) {\n}",
+            vec![Error::DuplicateParameter {
+                name: "a".into(),
+                position: Span::from((21, 1, 0), (28, 1, 0)),
+            }],
+        ),
+        (
+            "a := 1\na = 2\nmut b := 1\nb = 2",
+            vec![Error::ImmutableAssignment {
+                name: "a".into(),
+                kind: BindingKind::Variable { mutable: false },
+                position: Span::from((7, 2, 7), (11, 2, 7)),
+                declaration: Span::from((0, 1, 0), (5, 1, 0)),
+            }],
+        ),
+        (
+            "(a, _) := (1, 2)\na = 3\nb = _",
+            vec![
+                Error::ImmutableAssignment {
+                    name: "a".into(),
+                    kind: BindingKind::Variable { mutable: false },
+                    position: Span::from((17, 2, 17), (21, 2, 17)),
+                    declaration: Span::from((0, 1, 0), (15, 1, 0)),
+                },
+                Error::UndefinedName {
+                    name: "_".into(),
+                    position: Span::from((27, 3, 23), (27, 3, 23)),
+                },
+                Error::UndefinedName {
+                    name: "b".into(),
+                    position: Span::from((23, 3, 23), (27, 3, 23)),
+                },
+            ],
+        ),
+        (
+            "p := P{ x: 1 }\np.x = 3\ntype P = { x: int = y }\ny := 2",
+            vec![
+                Error::ImmutableAssignment {
+                    name: "p".into(),
+                    kind: BindingKind::Variable { mutable: false },
+                    position: Span::from((15, 2, 15), (21, 2, 15)),
+                    declaration: Span::from((0, 1, 0), (13, 1, 0)),
+                },
+                Error::UndefinedName {
+                    name: "y".into(),
+                    position: Span::from((43, 3, 23), (43, 3, 23)),
+                },
+            ],
+        ),
+        (
+            "xs := [1]\nxs.push(2)\nxs.len()\nm := {}\nm.contains(1)\nm.remove(1)",
+            vec![
+                Error::ImmutableMutation {
+                    name: "xs".into(),
+                    kind: BindingKind::Variable { mutable: false },
+                    method: "push".into(),
+                    position: Span::from((10, 2, 10), (19, 2, 10)),
+                    declaration: Span::from((0, 1, 0), (8, 1, 0)),
+                },
+                Error::ImmutableMutation {
+                    name: "m".into(),
+                    kind: BindingKind::Variable { mutable: false },
+                    method: "remove".into(),
+                    position: Span::from((52, 6, 52), (62, 6, 52)),
+                    declaration: Span::from((30, 4, 30), (36, 4, 30)),
+                },
+            ],
+        ),
+        (
+            "m := match A(1) {\n  A(n) => n\n  B => n\n  C(_) => 0\n}\nenum E { A(int), B }",
+            vec![
+                Error::UndefinedName {
+                    name: "n".into(),
+                    position: Span::from((37, 3, 30), (37, 3, 30)),
+                },
+                Error::UndefinedName {
+                    name: "C".into(),
+                    position: Span::from((41, 4, 39), (49, 4, 39)),
+                },
+            ],
+        ),
+        (
+            "r := Ok(1)\nmatch r {\n  Ok(v) => v\n  Err(e) => f(e)\n}",
+            vec![Error::UndefinedName {
+                name: "f".into(),
+                position: Span::from((46, 4, 34), (46, 4, 34)),
+            }],
+        ),
+        (
+            "mut a := 1\n{\n  mut b := [2]\n  f := fn (c: int) {\n    a += 1\n    b.push(3)\n    c = 4\n  }\n}",
+            vec![
+                Error::CapturedMutation {
+                    name: "b".into(),
+                    method: "push".into(),
+                    position: Span::from((64, 6, 60), (72, 6, 60)),
+                    declaration: Span::from((15, 3, 13), (26, 3, 13)),
+                },
+                Error::ImmutableAssignment {
+                    name: "c".into(),
+                    kind: BindingKind::Parameter,
+                    position: Span::from((78, 7, 74), (82, 7, 74)),
+                    declaration: Span::from((39, 4, 28), (44, 4, 28)),
+                },
+            ],
+        ),
+        (
+            "m := none\nif x := m {\n  x = 1\n} else {\n  x\n}\nx",
+            vec![
+                Error::ImmutableAssignment {
+                    name: "x".into(),
+                    kind: BindingKind::Variable { mutable: false },
+                    position: Span::from((24, 3, 22), (28, 3, 22)),
+                    declaration: Span::from((18, 2, 10), (18, 2, 10)),
+                },
+                Error::UndefinedName {
+                    name: "x".into(),
+                    position: Span::from((41, 5, 39), (41, 5, 39)),
+                },
+                Error::UndefinedName {
+                    name: "x".into(),
+                    position: Span::from((45, 7, 45), (45, 7, 45)),
+                },
+            ],
+        ),
+        (
+            "a := 1\na += 2",
+            vec![Error::ImmutableAssignment {
+                name: "a".into(),
+                kind: BindingKind::Variable { mutable: false },
+                position: Span::from((7, 2, 7), (12, 2, 7)),
+                declaration: Span::from((0, 1, 0), (5, 1, 0)),
+            }],
+        ),
+        (
+            "fn f(a: int) {\n  a = 1\n  f = f\n}",
+            vec![
+                Error::ImmutableAssignment {
+                    name: "a".into(),
+                    kind: BindingKind::Parameter,
+                    position: Span::from((17, 2, 15), (21, 2, 15)),
+                    declaration: Span::from((5, 1, 0), (10, 1, 0)),
+                },
+                Error::ImmutableAssignment {
+                    name: "f".into(),
+                    kind: BindingKind::Function,
+                    position: Span::from((25, 3, 23), (29, 3, 23)),
+                    declaration: Span::from((0, 1, 0), (31, 4, 31)),
+                },
+            ],
+        ),
+        (
+            "break\nfn f() {\n  while true {\n    fn g() {\n      continue\n    }\n  }\n}",
+            vec![
+                Error::OutsideLoop {
+                    statement: "break".into(),
+                    position: Span::from((0, 1, 0), (4, 1, 0)),
+                },
+                Error::OutsideLoop {
+                    statement: "continue".into(),
+                    position: Span::from((49, 5, 43), (56, 5, 43)),
+                },
+            ],
+        ),
+        (
+            "for x in [[1]] {\n  x = [2]\n  x.push(3)\n}",
+            vec![
+                Error::ImmutableAssignment {
+                    name: "x".into(),
+                    kind: BindingKind::LoopVariable,
+                    position: Span::from((19, 2, 17), (25, 2, 17)),
+                    declaration: Span::from((0, 1, 0), (39, 4, 39)),
+                },
+                Error::ImmutableMutation {
+                    name: "x".into(),
+                    kind: BindingKind::LoopVariable,
+                    method: "push".into(),
+                    position: Span::from((29, 3, 27), (37, 3, 27)),
+                    declaration: Span::from((0, 1, 0), (39, 4, 39)),
+                },
+            ],
+        ),
+    ];
+
+    for case in cases {
+        let file = Parser::new(case.0).parse().unwrap();
+        assert_eq!(Err(case.1.clone()), resolve(&file), "{}", case.0);
+    }
+}
+
+#[test]
+fn resolve_shadowing() {
+    let source = r#"
+a := 1
+fn f(a: int) {
+  {
+    a := 2
+  }
+}
+a := 3
+"#;
+    let resolution = resolve(&Parser::new(source).parse().unwrap()).unwrap();
+
+    // (name, line of the declaration, line of the shadowed declaration)
+    let got: Vec<(&str, usize, usize)> = resolution
+        .warnings
+        .iter()
+        .map(|w| match w {
+            Warning::Shadowing {
+                name,
+                position,
+                shadowed,
+            } => (name.as_str(), position.start.line, shadowed.start.line),
+        })
+        .collect();
+    assert_eq!(vec![("a", 3, 2), ("a", 5, 3), ("a", 8, 2)], got);
+}
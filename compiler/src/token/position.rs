@@ -2,7 +2,7 @@ use core::fmt;
 
 /// Position of a single character in the source.
 /// `offset` and `line_offset` start at 0. `line` starts at 1.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Position {
     pub offset: usize,
     pub line: usize,
@@ -45,7 +45,7 @@ impl fmt::Display for Position {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Span {
     pub start: Position,
     pub end: Position,
@@ -40,14 +40,28 @@ pub enum Token {
     Integer(String),
     /// 3.14159265359
     Float(String),
-    /// "Hello World"
+    /// "Hello World", "Hello\n", r#"Hello "World""#
+    ///
+    /// Spelled as in the source, including the delimiters and escape sequences.
     String(String),
+    /// "Hello {name}"
+    InterpolatedString(Vec<StringPart>),
 
     /* Operators */
     /// :=
     Declare,
     /// =
     Assign,
+    /// +=
+    PlusAssign,
+    /// -=
+    MinusAssign,
+    /// *=
+    AsteriskAssign,
+    /// /=
+    SlashAssign,
+    /// %=
+    PercentAssign,
     /// .
     Dot,
     /// +
@@ -62,6 +76,18 @@ pub enum Token {
     Percent,
     /// !
     ExclamationMark,
+    /// &
+    Ampersand,
+    /// |
+    Pipe,
+    /// ^
+    Caret,
+    /// ~
+    Tilde,
+    /// <<
+    ShiftLeft,
+    /// \>>
+    ShiftRight,
     /// ==
     Equal,
     /// !=
@@ -70,6 +96,20 @@ pub enum Token {
     LessThan,
     /// \>
     GreaterThan,
+    /// <=
+    LessThanOrEqual,
+    /// \>=
+    GreaterThanOrEqual,
+    /// &&
+    And,
+    /// ||
+    Or,
+    /// =>
+    Arrow,
+    /// ??
+    Coalesce,
+    /// ?
+    QuestionMark,
 
     /* Delimiters */
     /// ,
@@ -102,6 +142,37 @@ pub enum Token {
     Function,
     /// return
     Return,
+    /// if
+    If,
+    /// else
+    Else,
+    /// while
+    While,
+    /// for
+    For,
+    /// in
+    In,
+    /// break
+    Break,
+    /// continue
+    Continue,
+    /// type
+    Type,
+    /// enum
+    Enum,
+    /// match
+    Match,
+    /// none
+    None,
+}
+
+/// Part of an interpolated string literal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StringPart {
+    /// Text between the embedded expressions, spelled as in the source.
+    Literal(String),
+    /// Source of an embedded `{expression}` and the position of its first character.
+    Expression { source: String, position: Position },
 }
 
 impl Token {
@@ -112,6 +183,17 @@ impl Token {
             "mut" => Some(Token::Mutable),
             "fn" => Some(Token::Function),
             "return" => Some(Token::Return),
+            "if" => Some(Token::If),
+            "else" => Some(Token::Else),
+            "while" => Some(Token::While),
+            "for" => Some(Token::For),
+            "in" => Some(Token::In),
+            "break" => Some(Token::Break),
+            "continue" => Some(Token::Continue),
+            "type" => Some(Token::Type),
+            "enum" => Some(Token::Enum),
+            "match" => Some(Token::Match),
+            "none" => Some(Token::None),
 
             _ => None,
         }
@@ -131,11 +213,26 @@ impl fmt::Display for Token {
             Identifier(name) => write!(f, "{}", name),
             Integer(integer) => write!(f, "{}", integer),
             Float(float) => write!(f, "{}", float),
-            String(string) => write!(f, "\"{}\"", string),
+            String(string) => write!(f, "{}", string),
+            InterpolatedString(parts) => {
+                write!(f, "\"")?;
+                for part in parts {
+                    match part {
+                        StringPart::Literal(literal) => write!(f, "{}", literal)?,
+                        StringPart::Expression { source, .. } => write!(f, "{{{}}}", source)?,
+                    }
+                }
+                write!(f, "\"")
+            }
 
             /* Operators */
             Declare => write!(f, ":="),
             Assign => write!(f, "="),
+            PlusAssign => write!(f, "+="),
+            MinusAssign => write!(f, "-="),
+            AsteriskAssign => write!(f, "*="),
+            SlashAssign => write!(f, "/="),
+            PercentAssign => write!(f, "%="),
             Dot => write!(f, "."),
             Plus => write!(f, "+"),
             Minus => write!(f, "-"),
@@ -143,10 +240,23 @@ impl fmt::Display for Token {
             Slash => write!(f, "/"),
             Percent => write!(f, "%"),
             ExclamationMark => write!(f, "!"),
+            Ampersand => write!(f, "&"),
+            Pipe => write!(f, "|"),
+            Caret => write!(f, "^"),
+            Tilde => write!(f, "~"),
+            ShiftLeft => write!(f, "<<"),
+            ShiftRight => write!(f, ">>"),
             Equal => write!(f, "=="),
             NotEqual => write!(f, "!="),
             LessThan => write!(f, "<"),
             GreaterThan => write!(f, ">"),
+            LessThanOrEqual => write!(f, "<="),
+            GreaterThanOrEqual => write!(f, ">="),
+            And => write!(f, "&&"),
+            Or => write!(f, "||"),
+            Arrow => write!(f, "=>"),
+            Coalesce => write!(f, "??"),
+            QuestionMark => write!(f, "?"),
 
             /* Delimiters */
             Comma => write!(f, ","),
@@ -165,6 +275,17 @@ impl fmt::Display for Token {
             Mutable => write!(f, "mut"),
             Function => write!(f, "fn"),
             Return => write!(f, "return"),
+            If => write!(f, "if"),
+            Else => write!(f, "else"),
+            While => write!(f, "while"),
+            For => write!(f, "for"),
+            In => write!(f, "in"),
+            Break => write!(f, "break"),
+            Continue => write!(f, "continue"),
+            Type => write!(f, "type"),
+            Enum => write!(f, "enum"),
+            Match => write!(f, "match"),
+            None => write!(f, "none"),
         }
     }
 }